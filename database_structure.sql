@@ -30,12 +30,128 @@ CREATE TABLE `bot_admins` (
 
 CREATE TABLE `bug_reports` (
   `bug_id` int(11) NOT NULL,
+  `guild_id` bigint(20) UNSIGNED NOT NULL,
   `channel_id` bigint(20) NOT NULL,
   `message_id` bigint(20) NOT NULL,
   `title` tinytext CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
-  `status` enum('closed','forgevanilla','resolved','low','medium','high','critical') CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL DEFAULT 'medium',
+  `status` varchar(32) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL DEFAULT 'medium',
   `timestamp` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
   `category` enum('fa_renewed','fa_legacy','sa_renewed','sa_legacy') CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL DEFAULT 'fa_renewed',
+  `report_content` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL DEFAULT (''),
+  `duplicate_of` int(11) DEFAULT NULL,
+  `assignee` bigint(20) UNSIGNED DEFAULT NULL,
+  `thread_id` bigint(20) UNSIGNED DEFAULT NULL,
+  `affected_version` varchar(32) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci DEFAULT NULL,
+  `fixed_in` varchar(32) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci DEFAULT NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `bug_reports__affected_users`
+--
+
+CREATE TABLE `bug_reports__affected_users` (
+  `bug_id` int(11) NOT NULL,
+  `user_id` bigint(20) UNSIGNED NOT NULL,
+  `timestamp` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `bug_reports__crash_logs`
+--
+
+CREATE TABLE `bug_reports__crash_logs` (
+  `bug_id` int(11) NOT NULL,
+  `exception` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci,
+  `suspected_mods` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `minecraft_version` tinytext CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci,
+  `forge_version` tinytext CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci,
+  `mod_version` tinytext CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci,
+  `mod_frames` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `signature` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `bug_reports__crash_sightings`
+--
+
+CREATE TABLE `bug_reports__crash_sightings` (
+  `bug_id` int(11) NOT NULL,
+  `channel_id` bigint(20) UNSIGNED NOT NULL,
+  `message_id` bigint(20) UNSIGNED NOT NULL,
+  `user_id` bigint(20) UNSIGNED NOT NULL,
+  `timestamp` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `bug_reports__digests`
+--
+
+CREATE TABLE `bug_reports__digests` (
+  `guild_id` bigint(20) UNSIGNED NOT NULL,
+  `channel_id` bigint(20) UNSIGNED NOT NULL,
+  `stale_days` int(10) UNSIGNED NOT NULL DEFAULT '30',
+  `last_digest` timestamp NULL DEFAULT NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `bug_reports__history`
+--
+
+CREATE TABLE `bug_reports__history` (
+  `history_id` int(10) UNSIGNED NOT NULL,
+  `bug_id` int(11) NOT NULL,
+  `user_id` bigint(20) UNSIGNED NOT NULL,
+  `event` tinytext CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `old_value` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `new_value` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `timestamp` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `bug_reports__info_requests`
+--
+
+CREATE TABLE `bug_reports__info_requests` (
+  `bug_id` int(11) NOT NULL,
+  `user_id` bigint(20) UNSIGNED NOT NULL,
+  `timestamp` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  `reminded` tinyint(1) NOT NULL DEFAULT '0'
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `bug_reports__issues`
+--
+
+CREATE TABLE `bug_reports__issues` (
+  `bug_id` int(11) NOT NULL,
+  `issue_number` int(10) UNSIGNED NOT NULL,
+  `state` enum('open','completed','not_planned') CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL DEFAULT 'open'
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `bug_reports__labels`
+--
+
+CREATE TABLE `bug_reports__labels` (
+  `label_id` int(10) UNSIGNED NOT NULL,
+  `bug_id` int(11) NOT NULL,
+  `label` varchar(32) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
 
 -- --------------------------------------------------------
@@ -65,6 +181,86 @@ CREATE TABLE `bug_reports__notifications` (
 
 -- --------------------------------------------------------
 
+--
+-- Table structure for table `bug_reports__notification_queue`
+--
+
+CREATE TABLE `bug_reports__notification_queue` (
+  `queue_id` int(10) UNSIGNED NOT NULL,
+  `user_id` bigint(20) UNSIGNED NOT NULL,
+  `bug_id` int(10) UNSIGNED NOT NULL,
+  `message` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `timestamp` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `bug_reports__notification_settings`
+--
+
+CREATE TABLE `bug_reports__notification_settings` (
+  `user_id` bigint(20) UNSIGNED NOT NULL,
+  `mode` enum('instant','digest','status','resolved') CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL DEFAULT 'instant',
+  `last_digest` timestamp NULL DEFAULT NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `bug_trackers`
+--
+
+CREATE TABLE `bug_trackers` (
+  `guild_id` bigint(20) UNSIGNED NOT NULL,
+  `prefix` varchar(8) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL DEFAULT 'BUG',
+  `channel_id` bigint(20) UNSIGNED DEFAULT NULL,
+  `categories` set('fa_renewed','fa_legacy','sa_renewed','sa_legacy') CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL DEFAULT 'fa_renewed,fa_legacy,sa_renewed,sa_legacy',
+  `info_reminder_days` int(10) UNSIGNED NOT NULL DEFAULT '7',
+  `info_close_days` int(10) UNSIGNED NOT NULL DEFAULT '30'
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `bug_trackers__issue_sync`
+--
+
+CREATE TABLE `bug_trackers__issue_sync` (
+  `guild_id` bigint(20) UNSIGNED NOT NULL,
+  `repository` varchar(100) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `api_url` varchar(255) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci DEFAULT NULL,
+  `last_sync` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `bug_trackers__moderators`
+--
+
+CREATE TABLE `bug_trackers__moderators` (
+  `guild_id` bigint(20) UNSIGNED NOT NULL,
+  `user_id` bigint(20) UNSIGNED NOT NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `bug_trackers__statuses`
+--
+
+CREATE TABLE `bug_trackers__statuses` (
+  `guild_id` bigint(20) UNSIGNED NOT NULL,
+  `name` varchar(32) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `colour` int(10) UNSIGNED NOT NULL DEFAULT 0,
+  `marker` varchar(64) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `weight` int(11) NOT NULL DEFAULT 0,
+  `open` tinyint(1) NOT NULL DEFAULT 1
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
 --
 -- Table structure for table `channel_blacklist`
 --
@@ -91,6 +287,22 @@ CREATE TABLE `custom_commands` (
 
 -- --------------------------------------------------------
 
+--
+-- Table structure for table `custom_commands__revisions`
+--
+
+CREATE TABLE `custom_commands__revisions` (
+  `server_id` bigint(20) NOT NULL,
+  `name` varchar(255) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `revision` int(10) UNSIGNED NOT NULL,
+  `command_json` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `documentation` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `author_id` bigint(20) UNSIGNED NOT NULL,
+  `timestamp` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
 --
 -- Table structure for table `floppa_images`
 --
@@ -212,13 +424,72 @@ ALTER TABLE `bot_admins`
 -- Indexes for table `bug_reports`
 --
 ALTER TABLE `bug_reports`
+  ADD PRIMARY KEY (`bug_id`),
+  ADD KEY `guild_id` (`guild_id`),
+  ADD KEY `affected_version` (`affected_version`),
+  ADD KEY `fixed_in` (`fixed_in`),
+  ADD FULLTEXT KEY `title` (`title`),
+  ADD FULLTEXT KEY `title_content` (`title`,`report_content`);
+
+--
+-- Indexes for table `bug_reports__affected_users`
+--
+ALTER TABLE `bug_reports__affected_users`
+  ADD PRIMARY KEY (`bug_id`,`user_id`);
+
+--
+-- Indexes for table `bug_reports__crash_logs`
+--
+ALTER TABLE `bug_reports__crash_logs`
+  ADD PRIMARY KEY (`bug_id`),
+  ADD KEY `signature` (`signature`(255));
+
+--
+-- Indexes for table `bug_reports__crash_sightings`
+--
+ALTER TABLE `bug_reports__crash_sightings`
+  ADD PRIMARY KEY (`bug_id`,`message_id`);
+
+--
+-- Indexes for table `bug_reports__digests`
+--
+ALTER TABLE `bug_reports__digests`
+  ADD PRIMARY KEY (`guild_id`);
+
+--
+-- Indexes for table `bug_reports__history`
+--
+ALTER TABLE `bug_reports__history`
+  ADD PRIMARY KEY (`history_id`),
+  ADD KEY `bug_id` (`bug_id`);
+
+--
+-- Indexes for table `bug_reports__info_requests`
+--
+ALTER TABLE `bug_reports__info_requests`
+  ADD PRIMARY KEY (`bug_id`),
+  ADD KEY `user_id` (`user_id`);
+
+--
+-- Indexes for table `bug_reports__issues`
+--
+ALTER TABLE `bug_reports__issues`
   ADD PRIMARY KEY (`bug_id`);
 
+--
+-- Indexes for table `bug_reports__labels`
+--
+ALTER TABLE `bug_reports__labels`
+  ADD PRIMARY KEY (`label_id`),
+  ADD UNIQUE KEY `bug_label` (`bug_id`,`label`),
+  ADD KEY `label` (`label`);
+
 --
 -- Indexes for table `bug_reports__links`
 --
 ALTER TABLE `bug_reports__links`
-  ADD PRIMARY KEY (`link_id`);
+  ADD PRIMARY KEY (`link_id`),
+  ADD FULLTEXT KEY `link_title` (`link_title`);
 
 --
 -- Indexes for table `bug_reports__notifications`
@@ -226,6 +497,43 @@ ALTER TABLE `bug_reports__links`
 ALTER TABLE `bug_reports__notifications`
   ADD PRIMARY KEY (`notification_id`);
 
+--
+-- Indexes for table `bug_reports__notification_queue`
+--
+ALTER TABLE `bug_reports__notification_queue`
+  ADD PRIMARY KEY (`queue_id`),
+  ADD KEY `user_id` (`user_id`);
+
+--
+-- Indexes for table `bug_reports__notification_settings`
+--
+ALTER TABLE `bug_reports__notification_settings`
+  ADD PRIMARY KEY (`user_id`);
+
+--
+-- Indexes for table `bug_trackers`
+--
+ALTER TABLE `bug_trackers`
+  ADD PRIMARY KEY (`guild_id`);
+
+--
+-- Indexes for table `bug_trackers__issue_sync`
+--
+ALTER TABLE `bug_trackers__issue_sync`
+  ADD PRIMARY KEY (`guild_id`);
+
+--
+-- Indexes for table `bug_trackers__moderators`
+--
+ALTER TABLE `bug_trackers__moderators`
+  ADD PRIMARY KEY (`guild_id`,`user_id`);
+
+--
+-- Indexes for table `bug_trackers__statuses`
+--
+ALTER TABLE `bug_trackers__statuses`
+  ADD PRIMARY KEY (`guild_id`,`name`);
+
 --
 -- Indexes for table `channel_blacklist`
 --
@@ -238,6 +546,12 @@ ALTER TABLE `channel_blacklist`
 ALTER TABLE `custom_commands`
   ADD PRIMARY KEY (`command_id`);
 
+--
+-- Indexes for table `custom_commands__revisions`
+--
+ALTER TABLE `custom_commands__revisions`
+  ADD PRIMARY KEY (`server_id`,`name`,`revision`);
+
 --
 -- Indexes for table `floppa_images`
 --
@@ -308,6 +622,18 @@ ALTER TABLE `bot_admins`
 ALTER TABLE `bug_reports`
   MODIFY `bug_id` int(11) NOT NULL AUTO_INCREMENT;
 
+--
+-- AUTO_INCREMENT for table `bug_reports__history`
+--
+ALTER TABLE `bug_reports__history`
+  MODIFY `history_id` int(10) UNSIGNED NOT NULL AUTO_INCREMENT;
+
+--
+-- AUTO_INCREMENT for table `bug_reports__labels`
+--
+ALTER TABLE `bug_reports__labels`
+  MODIFY `label_id` int(10) UNSIGNED NOT NULL AUTO_INCREMENT;
+
 --
 -- AUTO_INCREMENT for table `bug_reports__links`
 --
@@ -320,6 +646,12 @@ ALTER TABLE `bug_reports__links`
 ALTER TABLE `bug_reports__notifications`
   MODIFY `notification_id` int(10) UNSIGNED NOT NULL AUTO_INCREMENT;
 
+--
+-- AUTO_INCREMENT for table `bug_reports__notification_queue`
+--
+ALTER TABLE `bug_reports__notification_queue`
+  MODIFY `queue_id` int(10) UNSIGNED NOT NULL AUTO_INCREMENT;
+
 --
 -- AUTO_INCREMENT for table `channel_blacklist`
 --
@@ -0,0 +1,91 @@
+-- Adds the bug report contents, duplicates, assignees, discussion threads,
+-- versions, history, labels, digests and crash logs.
+--
+-- Existing bug reports get an empty content, and no history is recorded
+-- for them.
+
+ALTER TABLE `bug_reports`
+  ADD `report_content` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL DEFAULT (''),
+  ADD `duplicate_of` int(11) DEFAULT NULL,
+  ADD `assignee` bigint(20) UNSIGNED DEFAULT NULL,
+  ADD `thread_id` bigint(20) UNSIGNED DEFAULT NULL,
+  ADD `affected_version` varchar(32) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci DEFAULT NULL,
+  ADD `fixed_in` varchar(32) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci DEFAULT NULL,
+  ADD KEY `affected_version` (`affected_version`),
+  ADD KEY `fixed_in` (`fixed_in`);
+
+ALTER TABLE `bug_reports`
+  ADD FULLTEXT KEY `title` (`title`);
+
+ALTER TABLE `bug_reports`
+  ADD FULLTEXT KEY `title_content` (`title`,`report_content`);
+
+ALTER TABLE `bug_reports__links`
+  ADD FULLTEXT KEY `link_title` (`link_title`);
+
+CREATE TABLE `bug_reports__history` (
+  `history_id` int(10) UNSIGNED NOT NULL,
+  `bug_id` int(11) NOT NULL,
+  `user_id` bigint(20) UNSIGNED NOT NULL,
+  `event` tinytext CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `old_value` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `new_value` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `timestamp` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+ALTER TABLE `bug_reports__history`
+  ADD PRIMARY KEY (`history_id`),
+  ADD KEY `bug_id` (`bug_id`);
+
+ALTER TABLE `bug_reports__history`
+  MODIFY `history_id` int(10) UNSIGNED NOT NULL AUTO_INCREMENT;
+
+CREATE TABLE `bug_reports__labels` (
+  `label_id` int(10) UNSIGNED NOT NULL,
+  `bug_id` int(11) NOT NULL,
+  `label` varchar(32) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+ALTER TABLE `bug_reports__labels`
+  ADD PRIMARY KEY (`label_id`),
+  ADD UNIQUE KEY `bug_label` (`bug_id`,`label`),
+  ADD KEY `label` (`label`);
+
+ALTER TABLE `bug_reports__labels`
+  MODIFY `label_id` int(10) UNSIGNED NOT NULL AUTO_INCREMENT;
+
+CREATE TABLE `bug_reports__digests` (
+  `guild_id` bigint(20) UNSIGNED NOT NULL,
+  `channel_id` bigint(20) UNSIGNED NOT NULL,
+  `stale_days` int(10) UNSIGNED NOT NULL DEFAULT '30',
+  `last_digest` timestamp NULL DEFAULT NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+ALTER TABLE `bug_reports__digests`
+  ADD PRIMARY KEY (`guild_id`);
+
+CREATE TABLE `bug_reports__crash_logs` (
+  `bug_id` int(11) NOT NULL,
+  `exception` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci,
+  `suspected_mods` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `minecraft_version` tinytext CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci,
+  `forge_version` tinytext CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci,
+  `mod_version` tinytext CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci,
+  `mod_frames` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `signature` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+ALTER TABLE `bug_reports__crash_logs`
+  ADD PRIMARY KEY (`bug_id`),
+  ADD KEY `signature` (`signature`(255));
+
+CREATE TABLE `bug_reports__crash_sightings` (
+  `bug_id` int(11) NOT NULL,
+  `channel_id` bigint(20) UNSIGNED NOT NULL,
+  `message_id` bigint(20) UNSIGNED NOT NULL,
+  `user_id` bigint(20) UNSIGNED NOT NULL,
+  `timestamp` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+ALTER TABLE `bug_reports__crash_sightings`
+  ADD PRIMARY KEY (`bug_id`,`message_id`);
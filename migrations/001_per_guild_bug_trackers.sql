@@ -0,0 +1,31 @@
+-- Moves the Eras of Arda bug reports to a per-guild bug tracker.
+--
+-- Existing bug ids are kept unchanged, and all the existing bug reports
+-- belong to the Eras of Arda Community Discord with the `EoA` prefix.
+
+CREATE TABLE `bug_trackers` (
+  `guild_id` bigint(20) UNSIGNED NOT NULL,
+  `prefix` varchar(8) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL DEFAULT 'BUG',
+  `channel_id` bigint(20) UNSIGNED DEFAULT NULL,
+  `categories` set('fa_renewed','fa_legacy','sa_renewed','sa_legacy') CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL DEFAULT 'fa_renewed,fa_legacy,sa_renewed,sa_legacy'
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+ALTER TABLE `bug_trackers`
+  ADD PRIMARY KEY (`guild_id`);
+
+CREATE TABLE `bug_trackers__moderators` (
+  `guild_id` bigint(20) UNSIGNED NOT NULL,
+  `user_id` bigint(20) UNSIGNED NOT NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+ALTER TABLE `bug_trackers__moderators`
+  ADD PRIMARY KEY (`guild_id`,`user_id`);
+
+INSERT INTO `bug_trackers` (`guild_id`, `prefix`) VALUES (325180225979809792, 'EoA');
+
+ALTER TABLE `bug_reports`
+  ADD `guild_id` bigint(20) UNSIGNED NOT NULL DEFAULT 325180225979809792 AFTER `bug_id`,
+  ADD KEY `guild_id` (`guild_id`);
+
+ALTER TABLE `bug_reports`
+  ALTER `guild_id` DROP DEFAULT;
@@ -0,0 +1,31 @@
+-- Moves the bug statuses to user-defined statuses of each bug tracker.
+--
+-- Every existing tracker gets the statuses that were previously built in,
+-- so existing bug reports keep their status, colour and priority.
+
+CREATE TABLE `bug_trackers__statuses` (
+  `guild_id` bigint(20) UNSIGNED NOT NULL,
+  `name` varchar(32) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `colour` int(10) UNSIGNED NOT NULL DEFAULT 0,
+  `marker` varchar(64) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `weight` int(11) NOT NULL DEFAULT 0,
+  `open` tinyint(1) NOT NULL DEFAULT 1
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+ALTER TABLE `bug_trackers__statuses`
+  ADD PRIMARY KEY (`guild_id`,`name`);
+
+INSERT INTO `bug_trackers__statuses` (`guild_id`, `name`, `colour`, `marker`, `weight`, `open`)
+SELECT t.`guild_id`, s.`name`, s.`colour`, s.`marker`, s.`weight`, s.`open` FROM `bug_trackers` t
+CROSS JOIN (
+  SELECT 'critical' AS `name`, 16711680 AS `colour`, ':bangbang:' AS `marker`, 6 AS `weight`, 1 AS `open`
+  UNION ALL SELECT 'high', 16605444, ':red_circle:', 5, 1
+  UNION ALL SELECT 'medium', 16620033, ':orange_circle:', 4, 1
+  UNION ALL SELECT 'low', 16707585, ':yellow_circle:', 3, 1
+  UNION ALL SELECT 'resolved', 3134756, ':green_circle:', 2, 0
+  UNION ALL SELECT 'forgevanilla', 10420421, ':regional_indicator_v:', 1, 0
+  UNION ALL SELECT 'closed', 7771339, ':blue_circle:', 0, 0
+) s;
+
+ALTER TABLE `bug_reports`
+  MODIFY `status` varchar(32) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL DEFAULT 'medium';
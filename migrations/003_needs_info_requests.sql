@@ -0,0 +1,20 @@
+-- Adds the `needs-info` status to every bug tracker, and the requests for
+-- more information sent to the reporters of these bugs.
+
+CREATE TABLE `bug_reports__info_requests` (
+  `bug_id` int(11) NOT NULL,
+  `user_id` bigint(20) UNSIGNED NOT NULL,
+  `timestamp` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  `reminded` tinyint(1) NOT NULL DEFAULT '0'
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+ALTER TABLE `bug_reports__info_requests`
+  ADD PRIMARY KEY (`bug_id`),
+  ADD KEY `user_id` (`user_id`);
+
+ALTER TABLE `bug_trackers`
+  ADD `info_reminder_days` int(10) UNSIGNED NOT NULL DEFAULT '7',
+  ADD `info_close_days` int(10) UNSIGNED NOT NULL DEFAULT '30';
+
+INSERT IGNORE INTO `bug_trackers__statuses` (`guild_id`, `name`, `colour`, `marker`, `weight`, `open`)
+SELECT `guild_id`, 'needs-info', 11188418, ':grey_question:', 3, 1 FROM `bug_trackers`;
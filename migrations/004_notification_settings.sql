@@ -0,0 +1,24 @@
+-- Adds the notification modes of the bug subscribers, and the queue of the
+-- notifications waiting for their daily digest.
+
+CREATE TABLE `bug_reports__notification_settings` (
+  `user_id` bigint(20) UNSIGNED NOT NULL,
+  `mode` enum('instant','digest','status','resolved') CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL DEFAULT 'instant',
+  `last_digest` timestamp NULL DEFAULT NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+ALTER TABLE `bug_reports__notification_settings`
+  ADD PRIMARY KEY (`user_id`);
+
+CREATE TABLE `bug_reports__notification_queue` (
+  `queue_id` int(10) UNSIGNED NOT NULL,
+  `user_id` bigint(20) UNSIGNED NOT NULL,
+  `bug_id` int(10) UNSIGNED NOT NULL,
+  `message` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `timestamp` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+ALTER TABLE `bug_reports__notification_queue`
+  ADD PRIMARY KEY (`queue_id`),
+  ADD KEY `user_id` (`user_id`),
+  MODIFY `queue_id` int(10) UNSIGNED NOT NULL AUTO_INCREMENT;
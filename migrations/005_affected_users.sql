@@ -0,0 +1,10 @@
+-- Adds the users who reported having a bug too.
+
+CREATE TABLE `bug_reports__affected_users` (
+  `bug_id` int(11) NOT NULL,
+  `user_id` bigint(20) UNSIGNED NOT NULL,
+  `timestamp` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+ALTER TABLE `bug_reports__affected_users`
+  ADD PRIMARY KEY (`bug_id`,`user_id`);
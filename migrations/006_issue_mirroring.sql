@@ -0,0 +1,21 @@
+-- Adds the repositories where the bugs of a guild are mirrored as issues, and
+-- the issues mirroring each bug.
+
+CREATE TABLE `bug_trackers__issue_sync` (
+  `guild_id` bigint(20) UNSIGNED NOT NULL,
+  `repository` varchar(100) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `api_url` varchar(255) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci DEFAULT NULL,
+  `last_sync` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+ALTER TABLE `bug_trackers__issue_sync`
+  ADD PRIMARY KEY (`guild_id`);
+
+CREATE TABLE `bug_reports__issues` (
+  `bug_id` int(11) NOT NULL,
+  `issue_number` int(10) UNSIGNED NOT NULL,
+  `state` enum('open','completed','not_planned') CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL DEFAULT 'open'
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+ALTER TABLE `bug_reports__issues`
+  ADD PRIMARY KEY (`bug_id`);
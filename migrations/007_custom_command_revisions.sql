@@ -0,0 +1,18 @@
+-- Adds the revisions of the custom commands. The current version of each
+-- existing command becomes its first revision, with an unknown author.
+
+CREATE TABLE `custom_commands__revisions` (
+  `server_id` bigint(20) NOT NULL,
+  `name` varchar(255) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `revision` int(10) UNSIGNED NOT NULL,
+  `command_json` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `documentation` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `author_id` bigint(20) UNSIGNED NOT NULL,
+  `timestamp` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+ALTER TABLE `custom_commands__revisions`
+  ADD PRIMARY KEY (`server_id`,`name`,`revision`);
+
+INSERT INTO `custom_commands__revisions` (`server_id`, `name`, `revision`, `command_json`, `documentation`, `author_id`)
+  SELECT `server_id`, `name`, 1, `command_json`, `documentation`, 0 FROM `custom_commands`;
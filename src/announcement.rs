@@ -2,7 +2,7 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Deserializer, Serialize};
-use serenity::builder::{CreateEmbed, CreateMessage, EditMessage};
+use serenity::builder::{CreateComponents, CreateEmbed, CreateMessage, EditMessage, ParseValue};
 use serenity::client::Context;
 use serenity::framework::standard::CommandResult;
 use serenity::futures::future::join_all;
@@ -160,6 +160,16 @@ pub struct AnnouncementButton {
     pub disabled: bool,
 }
 
+/// Button opening the bug report form, see
+/// [`open_bug_report_form`][crate::commands::bug_reports::open_bug_report_form]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AnnouncementBugReportButton {
+    pub label: Option<String>,
+    pub emoji: Option<AnnouncementReaction>,
+    /// Channel where the bug reports are posted, defaults to the announcement channel
+    pub channel: Option<ChannelId>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Announcement {
     pub content: Option<String>,
@@ -174,6 +184,7 @@ pub struct Announcement {
 
     pub reactions: Option<Vec<AnnouncementReaction>>,
     pub link_buttons: Option<Vec<AnnouncementButton>>,
+    pub bug_report_button: Option<AnnouncementBugReportButton>,
 
     #[serde(flatten)]
     pub extra: serde_json::Value,
@@ -322,13 +333,126 @@ fn parse_embed(embed: &AnnouncementEmbed) -> CreateEmbed {
     builder
 }
 
+fn create_components<'a>(
+    c: &'a mut CreateComponents,
+    message: &Announcement,
+    channel: ChannelId,
+) -> &'a mut CreateComponents {
+    if let Some(buttons) = message.link_buttons.as_ref().filter(|b| !b.is_empty()) {
+        c.create_action_row(|a| {
+            for button in buttons {
+                a.create_button(|b| {
+                    b.style(ButtonStyle::Link)
+                        .url(&button.url)
+                        .disabled(button.disabled);
+                    if let Some(label) = &button.label {
+                        b.label(label);
+                    }
+                    if let Some(emoji) = &button.emoji {
+                        b.emoji(emoji.0.clone());
+                    }
+                    b
+                });
+            }
+
+            a
+        });
+    }
+    if let Some(button) = &message.bug_report_button {
+        c.create_action_row(|a| {
+            a.create_button(|b| {
+                b.style(ButtonStyle::Primary)
+                    .custom_id(format!(
+                        "bug_report_form__{}",
+                        button.channel.unwrap_or(channel).0
+                    ))
+                    .label(button.label.as_deref().unwrap_or("Report a bug"));
+                if let Some(emoji) = &button.emoji {
+                    b.emoji(emoji.0.clone());
+                }
+                b
+            })
+        });
+    }
+    c
+}
+
+/// Mentions that can ping in a message
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AllowedMentions {
+    /// `@everyone` and `@here`
+    pub everyone: bool,
+    pub users: Vec<UserId>,
+    pub roles: Vec<RoleId>,
+}
+
+impl AllowedMentions {
+    /// Mentions written in a text, like `<@id>`, `<@&id>` or `@everyone`
+    pub fn from_text(text: &str) -> Self {
+        let mut mentions = Self {
+            everyone: text.contains("@everyone") || text.contains("@here"),
+            ..Default::default()
+        };
+        for (i, _) in text.match_indices("<@") {
+            let rest = &text[i + 2..];
+            let (is_role, rest) = match rest.strip_prefix('&') {
+                Some(rest) => (true, rest),
+                None => (false, rest.strip_prefix('!').unwrap_or(rest)),
+            };
+            let id = match rest
+                .split_once('>')
+                .and_then(|(id, _)| id.parse::<u64>().ok())
+            {
+                Some(id) => id,
+                None => continue,
+            };
+            if is_role {
+                mentions.roles.push(RoleId(id));
+            } else {
+                mentions.users.push(UserId(id));
+            }
+        }
+        mentions
+    }
+}
+
 pub async fn announce<'a>(
     ctx: &Context,
     channel: ChannelId,
     message: &'a Announcement,
+) -> CommandResult {
+    send_announcement(ctx, channel, message, None).await
+}
+
+/// Same as [`announce`], but only the given mentions ping
+pub async fn announce_with_mentions<'a>(
+    ctx: &Context,
+    channel: ChannelId,
+    message: &'a Announcement,
+    mentions: &AllowedMentions,
+) -> CommandResult {
+    send_announcement(ctx, channel, message, Some(mentions)).await
+}
+
+async fn send_announcement<'a>(
+    ctx: &Context,
+    channel: ChannelId,
+    message: &'a Announcement,
+    mentions: Option<&AllowedMentions>,
 ) -> CommandResult {
     let mut builder = CreateMessage::default();
 
+    if let Some(mentions) = mentions {
+        builder.allowed_mentions(|m| {
+            m.empty_parse();
+            if mentions.everyone {
+                m.parse(ParseValue::Everyone);
+            }
+            m.users(mentions.users.iter().copied())
+                .roles(mentions.roles.iter().copied())
+        });
+    }
+
     // message content
     if let Some(content) = &message.content {
         builder.content(content);
@@ -365,30 +489,8 @@ pub async fn announce<'a>(
     }
 
     // components
-    if let Some(buttons) = &message.link_buttons {
-        builder.components(|c| {
-            if !buttons.is_empty() {
-                c.create_action_row(|a| {
-                    for button in buttons {
-                        a.create_button(|b| {
-                            b.style(ButtonStyle::Link)
-                                .url(&button.url)
-                                .disabled(button.disabled);
-                            if let Some(label) = &button.label {
-                                b.label(label);
-                            }
-                            if let Some(emoji) = &button.emoji {
-                                b.emoji(emoji.0.clone());
-                            }
-                            b
-                        });
-                    }
-
-                    a
-                });
-            }
-            c
-        });
+    if message.link_buttons.is_some() || message.bug_report_button.is_some() {
+        builder.components(|c| create_components(c, message, channel));
     }
 
     channel
@@ -435,30 +537,8 @@ pub async fn edit_message(
         builder.set_embed(parse_embed(embed));
     }
 
-    if let Some(buttons) = &message.link_buttons {
-        builder.components(|c| {
-            if !buttons.is_empty() {
-                c.create_action_row(|a| {
-                    for button in buttons {
-                        a.create_button(|b| {
-                            b.style(ButtonStyle::Link)
-                                .url(&button.url)
-                                .disabled(button.disabled);
-                            if let Some(label) = &button.label {
-                                b.label(label);
-                            }
-                            if let Some(emoji) = &button.emoji {
-                                b.emoji(emoji.0.clone());
-                            }
-                            b
-                        });
-                    }
-
-                    a
-                });
-            }
-            c
-        });
+    if message.link_buttons.is_some() || message.bug_report_button.is_some() {
+        builder.components(|c| create_components(c, message, channel));
     }
 
     let msg = channel
@@ -0,0 +1,351 @@
+//! Mirroring of bug reports as issues of an external issue tracker.
+//!
+//! [`GitHubTracker`] implements [`IssueTracker`] with the GitHub REST API, which is
+//! also spoken by self-hosted forges like Gitea or Forgejo.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use serenity::async_trait;
+use serenity::framework::standard::{CommandError, CommandResult};
+use std::env;
+use std::str::FromStr;
+
+use crate::constants::GITHUB_API;
+
+/// Issues fetched per page when listing the updated issues
+const ISSUES_PER_PAGE: usize = 100;
+/// Maximum number of pages fetched when listing the updated issues
+const MAX_ISSUE_PAGES: u32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueState {
+    Open,
+    /// Closed as completed
+    Completed,
+    /// Closed without being fixed
+    NotPlanned,
+}
+
+impl IssueState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Completed => "completed",
+            Self::NotPlanned => "not_planned",
+        }
+    }
+}
+
+impl FromStr for IssueState {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "open" => Ok(Self::Open),
+            "completed" => Ok(Self::Completed),
+            "not_planned" => Ok(Self::NotPlanned),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    pub number: u64,
+    pub url: String,
+    pub state: IssueState,
+}
+
+/// An external issue tracker where bug reports can be mirrored
+#[async_trait]
+pub trait IssueTracker: Send + Sync {
+    /// Title of the bug links to the mirrored issues, like `GitHub issue`
+    fn issue_name(&self) -> &'static str;
+
+    /// Opens a new issue
+    async fn open_issue(
+        &self,
+        client: &reqwest::Client,
+        title: &str,
+        body: &str,
+    ) -> CommandResult<Issue>;
+
+    /// Lists the issues updated since a date, closed ones included
+    async fn updated_issues(
+        &self,
+        client: &reqwest::Client,
+        since: DateTime<Utc>,
+    ) -> CommandResult<Vec<Issue>>;
+}
+
+/// A repository of the GitHub REST API, or of a server compatible with it
+#[derive(Debug, Clone)]
+pub struct GitHubTracker {
+    api_url: String,
+    /// Repository path, like `owner/repo`
+    repository: String,
+    token: String,
+}
+
+impl GitHubTracker {
+    /// Uses the token from the `ISSUE_TRACKER_TOKEN` environment variable, and the
+    /// public GitHub API if no API url is given. The token is never sent to an
+    /// API url without https.
+    pub fn new(api_url: Option<&str>, repository: &str) -> CommandResult<Self> {
+        if api_url.map_or(false, |url| !url.starts_with("https://")) {
+            return Err("The API url must be a https url".into());
+        }
+        let token = env::var("ISSUE_TRACKER_TOKEN")
+            .map_err(|_| "Expected an issue tracker token in the environment")?;
+        Ok(Self::with_token(
+            api_url.unwrap_or(GITHUB_API),
+            repository,
+            token,
+        ))
+    }
+
+    pub fn with_token(api_url: &str, repository: &str, token: String) -> Self {
+        Self {
+            api_url: api_url.trim_end_matches('/').to_string(),
+            repository: repository.to_string(),
+            token,
+        }
+    }
+
+    fn request(
+        &self,
+        client: &reqwest::Client,
+        method: reqwest::Method,
+    ) -> reqwest::RequestBuilder {
+        client
+            .request(
+                method,
+                format!("{}/repos/{}/issues", self.api_url, self.repository),
+            )
+            .header("accept", "application/vnd.github+json")
+            .header("user-agent", env!("CARGO_PKG_NAME"))
+            .bearer_auth(&self.token)
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct NewGitHubIssue<'a> {
+    title: &'a str,
+    body: &'a str,
+}
+
+#[derive(Deserialize, Debug)]
+struct GitHubIssue {
+    number: u64,
+    html_url: String,
+    state: String,
+    #[serde(default)]
+    state_reason: Option<String>,
+    /// Only present on pull requests, which are listed with the issues
+    #[serde(default)]
+    pull_request: Option<serde_json::Value>,
+}
+
+impl From<GitHubIssue> for Issue {
+    fn from(issue: GitHubIssue) -> Self {
+        Self {
+            number: issue.number,
+            url: issue.html_url,
+            state: match (issue.state.as_str(), issue.state_reason.as_deref()) {
+                ("open", _) => IssueState::Open,
+                (_, Some("not_planned")) => IssueState::NotPlanned,
+                _ => IssueState::Completed,
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl IssueTracker for GitHubTracker {
+    fn issue_name(&self) -> &'static str {
+        "GitHub issue"
+    }
+
+    async fn open_issue(
+        &self,
+        client: &reqwest::Client,
+        title: &str,
+        body: &str,
+    ) -> CommandResult<Issue> {
+        let res = self
+            .request(client, reqwest::Method::POST)
+            .header("content-type", "application/json")
+            .body(serde_json::to_string(&NewGitHubIssue { title, body })?)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        serde_json::from_str::<GitHubIssue>(&res)
+            .map(Issue::from)
+            .map_err(CommandError::from)
+    }
+
+    async fn updated_issues(
+        &self,
+        client: &reqwest::Client,
+        since: DateTime<Utc>,
+    ) -> CommandResult<Vec<Issue>> {
+        let since = since.to_rfc3339_opts(SecondsFormat::Secs, true);
+        let per_page = ISSUES_PER_PAGE.to_string();
+        let mut issues = Vec::new();
+
+        for page in 1..=MAX_ISSUE_PAGES {
+            let res = self
+                .request(client, reqwest::Method::GET)
+                .query(&[
+                    ("state", "all"),
+                    ("since", &since),
+                    ("per_page", &per_page),
+                    ("page", &page.to_string()),
+                ])
+                .send()
+                .await?
+                .error_for_status()?
+                .text()
+                .await?;
+            let page_issues: Vec<GitHubIssue> = serde_json::from_str(&res)?;
+            let last_page = page_issues.len() < ISSUES_PER_PAGE;
+            issues.extend(
+                page_issues
+                    .into_iter()
+                    .filter(|issue| issue.pull_request.is_none())
+                    .map(Issue::from),
+            );
+            if last_page {
+                break;
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::task::JoinHandle;
+
+    /// Checks that the headers and the body of a request were received
+    fn is_complete_request(request: &[u8]) -> bool {
+        let request = String::from_utf8_lossy(request);
+        let headers_end = match request.find("\r\n\r\n") {
+            Some(end) => end,
+            None => return false,
+        };
+        let content_length = request[..headers_end]
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, value)| value.trim().parse().ok())
+            .unwrap_or(0);
+        request.len() >= headers_end + 4 + content_length
+    }
+
+    /// Starts a local server answering a single request with a JSON body, and
+    /// returns its url and the request it will receive.
+    async fn mock_server(status: &'static str, body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 4096];
+            while !is_complete_request(&request) {
+                let n = socket.read(&mut buffer).await.unwrap();
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buffer[..n]);
+            }
+            let response = format!(
+                "HTTP/1.1 {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\
+connection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn test_open_issue() {
+        let (url, request) = mock_server(
+            "201 Created",
+            r#"{"number": 7, "html_url": "https://github.com/owner/repo/issues/7", "state": "open", "state_reason": null}"#,
+        )
+        .await;
+        let tracker = GitHubTracker::with_token(&url, "owner/repo", "secret".into());
+
+        let issue = tracker
+            .open_issue(&reqwest::Client::new(), "EoA-42: Crash", "Details")
+            .await
+            .unwrap();
+        assert_eq!(
+            issue,
+            Issue {
+                number: 7,
+                url: "https://github.com/owner/repo/issues/7".into(),
+                state: IssueState::Open,
+            }
+        );
+
+        let request = request.await.unwrap();
+        assert!(request.starts_with("POST /repos/owner/repo/issues HTTP/1.1\r\n"));
+        assert!(request
+            .to_ascii_lowercase()
+            .contains("authorization: bearer secret\r\n"));
+        assert!(request.ends_with(r#"{"title":"EoA-42: Crash","body":"Details"}"#));
+    }
+
+    #[tokio::test]
+    async fn test_updated_issues() {
+        let (url, request) = mock_server(
+            "200 OK",
+            r#"[
+                {"number": 1, "html_url": "https://github.com/owner/repo/issues/1", "state": "closed", "state_reason": "completed"},
+                {"number": 2, "html_url": "https://github.com/owner/repo/pull/2", "state": "open", "pull_request": {}},
+                {"number": 3, "html_url": "https://github.com/owner/repo/issues/3", "state": "closed", "state_reason": "not_planned"},
+                {"number": 4, "html_url": "https://github.com/owner/repo/issues/4", "state": "open"}
+            ]"#,
+        )
+        .await;
+        let tracker = GitHubTracker::with_token(&url, "owner/repo", "secret".into());
+        let since = DateTime::parse_from_rfc3339("2022-03-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let issues = tracker
+            .updated_issues(&reqwest::Client::new(), since)
+            .await
+            .unwrap();
+        assert_eq!(
+            issues
+                .iter()
+                .map(|issue| (issue.number, issue.state))
+                .collect::<Vec<_>>(),
+            [
+                (1, IssueState::Completed),
+                (3, IssueState::NotPlanned),
+                (4, IssueState::Open)
+            ]
+        );
+
+        let request = request.await.unwrap();
+        assert!(request.starts_with(
+            "GET /repos/owner/repo/issues?state=all&since=2022-03-01T12%3A00%3A00Z&per_page=100&page=1 "
+        ));
+    }
+}
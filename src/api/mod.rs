@@ -1,7 +1,8 @@
-//! Module for API functions: queries to google, curseforge, the LOTR Mod wiki...
+//! Module for API functions: queries to google, curseforge, the LOTR Mod wiki, issue trackers...
 
 pub mod curseforge;
 pub mod google;
+pub mod issues;
 pub mod minecraft;
 pub mod wiki;
 
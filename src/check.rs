@@ -11,6 +11,12 @@
 //! [`is_admin`] checks wether the user is either the owner, a bot admin,
 //! or has the [`struct@MANAGE_BOT_PERMS`] permissions.
 //!
+//! [`has_bugtracker`] checks wether the guild has a
+//! [bug tracker][crate::database::bug_trackers].
+//!
+//! [`is_bugtracker_moderator`] checks wether the user is an admin, or a
+//! moderator of the bug tracker of the guild.
+//!
 //! [`is_minecraft_server`] checks wether there is a server IP registered
 //! with the guild. It fails if there is none, but is bypassed by bot
 //! admins.
@@ -29,7 +35,11 @@ use serenity::model::prelude::*;
 use serenity::prelude::*;
 
 use crate::constants::{EOA_DISCORD, MANAGE_BOT_PERMS, OWNER_ID};
-use crate::database::{blacklist::check_blacklist, config::get_minecraft_ip};
+use crate::database::{
+    blacklist::check_blacklist,
+    bug_trackers::{get_bug_tracker, is_bug_moderator},
+    config::get_minecraft_ip,
+};
 use crate::is_admin;
 use crate::utils::has_permission;
 
@@ -137,6 +147,43 @@ pub async fn is_admin(ctx: &Context, msg: &Message) -> Result<(), Reason> {
     }
 }
 
+#[check]
+#[name = "has_bugtracker"]
+pub async fn has_bugtracker(ctx: &Context, msg: &Message) -> Result<(), Reason> {
+    let server_id = msg
+        .guild_id
+        .ok_or_else(|| Reason::Log("Not in a guild".into()))?;
+    if get_bug_tracker(ctx, server_id).await.is_some() {
+        Ok(())
+    } else {
+        Err(Reason::User(
+            "There is no bug tracker on this server! Admins can enable it with `bug tracker enable`."
+                .into(),
+        ))
+    }
+}
+
+#[check]
+#[name = "is_bugtracker_moderator"]
+pub async fn is_bugtracker_moderator(ctx: &Context, msg: &Message) -> Result<(), Reason> {
+    let server_id = msg
+        .guild_id
+        .ok_or_else(|| Reason::Log("Not in a guild".into()))?;
+    if msg.author.id == OWNER_ID
+        || is_admin!(ctx, msg)
+        || has_permission(ctx, server_id, msg.author.id, MANAGE_BOT_PERMS).await
+        || is_bug_moderator(ctx, server_id, msg.author.id)
+            .await
+            .unwrap_or_default()
+    {
+        Ok(())
+    } else {
+        Err(Reason::User(
+            "You are not a bug tracker moderator on this server!".into(),
+        ))
+    }
+}
+
 #[check]
 #[name = "is_minecraft_server"]
 pub async fn is_minecraft_server(ctx: &Context, msg: &Message) -> Result<(), Reason> {
@@ -0,0 +1,519 @@
+//! Typed arguments of the [custom commands][crate::commands::custom_commands],
+//! declared in their `"arguments"` field.
+//!
+//! ```json
+//! "arguments": [
+//!     {"name": "page", "type": "text", "required": true},
+//!     {"name": "lang", "type": "choice", "choices": ["en", "fr"], "default": "en"}
+//! ]
+//! ```
+//!
+//! The values are available in the templates by name, and as `{{argN}}`. User,
+//! channel and role arguments are rendered as mentions, and their ids are
+//! available as `{{name.id}}`. Only the user arguments ping: a role given as
+//! argument is not pinged unless the command body mentions it.
+//!
+//! When the last argument is a `text`, it takes all the remaining words of the
+//! message.
+
+use serde::Deserialize;
+use serde_json::Value;
+use std::fmt;
+
+use crate::template::TemplateContext;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArgumentType {
+    User,
+    Channel,
+    Role,
+    Integer,
+    /// One of the `"choices"` of the argument
+    Choice,
+    Text,
+}
+
+impl Default for ArgumentType {
+    fn default() -> Self {
+        Self::Text
+    }
+}
+
+impl ArgumentType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::Channel => "channel",
+            Self::Role => "role",
+            Self::Integer => "integer",
+            Self::Choice => "choice",
+            Self::Text => "text",
+        }
+    }
+
+    /// Beginnings of the mentions of the type, tried in order
+    fn mention_prefixes(&self) -> &'static [&'static str] {
+        match self {
+            Self::User => &["<@!", "<@"],
+            Self::Channel => &["<#"],
+            Self::Role => &["<@&"],
+            _ => &[],
+        }
+    }
+
+    /// Renders the id of a user, channel or role as a mention
+    fn mention(&self, id: u64) -> String {
+        match self {
+            Self::Channel => format!("<#{}>", id),
+            Self::Role => format!("<@&{}>", id),
+            _ => format!("<@{}>", id),
+        }
+    }
+}
+
+/// Declaration of an argument of a custom command
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Argument {
+    pub name: String,
+    #[serde(rename = "type", default)]
+    pub kind: ArgumentType,
+    #[serde(default)]
+    pub choices: Vec<String>,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub default: Option<String>,
+    /// Description of the option of the slash command
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Value of an argument given to a custom command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgumentValue {
+    pub text: String,
+    /// Id of a user, channel or role
+    pub id: Option<u64>,
+}
+
+impl Argument {
+    /// Validates a value given for the argument
+    pub fn parse(&self, value: &str) -> Result<ArgumentValue, ArgumentError> {
+        let invalid = || ArgumentError::Invalid {
+            name: self.name.clone(),
+            kind: self.kind,
+            value: value.to_string(),
+        };
+
+        match self.kind {
+            ArgumentType::User | ArgumentType::Channel | ArgumentType::Role => {
+                let id = parse_id(value, self.kind.mention_prefixes()).ok_or_else(invalid)?;
+                Ok(ArgumentValue {
+                    text: self.kind.mention(id),
+                    id: Some(id),
+                })
+            }
+            ArgumentType::Integer => value
+                .parse::<i64>()
+                .map(|n| ArgumentValue {
+                    text: n.to_string(),
+                    id: None,
+                })
+                .map_err(|_| invalid()),
+            ArgumentType::Choice => self
+                .choices
+                .iter()
+                .find(|choice| choice.eq_ignore_ascii_case(value))
+                .map(|choice| ArgumentValue {
+                    text: choice.clone(),
+                    id: None,
+                })
+                .ok_or_else(invalid),
+            ArgumentType::Text => Ok(ArgumentValue {
+                text: value.to_string(),
+                id: None,
+            }),
+        }
+    }
+
+    /// Usage of the argument, like `<page>` or `[lang: en|fr]`
+    pub fn usage(&self) -> String {
+        let description = match self.kind {
+            ArgumentType::Text => self.name.clone(),
+            ArgumentType::Choice => format!("{}: {}", self.name, self.choices.join("|")),
+            kind => format!("{}: {}", self.name, kind.as_str()),
+        };
+        if self.required {
+            format!("<{}>", description)
+        } else {
+            format!("[{}]", description)
+        }
+    }
+}
+
+/// Parses a raw id, or a mention starting with one of the prefixes
+fn parse_id(value: &str, prefixes: &[&str]) -> Option<u64> {
+    let id = match prefixes
+        .iter()
+        .find_map(|prefix| value.strip_prefix(prefix))
+    {
+        Some(mention) => mention.strip_suffix('>')?,
+        None => value,
+    };
+    id.parse().ok()
+}
+
+/// Error in the `"arguments"` of a command definition
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaError {
+    Malformed(String),
+    InvalidName(String),
+    DuplicateName(String),
+    NoChoices(String),
+    RequiredWithDefault(String),
+    RequiredAfterOptional(String),
+    InvalidDefault(ArgumentError),
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Malformed(e) => write!(f, "malformed arguments: {}", e),
+            Self::InvalidName(name) => write!(
+                f,
+                "`{}` is not a valid argument name, use letters, digits and `_`",
+                name
+            ),
+            Self::DuplicateName(name) => write!(f, "the argument `{}` is declared twice", name),
+            Self::NoChoices(name) => {
+                write!(f, "the choice argument `{}` has no `\"choices\"`", name)
+            }
+            Self::RequiredWithDefault(name) => {
+                write!(f, "the required argument `{}` cannot have a default", name)
+            }
+            Self::RequiredAfterOptional(name) => write!(
+                f,
+                "the required argument `{}` comes after optional arguments",
+                name
+            ),
+            Self::InvalidDefault(e) => write!(f, "invalid default value: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+/// Arguments given to a command that do not match its schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArgumentError {
+    Missing(String),
+    TooMany,
+    Invalid {
+        name: String,
+        kind: ArgumentType,
+        value: String,
+    },
+}
+
+impl fmt::Display for ArgumentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Missing(name) => write!(f, "Missing argument `{}`", name),
+            Self::TooMany => write!(f, "Too many arguments"),
+            Self::Invalid { name, kind, value } => write!(
+                f,
+                "`{}` is not a valid {} for `{}`",
+                value,
+                kind.as_str(),
+                name
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ArgumentError {}
+
+/// Arguments declared by a command or a subcommand
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArgumentSchema(pub Vec<Argument>);
+
+impl ArgumentSchema {
+    /// Reads the `"arguments"` field of a command, if there is one
+    pub fn from_command(command: &Value) -> Result<Option<Self>, SchemaError> {
+        let arguments = match command.get("arguments") {
+            Some(arguments) => arguments,
+            None => return Ok(None),
+        };
+        let arguments: Vec<Argument> = serde_json::from_value(arguments.clone())
+            .map_err(|e| SchemaError::Malformed(e.to_string()))?;
+
+        for (i, argument) in arguments.iter().enumerate() {
+            let name = &argument.name;
+            if name.is_empty()
+                || name.starts_with(|c: char| c.is_ascii_digit())
+                || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                || name == "args"
+                || is_positional_name(name)
+            {
+                return Err(SchemaError::InvalidName(name.clone()));
+            }
+            if arguments[..i].iter().any(|other| &other.name == name) {
+                return Err(SchemaError::DuplicateName(name.clone()));
+            }
+            if argument.kind == ArgumentType::Choice && argument.choices.is_empty() {
+                return Err(SchemaError::NoChoices(name.clone()));
+            }
+            if argument.required {
+                if argument.default.is_some() {
+                    return Err(SchemaError::RequiredWithDefault(name.clone()));
+                }
+                if arguments[..i].iter().any(|other| !other.required) {
+                    return Err(SchemaError::RequiredAfterOptional(name.clone()));
+                }
+            }
+            if let Some(default) = &argument.default {
+                argument
+                    .parse(default)
+                    .map_err(SchemaError::InvalidDefault)?;
+            }
+        }
+
+        Ok(Some(Self(arguments)))
+    }
+
+    /// Usage of the arguments, like `<page> [lang: en|fr]`
+    pub fn usage(&self) -> String {
+        let mut usage = self
+            .0
+            .iter()
+            .map(Argument::usage)
+            .collect::<Vec<_>>()
+            .join(" ");
+        if self.has_rest_argument() {
+            // `<page>` becomes `<page...>`
+            usage.insert_str(usage.len() - 1, "...");
+        }
+        usage
+    }
+
+    /// Validates the arguments given to a command, filling the missing optional ones
+    /// with their default. A last `text` argument takes all the remaining words.
+    pub fn parse(&self, values: &[String]) -> Result<Vec<Option<ArgumentValue>>, ArgumentError> {
+        let last = self.0.len().saturating_sub(1);
+        let rest = if values.len() <= self.0.len() {
+            None
+        } else if self.has_rest_argument() {
+            Some(values[last..].join(" "))
+        } else {
+            return Err(ArgumentError::TooMany);
+        };
+        self.parse_with(|i, _| match &rest {
+            Some(rest) if i == last => Some(rest.clone()),
+            _ => values.get(i).cloned(),
+        })
+    }
+
+    /// Whether the last argument is a `text` taking the rest of the input
+    fn has_rest_argument(&self) -> bool {
+        self.0
+            .last()
+            .map_or(false, |argument| argument.kind == ArgumentType::Text)
+    }
+
+    /// Validates arguments given by name, like the options of a slash command
+    pub fn parse_named(
+        &self,
+        value: impl Fn(&str) -> Option<String>,
+    ) -> Result<Vec<Option<ArgumentValue>>, ArgumentError> {
+        self.parse_with(|_, argument| value(&argument.name))
+    }
+
+    fn parse_with(
+        &self,
+        value: impl Fn(usize, &Argument) -> Option<String>,
+    ) -> Result<Vec<Option<ArgumentValue>>, ArgumentError> {
+        self.0
+            .iter()
+            .enumerate()
+            .map(
+                |(i, argument)| match value(i, argument).or_else(|| argument.default.clone()) {
+                    Some(value) => argument.parse(&value).map(Some),
+                    None if argument.required => Err(ArgumentError::Missing(argument.name.clone())),
+                    None => Ok(None),
+                },
+            )
+            .collect()
+    }
+
+    /// Adds parsed argument values to a template context
+    pub fn add_variables(&self, values: &[Option<ArgumentValue>], context: &mut TemplateContext) {
+        for (i, (argument, value)) in self.0.iter().zip(values).enumerate() {
+            let value = match value {
+                Some(value) => value,
+                None => continue,
+            };
+            context
+                .set(argument.name.as_str(), value.text.as_str())
+                .set(format!("arg{}", i), value.text.as_str());
+            if let Some(id) = value.id {
+                context.set(format!("{}.id", argument.name), id.to_string());
+            }
+        }
+    }
+}
+
+/// Usage of the arguments declared by the JSON body of a command, if there are any
+pub fn command_usage(body: &str) -> Option<String> {
+    let command = serde_json::from_str(body).ok()?;
+    ArgumentSchema::from_command(&command)
+        .ok()
+        .flatten()
+        .map(|schema| schema.usage())
+}
+
+/// Checks for names like `arg0`, which are reserved for the positional arguments
+fn is_positional_name(name: &str) -> bool {
+    name.strip_prefix("arg").map_or(false, |n| {
+        !n.is_empty() && n.chars().all(|c| c.is_ascii_digit())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> ArgumentSchema {
+        ArgumentSchema::from_command(&json!({
+            "arguments": [
+                {"name": "page", "required": true},
+                {"name": "member", "type": "user"},
+                {"name": "lang", "type": "choice", "choices": ["en", "fr"], "default": "en"},
+                {"name": "count", "type": "integer"}
+            ]
+        }))
+        .unwrap()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_usage() {
+        assert_eq!(
+            schema().usage(),
+            "<page> [member: user] [lang: en|fr] [count: integer]"
+        );
+    }
+
+    #[test]
+    fn test_rest_argument() {
+        let schema = ArgumentSchema::from_command(&json!({
+            "arguments": [
+                {"name": "lang", "type": "choice", "choices": ["en", "fr"]},
+                {"name": "page", "required": false}
+            ]
+        }))
+        .unwrap()
+        .unwrap();
+        assert_eq!(schema.usage(), "[lang: en|fr] [page...]");
+
+        let values = schema
+            .parse(&["en".into(), "Minas".into(), "Tirith".into()])
+            .unwrap();
+        assert_eq!(values[1].as_ref().unwrap().text, "Minas Tirith");
+        let values = schema.parse(&["fr".into()]).unwrap();
+        assert_eq!(values[1], None);
+    }
+
+    #[test]
+    fn test_parse() {
+        let values = schema()
+            .parse(&["Gondor".into(), "<@!42>".into(), "FR".into()])
+            .unwrap();
+        assert_eq!(
+            values,
+            [
+                Some(ArgumentValue {
+                    text: "Gondor".into(),
+                    id: None
+                }),
+                Some(ArgumentValue {
+                    text: "<@42>".into(),
+                    id: Some(42)
+                }),
+                Some(ArgumentValue {
+                    text: "fr".into(),
+                    id: None
+                }),
+                None
+            ]
+        );
+
+        let values = schema().parse(&["Rohan".into()]).unwrap();
+        assert_eq!(values[2].as_ref().unwrap().text, "en");
+
+        let values = schema()
+            .parse_named(|name| match name {
+                "page" => Some("Rohan".into()),
+                "count" => Some("3".into()),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(values[1], None);
+        assert_eq!(values[3].as_ref().unwrap().text, "3");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            schema().parse(&[]),
+            Err(ArgumentError::Missing("page".into()))
+        );
+        assert_eq!(
+            schema().parse(&["a".into(), "42".into(), "en".into(), "many".into()]),
+            Err(ArgumentError::Invalid {
+                name: "count".into(),
+                kind: ArgumentType::Integer,
+                value: "many".into()
+            })
+        );
+        assert_eq!(
+            schema().parse(&["a".into(), "b".into(), "c".into(), "d".into(), "e".into()]),
+            Err(ArgumentError::TooMany)
+        );
+        assert!(schema().parse(&["a".into(), "<#42>".into()]).is_err());
+    }
+
+    #[test]
+    fn test_schema_errors() {
+        let error = |arguments: Value| {
+            ArgumentSchema::from_command(&json!({ "arguments": arguments })).unwrap_err()
+        };
+        assert_eq!(
+            error(json!([{"name": "arg1"}])),
+            SchemaError::InvalidName("arg1".into())
+        );
+        assert_eq!(
+            error(json!([{"name": "a"}, {"name": "a"}])),
+            SchemaError::DuplicateName("a".into())
+        );
+        assert_eq!(
+            error(json!([{"name": "a", "type": "choice"}])),
+            SchemaError::NoChoices("a".into())
+        );
+        assert_eq!(
+            error(json!([{"name": "a"}, {"name": "b", "required": true}])),
+            SchemaError::RequiredAfterOptional("b".into())
+        );
+        assert!(matches!(
+            error(json!([{"name": "a", "type": "integer", "default": "x"}])),
+            SchemaError::InvalidDefault(_)
+        ));
+        assert!(matches!(
+            error(json!([{"name": "a", "type": "number"}])),
+            SchemaError::Malformed(_)
+        ));
+        assert_eq!(ArgumentSchema::from_command(&json!({})), Ok(None));
+    }
+}
@@ -1,22 +1,58 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serenity::builder::{CreateApplicationCommand, CreateApplicationCommandOption};
 use serenity::client::Context;
 use serenity::collector::CollectComponentInteraction;
 use serenity::framework::standard::{macros::command, Args, CommandResult};
 use serenity::model::{
-    interactions::message_component::{ButtonStyle, MessageComponentInteraction},
+    interactions::{
+        application_command::{
+            ApplicationCommandInteraction, ApplicationCommandInteractionDataOption,
+            ApplicationCommandOptionType,
+        },
+        autocomplete::AutocompleteInteraction,
+        message_component::{
+            ActionRowComponent, ButtonStyle, InputTextStyle, MessageComponentInteraction,
+        },
+        modal::ModalSubmitInteraction,
+    },
     prelude::*,
 };
 use serenity::prelude::*;
 use std::time::Duration;
 
+use crate::api::issues::IssueState;
 use crate::check::*;
-use crate::constants::{EOA_DISCORD, MANAGE_BOT_PERMS, OWNER_ID};
+use crate::commands::bug_tracker_setup::BUG_TRACKER_COMMAND;
+use crate::constants::{MANAGE_BOT_PERMS, OWNER_ID};
+use crate::crash_logs::{crash_log_attachment, read_crash_log, CrashLog};
 use crate::database::admin_data::is_admin_function;
 use crate::database::bug_reports::{
-    add_bug_report, add_link, add_notified_user, change_bug_status, change_category, change_title,
-    get_bug_from_id, get_bug_list, get_bug_statistics, get_notifications_for_user,
-    get_notified_users, is_notified_user, remove_link, BugCategory, BugOrder, BugStatus,
+    add_bug_report, add_crash_sighting, add_info_request, add_label, add_link, add_notified_user,
+    autocomplete_bugs, change_bug_status, change_category, change_title, find_crash_duplicate,
+    get_bug_digest, get_bug_from_id, get_bug_history, get_bug_list, get_bug_reports,
+    get_bug_statistics, get_digest_config, get_due_digests, get_due_info_requests,
+    get_due_notification_digests, get_fixed_bugs, get_label_statistics, get_mirrored_issues,
+    get_notification_mode, get_notifications_for_user, get_notified_users,
+    get_queued_notifications, import_bug_reports, is_notified_user, mark_duplicate,
+    normalize_label, normalize_version, queue_notification, record_info_reply,
+    remove_digest_config, remove_label, remove_link, remove_queued_notifications, search_bugs,
+    set_affected_version, set_assignee, set_bug_thread, set_crash_log, set_digest_config,
+    set_digest_sent, set_fixed_in, set_info_reminded, set_mirrored_issue,
+    set_notification_digest_sent, set_notification_mode, BugCategory, BugFilter, BugLink, BugOrder,
+    BugReport, BugStatus, DigestConfig, InfoRequest, MirroredIssue, NotificationKind,
+    NotificationMode, PartialBugReport, QueuedNotification, MAX_LABEL_LENGTH, MAX_VERSION_LENGTH,
+    STATUS_CLOSED, STATUS_NEEDS_INFO, STATUS_RESOLVED,
 };
-use crate::failure;
+use crate::database::bug_trackers::{
+    get_bug_prefix, get_bug_tracker, get_issue_sync, get_issue_syncs, is_bug_moderator,
+    is_tracker_bug, set_issue_sync_time, BugTracker, IssueSync,
+};
+use crate::utils::{
+    download_attachment, parse_csv, to_csv_safe_string, InteractionEasyResponse, JsonMessageError,
+    NotInGuild,
+};
+use crate::{failure, get_reqwest_client, handle_json_error};
 
 pub const TERMITE_EMOJI: EmojiId = EmojiId(938135367486410792);
 
@@ -47,21 +83,77 @@ macro_rules! termite_success {
     }};
 }
 
+/// Gets the bug tracker of the guild of a message, or sends a failure message and returns
+macro_rules! get_tracker {
+    ($ctx:ident, $msg:ident) => {{
+        let tracker = match $msg.guild_id {
+            Some(guild_id) => get_bug_tracker($ctx, guild_id).await,
+            None => None,
+        };
+        match tracker {
+            Some(tracker) => tracker,
+            None => {
+                failure!($ctx, $msg, "There is no bug tracker on this server!");
+                return Ok(());
+            }
+        }
+    }};
+}
+
+/// Parses the next argument as a bug id of the tracker, or sends a failure message and returns
+macro_rules! get_bug_id {
+    ($ctx:ident, $msg:ident, $tracker:expr, $args:ident) => {
+        match $args.single::<String>() {
+            Ok(bug_id) => match $tracker.parse_bug_id(&bug_id) {
+                Some(bug_id)
+                    if is_tracker_bug($ctx, $tracker.guild_id, bug_id)
+                        .await
+                        .unwrap_or_default() =>
+                {
+                    bug_id
+                }
+                Some(bug_id) => {
+                    failure!(
+                        $ctx,
+                        $msg,
+                        "The bug {} does not exist!",
+                        $tracker.display_id(bug_id)
+                    );
+                    return Ok(());
+                }
+                None => {
+                    failure!($ctx, $msg, "`{}` is not a valid bug id!", bug_id);
+                    return Ok(());
+                }
+            },
+            Err(_) => {
+                failure!($ctx, $msg, "The first argument must be a bug id.");
+                return Ok(());
+            }
+        }
+    };
+}
+
+/// Name displayed as the author of the bug tracker embeds of a guild
+fn tracker_name(ctx: &Context, guild_id: GuildId) -> String {
+    guild_id.name(ctx).map_or_else(
+        || "Bugtracker".into(),
+        |name| format!("{} Bugtracker", name),
+    )
+}
+
 macro_rules! create_bug_embed {
-    ($bug:expr, $linked_message:expr) => {
+    ($ctx:ident, $bug:expr, $linked_message:expr) => {
         |e| {
             e.author(|a| {
-                a.name("Eras of Arda Bugtracker");
+                a.name(tracker_name($ctx, $bug.guild_id));
                 a.icon_url(crate::constants::TERMITE_IMAGE);
                 a
             });
-            e.colour($bug.status.colour());
+            e.colour($bug.status.colour);
             e.title(format!(
-                "{} EoA-{}: {} [{}]",
-                $bug.status.marker(),
-                $bug.bug_id,
-                $bug.title,
-                $bug.category
+                "{} {}-{}: {} [{}]",
+                $bug.status.marker, $bug.prefix, $bug.bug_id, $bug.title, $bug.category
             ));
             if let Ok(ref message) = $linked_message {
                 e.description(&message.content);
@@ -77,6 +169,53 @@ macro_rules! create_bug_embed {
             } else {
                 e.footer(|f| f.text(format!("Status: {}", $bug.status)));
             }
+            if let Some(assignee) = $bug.assignee {
+                e.field("Assignee", format!("<@{}>", assignee.0), true);
+            }
+            if !$bug.labels.is_empty() {
+                e.field(
+                    "Labels",
+                    $bug.labels
+                        .iter()
+                        .map(|label| format!("`{}`", label))
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                    true,
+                );
+            }
+            if let Some(version) = &$bug.affected_version {
+                e.field("Affected version", format!("`{}`", version), true);
+            }
+            if let Some(version) = &$bug.fixed_in {
+                e.field("Fixed in", format!("`{}`", version), true);
+            }
+            if let Some(thread_id) = $bug.thread_id {
+                e.field("Discussion", format!("<#{}>", thread_id.0), true);
+            }
+            if $bug.affected_users > 0 {
+                e.field("Affected users", $bug.affected_users, true);
+            }
+            if let Some(exception) = $bug.crash_log.as_ref().and_then(|c| c.exception.as_ref()) {
+                e.field(
+                    "Crash",
+                    format!(
+                        "```\n{}\n```\nSeen {}",
+                        exception,
+                        match $bug.times_seen {
+                            1 => "once".into(),
+                            n => format!("{} times", n),
+                        }
+                    ),
+                    false,
+                );
+            }
+            if let Some(original) = $bug.duplicate_of {
+                e.field(
+                    "Duplicate of",
+                    format!("{}-{}", $bug.prefix, original),
+                    false,
+                );
+            }
             if !$bug.links.is_empty() {
                 e.field(
                     "Additional information",
@@ -95,12 +234,43 @@ macro_rules! create_bug_embed {
     };
 }
 
+/// Notifies the subscribers of a bug who want this kind of notification. The ones in
+/// [digest mode][NotificationMode::Digest] get it in their next daily digest instead.
 pub async fn notify_users(
     ctx: &Context,
     bug_id: u64,
+    kind: NotificationKind,
+    message: impl std::fmt::Display,
+) -> CommandResult {
+    let subscribers = get_notified_users(ctx, bug_id).await?;
+    notify_subscribers(ctx, bug_id, subscribers, kind, message).await
+}
+
+/// Like [`notify_users`], for subscribers of the bug fetched beforehand
+async fn notify_subscribers(
+    ctx: &Context,
+    bug_id: u64,
+    subscribers: Vec<(UserId, NotificationMode)>,
+    kind: NotificationKind,
     message: impl std::fmt::Display,
 ) -> CommandResult {
-    let notified_users = get_notified_users(ctx, bug_id).await?;
+    let message = message.to_string();
+    let mut notified_users = Vec::new();
+    let mut digest_users = Vec::new();
+    for (user_id, mode) in subscribers {
+        if !mode.accepts(kind) {
+            continue;
+        }
+        if mode == NotificationMode::Digest {
+            digest_users.push(user_id);
+        } else {
+            notified_users.push(user_id);
+        }
+    }
+
+    if !digest_users.is_empty() {
+        queue_notification(ctx, bug_id, &digest_users, &message).await?;
+    }
     if notified_users.is_empty() {
         return Ok(());
     }
@@ -113,10 +283,16 @@ pub async fn notify_users(
         .message(ctx, bug.message_id)
         .await
         .map(|mut m| {
-            m.guild_id = Some(EOA_DISCORD);
+            m.guild_id = Some(bug.guild_id);
             m
         });
     let message_link = linked_message.as_ref().map(|m| m.link()).ok();
+    let thread_link = bug.thread_id.map(|thread_id| {
+        format!(
+            "https://discord.com/channels/{}/{}",
+            bug.guild_id.0, thread_id.0
+        )
+    });
 
     for user in notified_users {
         let channel = match user.create_dm_channel(ctx).await {
@@ -132,7 +308,8 @@ pub async fn notify_users(
         if let Err(e) = channel
             .send_message(ctx, |m| {
                 m.content(format!(
-                    "**Eras of Arda Bugtracker notification {}**\n\n{}\n ",
+                    "**{} notification {}**\n\n{}\n ",
+                    tracker_name(ctx, bug.guild_id),
                     ReactionType::from(EmojiIdentifier {
                         animated: false,
                         id: TERMITE_EMOJI,
@@ -140,7 +317,7 @@ pub async fn notify_users(
                     }),
                     message,
                 ))
-                .embed(create_bug_embed!(bug, linked_message))
+                .embed(create_bug_embed!(ctx, bug, linked_message))
                 .components(|c| {
                     c.create_action_row(|a| {
                         if let Some(link) = message_link.as_ref() {
@@ -150,6 +327,13 @@ pub async fn notify_users(
                                     .url(link)
                             });
                         }
+                        if let Some(link) = thread_link.as_ref() {
+                            a.create_button(|b| {
+                                b.style(ButtonStyle::Link)
+                                    .label("Discussion thread")
+                                    .url(link)
+                            });
+                        }
                         a.create_button(|b| {
                             b.style(ButtonStyle::Danger)
                                 .label("Unsubscribe")
@@ -169,12 +353,118 @@ pub async fn notify_users(
     res
 }
 
+/// Archives and locks the discussion thread of a bug when its new status is not open,
+/// and reopens it otherwise.
+pub async fn update_bug_thread(ctx: &Context, bug_id: u64, status: &BugStatus) -> CommandResult {
+    let bug = get_bug_from_id(ctx, bug_id).await?;
+    let thread_id = match bug.thread_id {
+        Some(thread_id) => thread_id,
+        None => return Ok(()),
+    };
+    let archived = !status.open;
+
+    if archived {
+        // sending a message would unarchive the thread, so it is sent beforehand
+        thread_id
+            .say(
+                ctx,
+                format!(
+                    "{}-{} has been marked as {}.",
+                    bug.prefix,
+                    bug_id,
+                    status.as_str()
+                ),
+            )
+            .await?;
+    }
+    thread_id
+        .edit_thread(ctx, |t| t.archived(archived).locked(archived))
+        .await?;
+
+    Ok(())
+}
+
+macro_rules! create_duplicate_buttons {
+    ($disabled:expr $(, $original:expr)?) => {
+        |c| {
+            c.create_action_row(|a| {
+                $(
+                    a.create_button(|b| {
+                        b.style(ButtonStyle::Success)
+                            .label(format!("Add to {}", $original))
+                            .custom_id("add_to_bug")
+                            .disabled($disabled)
+                    });
+                )?
+                a.create_button(|b| {
+                    b.style(ButtonStyle::Primary)
+                        .label("Track anyway")
+                        .custom_id("track_anyway")
+                        .disabled($disabled)
+                });
+                a.create_button(|b| {
+                    b.style(ButtonStyle::Secondary)
+                        .label("Cancel")
+                        .custom_id("cancel_track")
+                        .disabled($disabled)
+                })
+            })
+        }
+    };
+}
+
 #[command]
-#[checks(is_admin, is_lotr_discord)]
+#[checks(has_bugtracker, is_bugtracker_moderator)]
 #[aliases(report)]
+#[only_in(guilds)]
 pub async fn track(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    let category = args.single::<BugCategory>().unwrap_or_default();
-    let status = args.single::<BugStatus>().unwrap_or_default();
+    let tracker = get_tracker!(ctx, msg);
+
+    let open_thread = args
+        .current()
+        .map_or(false, |arg| arg.eq_ignore_ascii_case("thread"));
+    if open_thread {
+        args.advance();
+    }
+    let issue_sync = if args
+        .current()
+        .map_or(false, |arg| arg.eq_ignore_ascii_case("mirror"))
+    {
+        args.advance();
+        match get_issue_sync(ctx, tracker.guild_id).await {
+            Some(issue_sync) => Some(issue_sync),
+            None => {
+                failure!(
+                    ctx,
+                    msg,
+                    "Bugs are not mirrored on this server! Set it up with \
+`!bug tracker mirror <owner/repo>`."
+                );
+                return Ok(());
+            }
+        }
+    } else {
+        None
+    };
+    let category = args
+        .single::<BugCategory>()
+        .unwrap_or_else(|_| tracker.default_category());
+    if !tracker.has_category(category) {
+        failure!(
+            ctx,
+            msg,
+            "The category {} is not enabled on this bug tracker!",
+            category
+        );
+        return Ok(());
+    }
+    let status = match args.current().and_then(|arg| tracker.parse_status(arg)) {
+        Some(status) => {
+            args.advance();
+            status
+        }
+        _ => tracker.default_status(),
+    };
 
     let title = args.rest();
     if title.is_empty() {
@@ -189,20 +479,236 @@ pub async fn track(ctx: &Context, msg: &Message, mut args: Args) -> CommandResul
         return Ok(());
     };
 
-    let bug_id =
-        match add_bug_report(ctx, referenced_message, title.to_string(), status, category).await {
-            Ok(bug_id) => bug_id,
-            Err(e) => {
-                failure!(ctx, msg, "Could not submit the bug report!");
-                return Err(e);
+    let crash_log = match read_crash_log(referenced_message).await {
+        Ok(crash_log) => crash_log,
+        Err(e) => {
+            failure!(ctx, msg, e);
+            None
+        }
+    };
+
+    let crash_duplicate = match crash_log.as_ref().and_then(CrashLog::signature) {
+        Some(signature) => find_crash_duplicate(ctx, tracker.guild_id, &signature).await?,
+        None => None,
+    };
+
+    if let Some(original) = crash_duplicate {
+        let mut prompt = msg
+            .channel_id
+            .send_message(ctx, |m| {
+                m.embed(|e| {
+                    e.author(|a| {
+                        a.name(tracker_name(ctx, tracker.guild_id));
+                        a.icon_url(crate::constants::TERMITE_IMAGE);
+                        a
+                    });
+                    e.colour(serenity::utils::Colour::ORANGE);
+                    e.title("Known crash");
+                    e.description(format!(
+                        "This crash has the same signature as an open bug:\n{} {} [{}]",
+                        original.status.marker, original, original.category
+                    ));
+                    e.footer(|f| f.text("Add this report to the existing bug, or track a new one"));
+                    e
+                })
+                .components(create_duplicate_buttons!(
+                    false,
+                    tracker.display_id(original.bug_id)
+                ))
+            })
+            .await?;
+
+        let choice = if let Some(interaction) = CollectComponentInteraction::new(ctx)
+            .timeout(Duration::from_secs(60))
+            .channel_id(msg.channel_id)
+            .message_id(prompt.id)
+            .author_id(msg.author.id)
+            .await
+        {
+            interaction
+                .create_interaction_response(ctx, |r| {
+                    r.kind(InteractionResponseType::UpdateMessage)
+                        .interaction_response_data(|d| {
+                            d.components(create_duplicate_buttons!(
+                                true,
+                                tracker.display_id(original.bug_id)
+                            ))
+                        })
+                })
+                .await?;
+            interaction.data.custom_id.clone()
+        } else {
+            prompt
+                .edit(ctx, |m| {
+                    m.components(create_duplicate_buttons!(
+                        true,
+                        tracker.display_id(original.bug_id)
+                    ))
+                })
+                .await?;
+            String::new()
+        };
+
+        match choice.as_str() {
+            "add_to_bug" => {
+                let times_seen =
+                    add_crash_sighting(ctx, original.bug_id, referenced_message).await?;
+                let reporter = referenced_message.author.id;
+                if is_notified_user(ctx, original.bug_id, reporter).await == Some(false) {
+                    add_notified_user(ctx, original.bug_id, reporter).await?;
+                }
+                termite_success!(
+                    ctx,
+                    msg,
+                    "Added this report to {}, now seen {} times. The reporter has been \
+subscribed to it.",
+                    tracker.display_id(original.bug_id),
+                    times_seen
+                );
+                return Ok(());
             }
+            "track_anyway" => (),
+            _ => {
+                failure!(ctx, msg);
+                return Ok(());
+            }
+        }
+    } else if let Some((duplicates, total)) = search_bugs(ctx, tracker.guild_id, title, 5, 0)
+        .await
+        .filter(|(duplicates, _)| !duplicates.is_empty())
+    {
+        let mut prompt = msg
+            .channel_id
+            .send_message(ctx, |m| {
+                m.embed(|e| {
+                    e.author(|a| {
+                        a.name(tracker_name(ctx, tracker.guild_id));
+                        a.icon_url(crate::constants::TERMITE_IMAGE);
+                        a
+                    });
+                    e.colour(serenity::utils::Colour::ORANGE);
+                    e.title(format!("Possible duplicates (Total: {})", total));
+                    e.description(
+                        duplicates
+                            .iter()
+                            .map(|b| format!("{} {} [{}]", b.status.marker, b, b.category))
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                    );
+                    e.footer(|f| f.text("Check these bugs before tracking a new one"));
+                    e
+                })
+                .components(create_duplicate_buttons!(false))
+            })
+            .await?;
+
+        let confirmed = if let Some(interaction) = CollectComponentInteraction::new(ctx)
+            .timeout(Duration::from_secs(60))
+            .channel_id(msg.channel_id)
+            .message_id(prompt.id)
+            .author_id(msg.author.id)
+            .await
+        {
+            interaction
+                .create_interaction_response(ctx, |r| {
+                    r.kind(InteractionResponseType::UpdateMessage)
+                        .interaction_response_data(|d| {
+                            d.components(create_duplicate_buttons!(true))
+                        })
+                })
+                .await?;
+            interaction.data.custom_id == "track_anyway"
+        } else {
+            prompt
+                .edit(ctx, |m| m.components(create_duplicate_buttons!(true)))
+                .await?;
+            false
         };
 
+        if !confirmed {
+            failure!(ctx, msg);
+            return Ok(());
+        }
+    }
+
+    let bug_id = match add_bug_report(
+        ctx,
+        tracker.guild_id,
+        referenced_message,
+        title.to_string(),
+        &status,
+        category,
+    )
+    .await
+    {
+        Ok(bug_id) => bug_id,
+        Err(e) => {
+            failure!(ctx, msg, "Could not submit the bug report!");
+            return Err(e);
+        }
+    };
+
+    if let Some(crash_log) = &crash_log {
+        if let Err(e) = set_crash_log(ctx, bug_id, crash_log).await {
+            println!(
+                "=== ERROR ===
+Could not store the crash log of bug {}
+Error: {e}
+=== END ===",
+                tracker.display_id(bug_id)
+            );
+        }
+    }
+
+    let issue_url = match &issue_sync {
+        Some(issue_sync) => {
+            match mirror_bug(
+                ctx,
+                &tracker,
+                issue_sync,
+                bug_id,
+                title,
+                referenced_message,
+                msg.author.id,
+            )
+            .await
+            {
+                Ok(issue_url) => Some(issue_url),
+                Err(e) => {
+                    println!(
+                        "=== ERROR ===
+Could not mirror bug {} in {}
+Error: {e}
+=== END ===",
+                        tracker.display_id(bug_id),
+                        issue_sync.repository
+                    );
+                    failure!(ctx, msg, "Could not open an issue mirroring the bug!");
+                    None
+                }
+            }
+        }
+        None => None,
+    };
+
     msg.channel_id
         .send_message(ctx, |m| {
+            if let Some(crash_log) = &crash_log {
+                m.embed(|e| {
+                    e.colour(serenity::utils::Colour::ORANGE)
+                        .title("Crash log summary");
+                    crash_log.add_embed_fields(e)
+                });
+            }
             m.content(format!(
-                "Tracking bug EoA-{} (priority: `{}`) [{}]",
-                bug_id, status, category
+                "Tracking bug {} (priority: `{}`) [{}]{}",
+                tracker.display_id(bug_id),
+                status,
+                category,
+                issue_url
+                    .as_ref()
+                    .map(|url| format!("\nMirrored as <{}>", url))
+                    .unwrap_or_default()
             ))
             .reference_message(referenced_message)
             .allowed_mentions(|f| f.empty_parse())
@@ -218,60 +724,308 @@ pub async fn track(ctx: &Context, msg: &Message, mut args: Args) -> CommandResul
         })
         .await?;
 
-    if let Err(e) = add_notified_user(ctx, bug_id, referenced_message.author.id).await {
-        println!(
-            "=== ERROR ===
-Could not subscribe bug author to bug EoA-{bug_id}
+    if open_thread {
+        if let Err(e) = open_bug_thread(ctx, &tracker, bug_id, title, referenced_message).await {
+            println!(
+                "=== ERROR ===
+Could not create a discussion thread for bug {}
 Error: {e}
-=== END ==="
-        );
-        return Err(e);
+=== END ===",
+                tracker.display_id(bug_id)
+            );
+            failure!(ctx, msg, "Could not create a discussion thread!");
+        }
     }
 
-    notify_users(
-        ctx,
-        bug_id,
-        "A bug report you submitted is being tracked in the Eras of Arda bugtracker.
-You will receive notifications when its status is changed or further information is added.",
-    )
-    .await
-}
+    if tracker.channel_id != Some(msg.channel_id) {
+        announce_bug(ctx, &tracker, bug_id, title, referenced_message).await;
+    }
 
-enum Either<'a> {
-    Message(&'a Message),
-    Interaction(&'a MessageComponentInteraction),
+    subscribe_reporter(ctx, bug_id, referenced_message.author.id).await
 }
 
-impl<'a> Either<'a> {
-    async fn failure(&self, ctx: &Context, message: &str) -> Result<(), SerenityError> {
-        match self {
-            Either::Message(msg) => failure!(ctx, msg, message),
-            Either::Interaction(interaction) => {
-                interaction
-                    .create_interaction_response(ctx, |r| {
-                        r.kind(InteractionResponseType::ChannelMessageWithSource)
-                            .interaction_response_data(|d| {
-                                d.flags(InteractionApplicationCommandCallbackDataFlags::EPHEMERAL)
-                                    .content(message)
-                            })
-                    })
-                    .await?;
-            }
-        }
-        Ok(())
+#[command]
+#[bucket("basic")]
+#[aliases(crashreport)]
+pub async fn crashlog(ctx: &Context, msg: &Message) -> CommandResult {
+    let log_message = match &msg.referenced_message {
+        Some(message) if crash_log_attachment(msg).is_none() => message.as_ref(),
+        _ => msg,
+    };
+
+    if crash_log_attachment(log_message).is_none() {
+        failure!(
+            ctx,
+            msg,
+            "You must attach a crash report or a log file, or reference a message with one!"
+        );
+        return Ok(());
     }
-}
 
-macro_rules! create_buttons {
-    ($disable_previous:expr, $disable_next:expr) => {
-        |c| {
-            c.create_action_row(|a| {
-                a.create_button(|b| {
-                    b.style(ButtonStyle::Secondary);
-                    b.label("Previous");
-                    b.custom_id("previous_page");
-                    b.emoji(ReactionType::Unicode("⬅️".into()));
-                    b.disabled($disable_previous);
+    let crash_log = match read_crash_log(log_message).await {
+        Ok(Some(crash_log)) => crash_log,
+        Ok(None) => {
+            failure!(
+                ctx,
+                msg,
+                "Could not find any crash information in this file!"
+            );
+            return Ok(());
+        }
+        Err(e) => {
+            failure!(ctx, msg, e);
+            return Ok(());
+        }
+    };
+
+    let tracker = match msg.guild_id {
+        Some(guild_id) => get_bug_tracker(ctx, guild_id).await,
+        None => None,
+    };
+    let known_crash = match (crash_log.signature(), &tracker) {
+        (Some(signature), Some(tracker)) => {
+            find_crash_duplicate(ctx, tracker.guild_id, &signature).await?
+        }
+        _ => None,
+    };
+
+    // the crash is reported again: count it, and subscribe its reporter to the bug.
+    // Only the reporter themselves or a moderator can do so, anyone else just gets the summary
+    let can_record = match &tracker {
+        Some(tracker) => {
+            log_message.author.id == msg.author.id
+                || is_tracker_moderator(ctx, tracker.guild_id, msg.author.id).await
+        }
+        None => false,
+    };
+    let times_seen = match &known_crash {
+        Some(original) if can_record => {
+            let reporter = log_message.author.id;
+            if is_notified_user(ctx, original.bug_id, reporter).await == Some(false) {
+                add_notified_user(ctx, original.bug_id, reporter).await?;
+            }
+            Some(add_crash_sighting(ctx, original.bug_id, log_message).await?)
+        }
+        _ => None,
+    };
+
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.colour(serenity::utils::Colour::ORANGE)
+                    .title("Crash log summary");
+                crash_log.add_embed_fields(e);
+                if let Some(original) = &known_crash {
+                    let description = match times_seen {
+                        Some(times_seen) => format!(
+                            "This crash is already tracked as {}-{}, and has been seen {} times. \
+The reporter has been subscribed to it.",
+                            original.prefix, original.bug_id, times_seen
+                        ),
+                        None => format!(
+                            "This crash is already tracked as {}-{}.",
+                            original.prefix, original.bug_id
+                        ),
+                    };
+                    e.field("Known crash", description, false);
+                }
+                e
+            })
+            .reference_message(log_message)
+            .allowed_mentions(|f| f.empty_parse())
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// Opens a discussion thread on the reported message of a new bug
+async fn open_bug_thread(
+    ctx: &Context,
+    tracker: &BugTracker,
+    bug_id: u64,
+    title: &str,
+    reported_message: &Message,
+) -> CommandResult {
+    // thread names are limited to 100 characters
+    let thread_name: String = format!("{}: {}", tracker.display_id(bug_id), title)
+        .chars()
+        .take(100)
+        .collect();
+    let thread = reported_message
+        .channel_id
+        .create_public_thread(ctx, reported_message.id, |t| t.name(thread_name))
+        .await?;
+    set_bug_thread(ctx, bug_id, thread.id).await
+}
+
+/// Opens an issue mirroring a new bug, and links it to the bug. Returns the url of the issue.
+async fn mirror_bug(
+    ctx: &Context,
+    tracker: &BugTracker,
+    issue_sync: &IssueSync,
+    bug_id: u64,
+    title: &str,
+    reported_message: &Message,
+    user_id: UserId,
+) -> CommandResult<String> {
+    let issue_tracker = issue_sync.issue_tracker()?;
+    let body = format!(
+        "{}\n\nReported on Discord by {}: {}",
+        reported_message
+            .content
+            .lines()
+            .map(|line| format!("> {}", line))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        reported_message.author.tag(),
+        bug_message_link(
+            tracker.guild_id,
+            reported_message.channel_id,
+            reported_message.id
+        )
+    );
+
+    let client = get_reqwest_client!(ctx);
+    let issue = issue_tracker
+        .open_issue(
+            &client,
+            &format!("{}: {}", tracker.display_id(bug_id), title),
+            &body,
+        )
+        .await?;
+
+    set_mirrored_issue(ctx, bug_id, issue.number, issue.state).await?;
+    add_link(
+        ctx,
+        bug_id,
+        &issue.url,
+        &format!("{} #{}", issue_tracker.issue_name(), issue.number),
+        user_id,
+    )
+    .await
+    .ok_or("Could not link the issue to the bug")?;
+
+    Ok(issue.url)
+}
+
+/// Announces a new bug in the channel of its tracker, if there is one
+async fn announce_bug(
+    ctx: &Context,
+    tracker: &BugTracker,
+    bug_id: u64,
+    title: &str,
+    reported_message: &Message,
+) {
+    let channel_id = match tracker.channel_id {
+        Some(channel_id) => channel_id,
+        None => return,
+    };
+    let message_link = bug_message_link(
+        tracker.guild_id,
+        reported_message.channel_id,
+        reported_message.id,
+    );
+    if let Err(e) = channel_id
+        .send_message(ctx, |m| {
+            m.content(format!("New bug {}: {}", tracker.display_id(bug_id), title))
+                .allowed_mentions(|f| f.empty_parse())
+                .components(|c| {
+                    c.create_action_row(|a| {
+                        a.create_button(|b| {
+                            b.style(ButtonStyle::Primary)
+                                .label("Subscribe")
+                                .custom_id(format!("bug_subscribe__{bug_id}"))
+                        });
+                        a.create_button(|b| {
+                            b.style(ButtonStyle::Link)
+                                .label("Message link")
+                                .url(message_link)
+                        })
+                    })
+                })
+        })
+        .await
+    {
+        println!(
+            "=== ERROR ===
+Could not announce bug {} in the tracker channel
+Error: {e}
+=== END ===",
+            tracker.display_id(bug_id)
+        );
+    }
+}
+
+/// Subscribes the author of a new bug report to its notifications
+async fn subscribe_reporter(ctx: &Context, bug_id: u64, reporter: UserId) -> CommandResult {
+    if let Err(e) = add_notified_user(ctx, bug_id, reporter).await {
+        println!(
+            "=== ERROR ===
+Could not subscribe bug author to bug #{bug_id}
+Error: {e}
+=== END ==="
+        );
+        return Err(e);
+    }
+
+    notify_users(
+        ctx,
+        bug_id,
+        NotificationKind::Update,
+        "A bug report you submitted is being tracked in the bugtracker.
+You will receive notifications when its status is changed or further information is added.",
+    )
+    .await
+}
+
+enum Either<'a> {
+    Message(&'a Message),
+    Interaction(&'a MessageComponentInteraction),
+    Command(&'a ApplicationCommandInteraction),
+}
+
+impl<'a> Either<'a> {
+    async fn failure(&self, ctx: &Context, message: &str) -> Result<(), SerenityError> {
+        match self {
+            Either::Message(msg) => failure!(ctx, msg, message),
+            Either::Interaction(interaction) => {
+                interaction
+                    .create_interaction_response(ctx, |r| {
+                        r.kind(InteractionResponseType::ChannelMessageWithSource)
+                            .interaction_response_data(|d| {
+                                d.flags(InteractionApplicationCommandCallbackDataFlags::EPHEMERAL)
+                                    .content(message)
+                            })
+                    })
+                    .await?;
+            }
+            Either::Command(interaction) => {
+                interaction
+                    .create_interaction_response(ctx, |r| {
+                        r.kind(InteractionResponseType::ChannelMessageWithSource)
+                            .interaction_response_data(|d| {
+                                d.flags(InteractionApplicationCommandCallbackDataFlags::EPHEMERAL)
+                                    .content(message)
+                            })
+                    })
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+macro_rules! create_buttons {
+    ($disable_previous:expr, $disable_next:expr) => {
+        |c| {
+            c.create_action_row(|a| {
+                a.create_button(|b| {
+                    b.style(ButtonStyle::Secondary);
+                    b.label("Previous");
+                    b.custom_id("previous_page");
+                    b.emoji(ReactionType::Unicode("⬅️".into()));
+                    b.disabled($disable_previous);
                     b
                 });
                 a.create_button(|b| {
@@ -289,19 +1043,117 @@ macro_rules! create_buttons {
     };
 }
 
+struct BugPage {
+    guild_id: GuildId,
+    title: String,
+    colour: serenity::utils::Colour,
+    content: String,
+    content_alt: &'static str,
+    total: u32,
+}
+
+async fn send_bug_page(
+    ctx: &Context,
+    bug_page: BugPage,
+    limit: u32,
+    page: u32,
+    reply_to: Either<'_>,
+) -> Result<Option<Message>, SerenityError> {
+    let BugPage {
+        guild_id,
+        title,
+        colour,
+        content,
+        content_alt,
+        total,
+    } = bug_page;
+
+    if content.len() > 4096 {
+        reply_to
+            .failure(
+                ctx,
+                "Too many bugs to display. Consider lowering the limit.",
+            )
+            .await?;
+        return Err(SerenityError::Other("too_many_bugs"));
+    }
+
+    macro_rules! create_embed_reponse {
+        () => {
+            |e| {
+                e.author(|a| {
+                    a.name(tracker_name(ctx, guild_id));
+                    a.icon_url(crate::constants::TERMITE_IMAGE);
+                    a
+                });
+                e.colour(colour);
+                e.title(title);
+                e.description(if content.is_empty() {
+                    content_alt
+                } else {
+                    &content
+                });
+                e.footer(|f| f.text(format!("Page {}/{}", page, (total.max(1) - 1) / limit + 1)));
+                e
+            }
+        };
+    }
+
+    match reply_to {
+        Either::Interaction(interaction) => {
+            interaction
+                .create_interaction_response(ctx, |r| {
+                    r.kind(InteractionResponseType::UpdateMessage)
+                        .interaction_response_data(|m| {
+                            m.embeds([])
+                                .create_embed(create_embed_reponse!())
+                                .components(create_buttons!(page <= 1, (page * limit) >= total))
+                        })
+                })
+                .await?;
+
+            Ok(None)
+        }
+        Either::Message(msg) => {
+            let response_message = msg
+                .channel_id
+                .send_message(ctx, |m| {
+                    m.embed(create_embed_reponse!())
+                        .components(create_buttons!(page <= 1, (page * limit) >= total))
+                })
+                .await?;
+            Ok(Some(response_message))
+        }
+        Either::Command(interaction) => {
+            interaction
+                .create_interaction_response(ctx, |r| {
+                    r.kind(InteractionResponseType::ChannelMessageWithSource)
+                        .interaction_response_data(|m| {
+                            m.create_embed(create_embed_reponse!())
+                                .components(create_buttons!(page <= 1, (page * limit) >= total))
+                        })
+                })
+                .await?;
+            Ok(Some(interaction.get_interaction_response(ctx).await?))
+        }
+    }
+}
+
 async fn display_bugs(
     ctx: &Context,
-    status: Option<BugStatus>,
+    filter: &BugFilter,
     limit: u32,
     display_order: BugOrder,
-    category: Option<BugCategory>,
     page: u32,
     reply_to: Either<'_>,
 ) -> Result<Option<Message>, SerenityError> {
     assert_ne!(page, 0);
 
+    let status = &filter.status;
+    let category = filter.category;
+
     if let Some((bugs, total_bugs)) =
-        get_bug_list(ctx, status, limit, display_order, category, page - 1).await
+        get_bug_list(ctx, filter, limit, display_order, page - 1).await
     {
         if total_bugs != 0 && (page - 1) * limit >= total_bugs {
             reply_to.failure(ctx, "Page number too high, consider calling `!bugs` and using the navigation arrows.").await?;
@@ -315,7 +1167,7 @@ async fn display_bugs(
         if let Some(status) = status {
             title = format!(
                 "{} Bug reports (Status: {}){} (Total: {})",
-                status.marker(),
+                status.marker,
                 status,
                 if let Some(c) = category {
                     format!(" [{c}]")
@@ -340,7 +1192,7 @@ async fn display_bugs(
                 })
                 .collect::<Vec<_>>()
                 .join("\n");
-            colour = status.colour();
+            colour = status.colour;
         } else {
             title = format!(
                 "Open bug reports{} (Total: {})",
@@ -357,7 +1209,7 @@ async fn display_bugs(
                 .map(|b| {
                     format!(
                         "{} {}{}",
-                        b.status.marker(),
+                        b.status.marker,
                         b,
                         if category.is_none() {
                             format!(" [{}]", b.category)
@@ -371,91 +1223,177 @@ async fn display_bugs(
             colour = serenity::utils::Colour::LIGHT_GREY;
         }
 
-        if content.len() > 4096 {
+        let content = if filter.labels.is_empty() || bugs.is_empty() {
+            content
+        } else {
+            format!(
+                "_Labels: {}_\n\n{}",
+                filter
+                    .labels
+                    .iter()
+                    .map(|label| format!("`{}`", label))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                content
+            )
+        };
+        let content = match &filter.version {
+            Some(version) if !bugs.is_empty() => format!("_Version: `{}`_\n\n{}", version, content),
+            _ => content,
+        };
+        let content = match filter.assignee {
+            Some(assignee) if bugs.is_empty() => {
+                format!("_No bugs assigned to <@{}>!_", assignee.0)
+            }
+            Some(assignee) => format!("_Assigned to <@{}>_\n\n{}", assignee.0, content),
+            None => content,
+        };
+
+        send_bug_page(
+            ctx,
+            BugPage {
+                guild_id: filter.guild_id.unwrap_or_default(),
+                title,
+                colour,
+                content,
+                content_alt,
+                total: total_bugs,
+            },
+            limit,
+            page,
+            reply_to,
+        )
+        .await
+    } else {
+        Err(SerenityError::Other(
+            "Could not get bugs from the database!",
+        ))
+    }
+}
+
+async fn display_search_results(
+    ctx: &Context,
+    guild_id: GuildId,
+    terms: &str,
+    limit: u32,
+    page: u32,
+    reply_to: Either<'_>,
+) -> Result<Option<Message>, SerenityError> {
+    assert_ne!(page, 0);
+
+    if let Some((bugs, total_bugs)) = search_bugs(ctx, guild_id, terms, limit, page - 1).await {
+        if total_bugs != 0 && (page - 1) * limit >= total_bugs {
             reply_to
                 .failure(
                     ctx,
-                    "Too many bugs to display. Consider lowering the limit.",
+                    "Page number too high, consider using the navigation arrows.",
                 )
                 .await?;
-            return Err(SerenityError::Other("too_many_bugs"));
+            return Err(SerenityError::Other("page_too_high"));
         }
 
-        macro_rules! create_embed_reponse {
-            () => {
-                |e| {
-                    e.author(|a| {
-                        a.name("Eras of Arda Bugtracker");
-                        a.icon_url(crate::constants::TERMITE_IMAGE);
-                        a
-                    });
-                    e.colour(colour);
-                    e.title(title);
-                    e.description(if bugs.is_empty() {
-                        content_alt
-                    } else {
-                        &content
-                    });
-                    e.footer(|f| {
-                        f.text(format!(
-                            "Page {}/{}",
-                            page,
-                            (total_bugs.max(1) - 1) / limit + 1
-                        ))
-                    });
-                    e
-                }
-            };
-        }
+        send_bug_page(
+            ctx,
+            BugPage {
+                guild_id,
+                title: format!("Search results for \"{}\" (Total: {})", terms, total_bugs),
+                colour: serenity::utils::Colour::LIGHT_GREY,
+                content: bugs
+                    .iter()
+                    .map(|b| format!("{} {} [{}]", b.status.marker, b, b.category))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                content_alt: "_No matching bugs!_",
+                total: total_bugs,
+            },
+            limit,
+            page,
+            reply_to,
+        )
+        .await
+    } else {
+        Err(SerenityError::Other(
+            "Could not search bugs in the database!",
+        ))
+    }
+}
 
-        match reply_to {
-            Either::Interaction(interaction) => {
-                interaction
-                    .create_interaction_response(ctx, |r| {
-                        r.kind(InteractionResponseType::UpdateMessage)
-                            .interaction_response_data(|m| {
-                                m.embeds([])
-                                    .create_embed(create_embed_reponse!())
-                                    .components(create_buttons!(
-                                        page <= 1,
-                                        (page * limit) >= total_bugs
-                                    ))
-                            })
-                    })
-                    .await?;
+/// Parses the leading filter keywords of the `!buglist` command, or returns the message
+/// of the first invalid one
+fn parse_bug_filter(
+    msg: &Message,
+    tracker: &BugTracker,
+    args: &mut Args,
+) -> Result<BugFilter, String> {
+    let mut filter = BugFilter {
+        guild_id: Some(tracker.guild_id),
+        ..Default::default()
+    };
 
-                Ok(None)
+    while let Some(current) = args.current() {
+        if current.eq_ignore_ascii_case("mine") {
+            filter.assignee = Some(msg.author.id);
+        } else if let Some(assignee) = current.strip_prefix("assignee:") {
+            let assignee = if assignee.is_empty() {
+                args.advance();
+                args.current().unwrap_or_default()
+            } else {
+                assignee
+            };
+            match assignee.parse::<UserId>() {
+                Ok(assignee) => filter.assignee = Some(assignee),
+                Err(_) => {
+                    return Err(format!(
+                        "`{}` is not a valid assignee, mention them or use their id.",
+                        assignee
+                    ))
+                }
             }
-            Either::Message(msg) => {
-                let response_message = msg
-                    .channel_id
-                    .send_message(ctx, |m| {
-                        m.embed(create_embed_reponse!())
-                            .components(create_buttons!(page <= 1, (page * limit) >= total_bugs))
-                    })
-                    .await?;
-                Ok(Some(response_message))
+        } else if let Some(label) = current.strip_prefix("label:") {
+            match normalize_label(label) {
+                Some(label) => filter.labels.push(label),
+                None => return Err(format!("`{}` is not a valid label.", label)),
             }
+        } else if let Some(version) = current.strip_prefix("version:") {
+            match normalize_version(version) {
+                Some(version) => filter.version = Some(version),
+                None => return Err(format!("`{}` is not a valid version.", version)),
+            }
+        } else {
+            break;
         }
-    } else {
-        Err(SerenityError::Other(
-            "Could not get bugs from the database!",
-        ))
+        args.advance();
     }
+
+    Ok(filter)
 }
 
 #[command]
+#[checks(has_bugtracker)]
 #[aliases(bugs)]
-#[sub_commands(bugtracker_help)]
+#[sub_commands(bugtracker_help, buglist_export)]
+#[only_in(guilds)]
 pub async fn buglist(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    let category = args.single::<BugCategory>().ok();
-    let status = args.single::<BugStatus>().ok();
+    let tracker = get_tracker!(ctx, msg);
+    let mut filter = match parse_bug_filter(msg, &tracker, &mut args) {
+        Ok(filter) => filter,
+        Err(e) => {
+            failure!(ctx, msg, e);
+            return Ok(());
+        }
+    };
+    filter.category = args.single::<BugCategory>().ok();
+    filter.status = args.current().and_then(|arg| tracker.parse_status(arg));
+    if filter.status.is_some() {
+        args.advance();
+    }
 
     let mut display_order = match args.current() {
         Some("latest") => BugOrder::Chronological(false),
         Some("oldest") => BugOrder::Chronological(true),
         Some("highest") => BugOrder::Priority(false),
         Some("lowest") => BugOrder::Priority(true),
+        Some("affected") => BugOrder::Affected(false),
         _ => BugOrder::None,
     };
     if let BugOrder::None = display_order {
@@ -474,12 +1412,11 @@ pub async fn buglist(ctx: &Context, msg: &Message, mut args: Args) -> CommandRes
     }
     .unwrap_or(10);
 
-    let mut response_message = match display_bugs(
+    let response_message = match display_bugs(
         ctx,
-        status,
+        &filter,
         limit,
         display_order,
-        category,
         page,
         Either::Message(msg),
     )
@@ -491,13 +1428,35 @@ pub async fn buglist(ctx: &Context, msg: &Message, mut args: Args) -> CommandRes
         Err(e) => return Err(e.into()),
     };
 
-    while let Some(interaction) = CollectComponentInteraction::new(ctx)
-        .timeout(Duration::from_secs(120))
-        .channel_id(msg.channel_id)
+    paginate_bug_list(
+        ctx,
+        response_message,
+        msg.author.id,
+        &filter,
+        limit,
+        display_order,
+        page,
+    )
+    .await
+}
+
+/// Handles the navigation buttons of a bug list for 120 seconds
+async fn paginate_bug_list(
+    ctx: &Context,
+    mut response_message: Message,
+    author_id: UserId,
+    filter: &BugFilter,
+    limit: u32,
+    display_order: BugOrder,
+    mut page: u32,
+) -> CommandResult {
+    while let Some(interaction) = CollectComponentInteraction::new(ctx)
+        .timeout(Duration::from_secs(120))
+        .channel_id(response_message.channel_id)
         .message_id(response_message.id)
         .await
     {
-        if interaction.user.id != msg.author.id {
+        if interaction.user.id != author_id {
             interaction.create_interaction_response(ctx, |r| {
                 r.kind(InteractionResponseType::ChannelMessageWithSource);
                 r.interaction_response_data(|d| {
@@ -522,10 +1481,494 @@ pub async fn buglist(ctx: &Context, msg: &Message, mut args: Args) -> CommandRes
 
         let _ = display_bugs(
             ctx,
-            status,
+            filter,
             limit,
             display_order,
+            page,
+            Either::Interaction(interaction.as_ref()),
+        )
+        .await?;
+    }
+
+    response_message
+        .edit(ctx, |m| m.components(create_buttons!(true, true)))
+        .await?;
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BugRecordLink {
+    pub title: String,
+    pub url: String,
+}
+
+/// Serialized form of a bug report, used for exports and imports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BugRecord {
+    #[serde(default)]
+    pub bug_id: u64,
+    pub title: String,
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub category: String,
+    pub timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub message_link: String,
+    #[serde(default)]
+    pub links: Vec<BugRecordLink>,
+}
+
+impl From<&BugReport> for BugRecord {
+    fn from(bug: &BugReport) -> Self {
+        Self {
+            bug_id: bug.bug_id,
+            title: bug.title.clone(),
+            status: bug.status.as_str().into(),
+            category: bug.category.as_str().into(),
+            timestamp: bug.timestamp,
+            message_link: bug_message_link(bug.guild_id, bug.channel_id, bug.message_id),
+            links: bug
+                .links
+                .iter()
+                .map(|link| BugRecordLink {
+                    title: link.title.clone(),
+                    url: link.url.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+pub const BUG_CSV_HEADER: &str = "id,title,status,category,timestamp,message_link,links";
+
+impl BugRecord {
+    /// Formats the record as a CSV row matching [`BUG_CSV_HEADER`], with the id prefix
+    /// of the bug tracker.
+    ///
+    /// Links are written one per line, as the url followed by the link title.
+    pub fn to_csv_row(&self, prefix: &str) -> String {
+        [
+            format!("{}-{}", prefix, self.bug_id),
+            to_csv_safe_string(&self.title),
+            self.status.clone(),
+            self.category.clone(),
+            self.timestamp.to_rfc3339(),
+            to_csv_safe_string(&self.message_link),
+            to_csv_safe_string(
+                &self
+                    .links
+                    .iter()
+                    .map(|link| format!("{} {}", link.url, link.title))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+        ]
+        .join(",")
+    }
+}
+
+fn parse_message_link(link: &str) -> Option<(ChannelId, MessageId)> {
+    let mut ids = link
+        .strip_prefix("https://")?
+        .split('/')
+        .skip_while(|&s| s != "channels")
+        .skip(2);
+    let channel_id = ids.next()?.parse().ok()?;
+    let message_id = ids.next()?.parse().ok()?;
+    Some((ChannelId(channel_id), MessageId(message_id)))
+}
+
+impl BugRecord {
+    /// Reads a record from a CSV row, using the column indices of the header row
+    fn from_csv_row(header: &[String], row: &[String]) -> Result<Self, String> {
+        let column = |name: &str| {
+            header
+                .iter()
+                .position(|h| h.trim().eq_ignore_ascii_case(name))
+                .and_then(|i| row.get(i))
+                .map_or("", |s| s.trim())
+        };
+
+        let timestamp = DateTime::parse_from_rfc3339(column("timestamp"))
+            .map_err(|_| format!("invalid timestamp `{}`", column("timestamp")))?
+            .with_timezone(&Utc);
+        let links = column("links")
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let (url, title) = line.split_once(' ').unwrap_or((line, line));
+                BugRecordLink {
+                    title: title.trim().into(),
+                    url: url.into(),
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            // the id prefix can be the one of another bug tracker
+            bug_id: column("id")
+                .rsplit('-')
+                .next()
+                .and_then(|id| id.parse().ok())
+                .unwrap_or_default(),
+            title: column("title").into(),
+            status: column("status").into(),
+            category: column("category").into(),
+            timestamp,
+            message_link: column("message_link").into(),
+            links,
+        })
+    }
+
+    /// Validates the record for the bug tracker of a guild.
+    /// Records without a message link are attached to `msg`.
+    fn into_bug_report(self, msg: &Message, tracker: &BugTracker) -> Result<BugReport, String> {
+        let title = self.title.trim();
+        if title.is_empty() {
+            return Err("empty title".into());
+        }
+        if title.len() > 255 {
+            return Err("title is longer than 255 bytes".into());
+        }
+        let status = if self.status.is_empty() {
+            tracker.default_status()
+        } else {
+            tracker
+                .parse_status(&self.status)
+                .ok_or_else(|| format!("unknown status `{}`", self.status))?
+        };
+        let category = if self.category.is_empty() {
+            tracker.default_category()
+        } else {
+            self.category
+                .parse()
+                .ok()
+                .filter(|&category| tracker.has_category(category))
+                .ok_or_else(|| format!("unknown category `{}`", self.category))?
+        };
+        let (channel_id, message_id) = if self.message_link.is_empty() {
+            (msg.channel_id, msg.id)
+        } else {
+            parse_message_link(&self.message_link)
+                .ok_or_else(|| format!("invalid message link `{}`", self.message_link))?
+        };
+        let links = self
+            .links
+            .into_iter()
+            .map(|link| {
+                if link.url.starts_with("http://") || link.url.starts_with("https://") {
+                    Ok(BugLink {
+                        id: 0,
+                        title: if link.title.is_empty() {
+                            link.url.clone()
+                        } else {
+                            link.title
+                        },
+                        url: link.url,
+                    })
+                } else {
+                    Err(format!("invalid link url `{}`", link.url))
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(BugReport {
+            bug_id: 0,
+            guild_id: tracker.guild_id,
+            prefix: tracker.prefix.clone(),
+            channel_id,
+            message_id,
+            title: title.into(),
+            status,
+            timestamp: self.timestamp,
             category,
+            links,
+            duplicate_of: None,
+            assignee: None,
+            labels: Vec::new(),
+            thread_id: None,
+            affected_version: None,
+            fixed_in: None,
+            crash_log: None,
+            times_seen: 1,
+            affected_users: 0,
+        })
+    }
+}
+
+#[command]
+#[checks(has_bugtracker)]
+#[aliases("export")]
+#[bucket("basic")]
+#[only_in(guilds)]
+pub async fn buglist_export(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let tracker = get_tracker!(ctx, msg);
+    let json = match args.current() {
+        Some(format) if format.eq_ignore_ascii_case("json") => {
+            args.advance();
+            true
+        }
+        Some(format) if format.eq_ignore_ascii_case("csv") => {
+            args.advance();
+            false
+        }
+        _ => false,
+    };
+
+    let mut filter = match parse_bug_filter(msg, &tracker, &mut args) {
+        Ok(filter) => filter,
+        Err(e) => {
+            failure!(ctx, msg, e);
+            return Ok(());
+        }
+    };
+    // accept the status and the category in any order
+    for _ in 0..2 {
+        if let Some(status) = args.current().and_then(|arg| tracker.parse_status(arg)) {
+            args.advance();
+            filter.status = Some(status);
+        } else if let Ok(category) = args.single::<BugCategory>() {
+            filter.category = Some(category);
+        }
+    }
+
+    let bugs = match get_bug_reports(ctx, &filter).await {
+        Ok(bugs) => bugs,
+        Err(e) => {
+            failure!(ctx, msg, "Could not get bugs from the database!");
+            return Err(e);
+        }
+    };
+    let records: Vec<BugRecord> = bugs.iter().map(BugRecord::from).collect();
+
+    let (file, extension) = if json {
+        (serde_json::to_vec_pretty(&records)?, "json")
+    } else {
+        let mut csv = String::from(BUG_CSV_HEADER);
+        for record in &records {
+            csv.push_str("\r\n");
+            csv.push_str(&record.to_csv_row(&tracker.prefix));
+        }
+        (csv.into_bytes(), "csv")
+    };
+    let filename = format!("{}_bugs.{}", tracker.prefix.to_lowercase(), extension);
+
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.content(format!("Exported {} bug reports.", records.len()))
+                .reference_message(msg)
+                .add_file((file.as_slice(), filename.as_str()))
+        })
+        .await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(has_bugtracker, is_bugtracker_moderator)]
+#[aliases("import")]
+#[only_in(guilds)]
+pub async fn bug_import(ctx: &Context, msg: &Message) -> CommandResult {
+    let tracker = get_tracker!(ctx, msg);
+    let attachment = match msg.attachments.first() {
+        Some(attachment) => attachment,
+        None => {
+            failure!(
+                ctx,
+                msg,
+                "You must attach a JSON or CSV file, in the format of `!bugs export`."
+            );
+            return Ok(());
+        }
+    };
+    let data = match download_attachment(attachment).await {
+        Ok(data) => data,
+        Err(e) => {
+            handle_json_error!(ctx, msg, e);
+            return Ok(());
+        }
+    };
+
+    // each row is numbered starting from 1, excluding the CSV header
+    let records: Vec<Result<BugRecord, String>> =
+        if attachment.filename.to_lowercase().ends_with(".csv") {
+            let rows = match std::str::from_utf8(&data).ok().and_then(parse_csv) {
+                Some(rows) if !rows.is_empty() => rows,
+                _ => {
+                    failure!(ctx, msg, "Could not read the CSV file!");
+                    return Ok(());
+                }
+            };
+            let header = &rows[0];
+            if !["title", "timestamp"]
+                .iter()
+                .all(|col| header.iter().any(|h| h.trim().eq_ignore_ascii_case(col)))
+            {
+                failure!(
+                    ctx,
+                    msg,
+                    "The CSV header must contain at least the `title` and `timestamp` columns."
+                );
+                return Ok(());
+            }
+            rows[1..]
+                .iter()
+                .map(|row| BugRecord::from_csv_row(header, row))
+                .collect()
+        } else {
+            match serde_json::from_slice::<Vec<serde_json::Value>>(&data) {
+                Ok(values) => values
+                    .into_iter()
+                    .map(|value| serde_json::from_value(value).map_err(|e| e.to_string()))
+                    .collect(),
+                Err(e) => {
+                    let e = JsonMessageError::JsonError(e);
+                    handle_json_error!(ctx, msg, e);
+                    return Ok(());
+                }
+            }
+        };
+
+    let mut bugs = Vec::new();
+    let mut rejected = Vec::new();
+    for (row, record) in records.into_iter().enumerate() {
+        match record.and_then(|record| record.into_bug_report(msg, &tracker)) {
+            Ok(bug) => bugs.push(bug),
+            Err(reason) => rejected.push(format!("Row {}: {}", row + 1, reason)),
+        }
+    }
+
+    let bug_ids = match import_bug_reports(ctx, &bugs).await {
+        Ok(bug_ids) => bug_ids,
+        Err(e) => {
+            failure!(
+                ctx,
+                msg,
+                "Could not import the bug reports, none of them were imported!"
+            );
+            return Err(e);
+        }
+    };
+
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.author(|a| {
+                    a.name(tracker_name(ctx, tracker.guild_id));
+                    a.icon_url(crate::constants::TERMITE_IMAGE);
+                    a
+                });
+                e.title("Bug import");
+                e.colour(serenity::utils::Colour::DARK_GREEN);
+                e.field(
+                    format!("Created: {}", bug_ids.len()),
+                    match (bug_ids.first(), bug_ids.last()) {
+                        (Some(first), Some(last)) if first == last => tracker.display_id(*first),
+                        (Some(first), Some(last)) => format!(
+                            "{} to {}",
+                            tracker.display_id(*first),
+                            tracker.display_id(*last)
+                        ),
+                        _ => "None".into(),
+                    },
+                    false,
+                );
+                if !rejected.is_empty() {
+                    let mut content = String::new();
+                    for (i, reason) in rejected.iter().enumerate() {
+                        if content.len() + reason.len() > 1000 {
+                            content.push_str(&format!("...and {} more", rejected.len() - i));
+                            break;
+                        }
+                        content.push_str(reason);
+                        content.push('\n');
+                    }
+                    e.field(format!("Rejected: {}", rejected.len()), content, false);
+                }
+                e
+            })
+            .reference_message(msg)
+        })
+        .await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(has_bugtracker)]
+#[aliases("search")]
+#[only_in(guilds)]
+pub async fn bug_search(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let tracker = get_tracker!(ctx, msg);
+    let limit = if args.current() == Some("limit") {
+        args.advance();
+        args.single::<u32>().ok()
+    } else {
+        None
+    }
+    .unwrap_or(10);
+
+    let terms = args.rest().trim();
+    if terms.is_empty() {
+        failure!(ctx, msg, "You must provide some search terms!");
+        return Ok(());
+    }
+
+    let mut page = 1;
+
+    let mut response_message = match display_search_results(
+        ctx,
+        tracker.guild_id,
+        terms,
+        limit,
+        page,
+        Either::Message(msg),
+    )
+    .await
+    {
+        Ok(Some(msg)) => msg,
+        Ok(None) => unreachable!(),
+        Err(SerenityError::Other("page_too_high" | "too_many_bugs")) => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+
+    while let Some(interaction) = CollectComponentInteraction::new(ctx)
+        .timeout(Duration::from_secs(120))
+        .channel_id(msg.channel_id)
+        .message_id(response_message.id)
+        .await
+    {
+        if interaction.user.id != msg.author.id {
+            interaction.create_interaction_response(ctx, |r| {
+                r.kind(InteractionResponseType::ChannelMessageWithSource);
+                r.interaction_response_data(|d| {
+                    d.content("You are not the original user of the command! Call `!bug search` yourself to use the buttons.");
+                    d.flags(InteractionApplicationCommandCallbackDataFlags::EPHEMERAL)
+                })
+            })
+            .await?;
+            continue;
+        }
+        match interaction.data.custom_id.as_str() {
+            "previous_page" => {
+                if page > 1 {
+                    page -= 1;
+                }
+            }
+            "next_page" => {
+                page += 1;
+            }
+            _ => (),
+        }
+
+        let _ = display_search_results(
+            ctx,
+            tracker.guild_id,
+            terms,
+            limit,
             page,
             Either::Interaction(interaction.as_ref()),
         )
@@ -543,42 +1986,54 @@ pub async fn buglist(ctx: &Context, msg: &Message, mut args: Args) -> CommandRes
 #[sub_commands(
     track,
     bug_status,
+    bug_search,
+    bug_import,
+    bug_digest,
     resolve,
     bug_close,
+    bug_needinfo,
+    bug_duplicate,
+    bug_assign,
+    bug_unassign,
+    bug_label,
     bug_link,
     bug_rename,
+    bug_version,
+    bug_changelog,
     stats,
     bug_toggle_edition,
     bugtracker_help,
     notifications,
     unsubscribe,
-    subscribe
+    subscribe,
+    bug_tracker
 )]
+#[checks(has_bugtracker)]
+#[only_in(guilds)]
 pub async fn bug(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    let bug_id = if let Ok(bug_id) = args.single::<String>() {
-        if let Ok(bug_id) = bug_id
-            .to_uppercase()
-            .trim_start_matches("EoA-")
-            .parse::<u64>()
-        {
-            bug_id
-        } else {
-            failure!(ctx, msg, "`{}` is not a valid bug id!", bug_id);
-            return Ok(());
-        }
-    } else {
-        failure!(ctx, msg, "The first argument must be a bug id.");
-        return Ok(());
-    };
+    let tracker = get_tracker!(ctx, msg);
+    let bug_id = get_bug_id!(ctx, msg, tracker, args);
 
     let mut bug = match get_bug_from_id(ctx, bug_id).await {
         Ok(bug) => bug,
         Err(e) => {
-            failure!(ctx, msg, "Bug EoA-{} does not exist!", bug_id);
+            failure!(
+                ctx,
+                msg,
+                "Bug {} does not exist!",
+                tracker.display_id(bug_id)
+            );
             return Err(e);
         }
     };
 
+    if args
+        .current()
+        .map_or(false, |s| s.eq_ignore_ascii_case("history"))
+    {
+        return bug_history(ctx, msg, &tracker, bug_id).await;
+    }
+
     macro_rules! create_bug_buttons {
         ($message_link:expr) => {
             |c| {
@@ -588,6 +2043,11 @@ pub async fn bug(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult
                             .label("Subscribe")
                             .custom_id(format!("bug_subscribe__{bug_id}"))
                     });
+                    a.create_button(|b| {
+                        b.style(ButtonStyle::Secondary)
+                            .label("I have this too")
+                            .custom_id(format!("bug_affected__{bug_id}"))
+                    });
                     if let Some(ref link) = $message_link {
                         a.create_button(|b| {
                             b.style(ButtonStyle::Link).label("Message link").url(link)
@@ -607,6 +2067,11 @@ pub async fn bug(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult
                             .label("Subscribe")
                             .custom_id(format!("bug_subscribe__{bug_id}"))
                     });
+                    a.create_button(|b| {
+                        b.style(ButtonStyle::Secondary)
+                            .label("I have this too")
+                            .custom_id(format!("bug_affected__{bug_id}"))
+                    });
                     if let Some(link) = $message_link.as_ref() {
                         a.create_button(|b| {
                             b.style(ButtonStyle::Link).label("Message link").url(link)
@@ -639,29 +2104,18 @@ pub async fn bug(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult
         .message(ctx, bug.message_id)
         .await
         .map(|mut m| {
-            m.guild_id = Some(EOA_DISCORD);
+            m.guild_id = Some(bug.guild_id);
             m
         });
     let message_link = linked_message.as_ref().map(|m| m.link()).ok();
 
-    let is_lotr_discord = msg.guild_id == Some(EOA_DISCORD);
-    let is_admin = if let Some(guild_id) = msg.guild_id {
-        is_admin_function(ctx, guild_id, msg.author.id)
-            .await
-            .unwrap_or_default()
-            || crate::utils::has_permission(ctx, guild_id, msg.author.id, MANAGE_BOT_PERMS).await
-    } else {
-        false
-    };
-
-    let mut create_buttons = bug.status != BugStatus::Resolved
-        && bug.status != BugStatus::Closed
-        && (msg.author.id == OWNER_ID || (is_lotr_discord && is_admin));
+    let mut create_buttons =
+        bug.status.open && is_tracker_moderator(ctx, tracker.guild_id, msg.author.id).await;
 
     let mut response_message = msg
         .channel_id
         .send_message(ctx, |m| {
-            m.embed(create_bug_embed!(bug, linked_message))
+            m.embed(create_bug_embed!(ctx, bug, linked_message))
                 .components(create_bug_buttons!(message_link, create_buttons, false))
         })
         .await?;
@@ -686,22 +2140,21 @@ pub async fn bug(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult
                     .await?;
             } else {
                 let new_status = match interaction.data.custom_id.as_str() {
-                    "resolve_bug" => BugStatus::Resolved,
-                    "close_bug" => BugStatus::Closed,
+                    "resolve_bug" => tracker.status_or_unknown(STATUS_RESOLVED),
+                    "close_bug" => tracker.status_or_unknown(STATUS_CLOSED),
                     _ => continue,
                 };
 
-                change_bug_status(ctx, bug_id, new_status).await?;
+                change_bug_status(ctx, bug_id, &new_status, interaction.user.id).await?;
 
-                let old_status = bug.status;
-                bug.status = new_status;
+                let old_status = std::mem::replace(&mut bug.status, new_status.clone());
 
                 interaction
                     .create_interaction_response(ctx, |r| {
                         r.kind(InteractionResponseType::UpdateMessage)
                             .interaction_response_data(|m| {
                                 m.embeds([])
-                                    .create_embed(create_bug_embed!(bug, linked_message))
+                                    .create_embed(create_bug_embed!(ctx, bug, linked_message))
                                     .components(create_bug_buttons!(message_link))
                             })
                     })
@@ -710,12 +2163,14 @@ pub async fn bug(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult
                 notify_users(
                     ctx,
                     bug_id,
+                    NotificationKind::of_status(&new_status),
                     format!(
                         "A bug you are subscribed to has been changed from `{}` to `{}`",
                         old_status, new_status
                     ),
                 )
                 .await?;
+                update_bug_thread(ctx, bug_id, &new_status).await?;
 
                 create_buttons = false;
 
@@ -736,486 +2191,2880 @@ pub async fn bug(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult
     Ok(())
 }
 
-#[command]
-#[checks(is_lotr_discord, is_admin)]
-#[aliases("status")]
-pub async fn bug_status(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    if let Ok(bug_id) = args.single::<String>() {
-        if let Ok(bug_id) = bug_id
-            .to_uppercase()
-            .trim_start_matches("EoA-")
-            .parse::<u64>()
-        {
-            if let Ok(new_status) = args.single::<BugStatus>() {
-                let old_status = match change_bug_status(ctx, bug_id, new_status).await {
-                    Ok(old_status) => {
-                        termite_success!(
-                            ctx,
-                            msg,
-                            "Status changed for EoA-{} from `{}` to `{}`!",
-                            bug_id,
-                            old_status,
-                            new_status
-                        );
-                        old_status
-                    }
-                    Err(e) => {
-                        failure!(ctx, msg, "The bug EoA-{} does not exist!", bug_id);
-                        return Err(e);
-                    }
-                };
+async fn display_bug_history(
+    ctx: &Context,
+    tracker: &BugTracker,
+    bug_id: u64,
+    limit: u32,
+    page: u32,
+    reply_to: Either<'_>,
+) -> Result<Option<Message>, SerenityError> {
+    assert_ne!(page, 0);
 
-                if old_status != new_status {
-                    notify_users(
-                        ctx,
-                        bug_id,
-                        format!(
-                            "A bug you are subscribed to has been changed from `{}` to `{}`",
-                            old_status, new_status
-                        ),
-                    )
-                    .await?;
-                }
-            } else {
-                failure!(ctx, msg, "The second argument must be a bug status.")
-            }
-        } else {
-            failure!(ctx, msg, "`{}` is not a valid bug id!", bug_id)
-        }
-    } else {
-        failure!(ctx, msg, "The first argument must be a bug id.")
-    }
-    Ok(())
-}
-
-#[command]
-#[checks(is_lotr_discord, is_admin)]
-pub async fn resolve(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    if let Ok(bug_id) = args.single::<String>() {
-        if let Ok(bug_id) = bug_id
-            .to_uppercase()
-            .trim_start_matches("EoA-")
-            .parse::<u64>()
-        {
-            if let Err(e) = change_bug_status(ctx, bug_id, BugStatus::Resolved).await {
-                failure!(ctx, msg, "The bug EoA-{} does not exist!", bug_id);
-                return Err(e);
-            } else {
-                termite_success!(ctx, msg, "EoA-{} has been marked as resolved.", bug_id);
-                notify_users(
+    if let Some((entries, total)) = get_bug_history(ctx, bug_id, limit, page - 1).await {
+        if total != 0 && (page - 1) * limit >= total {
+            reply_to
+                .failure(
                     ctx,
-                    bug_id,
-                    "A bug you are subscribed to has been marked as resolved.",
+                    "Page number too high, consider using the navigation arrows.",
                 )
                 .await?;
-            }
-        } else {
-            failure!(ctx, msg, "`{}` is not a valid bug id!", bug_id)
+            return Err(SerenityError::Other("page_too_high"));
         }
+
+        send_bug_page(
+            ctx,
+            BugPage {
+                guild_id: tracker.guild_id,
+                title: format!(
+                    "History of {} (Total: {})",
+                    tracker.display_id(bug_id),
+                    total
+                ),
+                colour: serenity::utils::Colour::TEAL,
+                content: entries
+                    .iter()
+                    .map(|entry| entry.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                content_alt: "_This bug has not been edited yet!_",
+                total,
+            },
+            limit,
+            page,
+            reply_to,
+        )
+        .await
     } else {
-        failure!(ctx, msg, "The first argument must be a bug id.")
+        Err(SerenityError::Other(
+            "Could not get bug history from the database!",
+        ))
     }
-    Ok(())
 }
 
-#[command]
-#[checks(is_lotr_discord, is_admin)]
-#[aliases("close")]
-pub async fn bug_close(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    if let Ok(bug_id) = args.single::<String>() {
-        if let Ok(bug_id) = bug_id
-            .to_uppercase()
-            .trim_start_matches("EoA-")
-            .parse::<u64>()
-        {
-            if let Err(e) = change_bug_status(ctx, bug_id, BugStatus::Closed).await {
-                failure!(ctx, msg, "The bug EoA-{} does not exist!", bug_id);
-                return Err(e);
-            } else {
-                termite_success!(ctx, msg, "EoA-{} has been marked as closed.", bug_id);
-                notify_users(
+async fn bug_history(
+    ctx: &Context,
+    msg: &Message,
+    tracker: &BugTracker,
+    bug_id: u64,
+) -> CommandResult {
+    let limit = 10;
+    let mut page = 1;
+
+    let mut response_message =
+        match display_bug_history(ctx, tracker, bug_id, limit, page, Either::Message(msg)).await {
+            Ok(Some(msg)) => msg,
+            Ok(None) => unreachable!(),
+            Err(SerenityError::Other("page_too_high" | "too_many_bugs")) => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+
+    while let Some(interaction) = CollectComponentInteraction::new(ctx)
+        .timeout(Duration::from_secs(120))
+        .channel_id(msg.channel_id)
+        .message_id(response_message.id)
+        .await
+    {
+        if interaction.user.id != msg.author.id {
+            interaction
+                .say_ephemeral(
                     ctx,
-                    bug_id,
-                    "A bug you are subscribed to has been marked as closed.",
+                    "You are not the original user of the command! \
+Call `!bug <bug id> history` yourself to use the buttons.",
                 )
-                .await?;
-            }
-        } else {
-            failure!(ctx, msg, "`{}` is not a valid bug id!", bug_id)
+                .await;
+            continue;
         }
-    } else {
-        failure!(ctx, msg, "The first argument must be a bug id.")
-    }
-    Ok(())
-}
-
-#[command]
-#[checks(is_lotr_discord, is_admin)]
-#[sub_commands(bug_link_remove)]
-#[aliases("link")]
-pub async fn bug_link(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    if let Ok(bug_id) = args.single::<String>() {
-        if let Ok(bug_id) = bug_id
-            .to_uppercase()
-            .trim_start_matches("EoA-")
-            .parse::<u64>()
-        {
-            if let Some(message) = &msg.referenced_message {
-                let title = args.rest();
-                if title.is_empty() {
-                    failure!(ctx, msg, "Specify a title for your message link!");
-                    return Ok(());
-                }
-                if let Some(link_id) = add_link(ctx, bug_id, &message.link(), title).await {
-                    termite_success!(ctx, msg, "Added link #{} to EoA-{}", link_id, bug_id);
-                    notify_users(
-                        ctx,
-                        bug_id,
-                        format!("Link #{link_id} has been added to a bug you are subscribed to"),
-                    )
-                    .await?;
-                } else {
-                    failure!(ctx, msg, "EoA-{} does not exist!", bug_id);
-                }
-            } else if let Some(link) = args
-                .single::<String>()
-                .ok()
-                .filter(|s| s.starts_with("http"))
-            {
-                let title = args.rest();
-                if title.is_empty() {
-                    failure!(ctx, msg, "Specify a title for your message link!");
-                    return Ok(());
-                }
-                if let Some(link_id) = add_link(ctx, bug_id, &link, title).await {
-                    termite_success!(ctx, msg, "Added link #{} to EoA-{}", link_id, bug_id);
-                    notify_users(
-                        ctx,
-                        bug_id,
-                        format!("Link #{link_id} has been added to a bug you are subscribed to"),
-                    )
-                    .await?;
-                } else {
-                    failure!(ctx, msg, "EoA-{} does not exist!", bug_id);
+        match interaction.data.custom_id.as_str() {
+            "previous_page" => {
+                if page > 1 {
+                    page -= 1;
                 }
-            } else {
-                failure!(ctx, msg, "You need to either reference a message or specify a link to add to the bug report.");
             }
-        } else {
-            failure!(ctx, msg, "`{}` is not a valid bug id!", bug_id);
+            "next_page" => {
+                page += 1;
+            }
+            _ => (),
         }
-    } else {
-        failure!(ctx, msg, "The first argument must be a bug id.");
+
+        let _ = display_bug_history(
+            ctx,
+            tracker,
+            bug_id,
+            limit,
+            page,
+            Either::Interaction(interaction.as_ref()),
+        )
+        .await?;
     }
+
+    response_message
+        .edit(ctx, |m| m.components(create_buttons!(true, true)))
+        .await?;
+
     Ok(())
 }
 
 #[command]
-#[checks(is_admin, is_lotr_discord)]
-#[aliases("remove")]
-pub async fn bug_link_remove(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    if let Ok(bug_id) = args.single::<String>() {
-        if let Ok(bug_id) = bug_id
-            .to_uppercase()
-            .trim_start_matches("EoA-")
-            .parse::<u64>()
-        {
-            let link_id = args.single::<String>();
-            if let Ok(link_id) = link_id {
-                if let Ok(link_id) = link_id.trim_start_matches('#').parse::<u64>() {
-                    if remove_link(ctx, bug_id, link_id).await.is_ok() {
-                        termite_success!(
-                            ctx,
-                            msg,
-                            "Successfully removed link #{} from EoA-{}",
-                            link_id,
-                            bug_id
-                        );
-                    } else {
-                        failure!(
-                            ctx,
-                            msg,
-                            "Link #{} does not exist in EoA-{}",
-                            link_id,
-                            bug_id
-                        );
-                    }
-                } else {
-                    failure!(ctx, msg, "`{}` is not a valid link id!", link_id);
-                }
-            } else {
-                failure!(ctx, msg, "The second argument must be a valid link id.");
+#[checks(has_bugtracker, is_bugtracker_moderator)]
+#[aliases("status")]
+#[only_in(guilds)]
+pub async fn bug_status(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let tracker = get_tracker!(ctx, msg);
+    let bug_id = get_bug_id!(ctx, msg, tracker, args);
+
+    if let Some(new_status) = args.current().and_then(|arg| tracker.parse_status(arg)) {
+        let old_status = match change_bug_status(ctx, bug_id, &new_status, msg.author.id).await {
+            Ok(old_status) => {
+                termite_success!(
+                    ctx,
+                    msg,
+                    "Status changed for {} from `{}` to `{}`!",
+                    tracker.display_id(bug_id),
+                    old_status,
+                    new_status
+                );
+                old_status
+            }
+            Err(e) => {
+                failure!(
+                    ctx,
+                    msg,
+                    "The bug {} does not exist!",
+                    tracker.display_id(bug_id)
+                );
+                return Err(e);
+            }
+        };
+
+        if old_status != new_status {
+            notify_users(
+                ctx,
+                bug_id,
+                NotificationKind::of_status(&new_status),
+                format!(
+                    "A bug you are subscribed to has been changed from `{}` to `{}`",
+                    old_status, new_status
+                ),
+            )
+            .await?;
+            update_bug_thread(ctx, bug_id, &new_status).await?;
+        }
+        if new_status.name == STATUS_NEEDS_INFO {
+            if let Err(e) = request_more_info(ctx, &tracker, bug_id, msg.author.id, None).await {
+                failure!(
+                    ctx,
+                    msg,
+                    "Could not ask the reporter for more information: {}",
+                    e
+                );
             }
-        } else {
-            failure!(ctx, msg, "`{}` is not a valid bug id!", bug_id);
         }
     } else {
-        failure!(ctx, msg, "The first argument must be a bug id.");
+        failure!(
+            ctx,
+            msg,
+            "The second argument must be a bug status: {}",
+            tracker
+                .statuses
+                .iter()
+                .map(|status| format!("`{}`", status))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
     }
     Ok(())
 }
 
 #[command]
-#[checks(is_lotr_discord, is_admin)]
-#[aliases("toggle")]
-pub async fn bug_toggle_edition(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    if let Ok(bug_id) = args.single::<String>() {
-        if let Ok(bug_id) = bug_id
-            .to_uppercase()
-            .trim_start_matches("EoA-")
-            .parse::<u64>()
-        {
-            if let Ok(category) = args.single::<BugCategory>() {
-                if let Some(old_category) = change_category(ctx, bug_id, category).await {
-                     if category != old_category {
-                        termite_success!(
-                            ctx,
-                            msg,
-                            "EoA-{} has been changed from {} to {}",
-                            bug_id,
-                            old_category,
-                            category
-                        );
-                        notify_users(
-                            ctx,
-                            bug_id,
-                            format!(
-                                "A bug you are subscribed to has been changed from {} to {}",
-                                old_category, category
-                            ),
-                        )
-                        .await?;
-                    }
-                } else {
-                    failure!(ctx, msg, "The bug EoA-{} does not exist!", bug_id);
-                }
-            } else {
+#[checks(has_bugtracker, is_bugtracker_moderator)]
+#[only_in(guilds)]
+pub async fn resolve(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let tracker = get_tracker!(ctx, msg);
+    let bug_id = get_bug_id!(ctx, msg, tracker, args);
+
+    let fixed_in = if args
+        .current()
+        .map_or(false, |arg| arg.eq_ignore_ascii_case("in"))
+    {
+        args.advance();
+        match normalize_version(args.rest()) {
+            Some(version) => Some(version),
+            None => {
                 failure!(
                     ctx,
                     msg,
-                    "The first argument must be one of \
-`fa_renewed`, `fa_legacy`, `sa_renewed` or `sa_legacy`"
+                    "Versions can only contain letters, digits, `.`, `-`, `_` and `+`, \
+and must be at most {} characters long.",
+                    MAX_VERSION_LENGTH
                 );
+                return Ok(());
             }
-        } else {
-            failure!(ctx, msg, "`{}` is not a valid bug id!", bug_id);
         }
     } else {
-        failure!(ctx, msg, "The first argument must be a bug id.");
+        None
+    };
+
+    let resolved = tracker.status_or_unknown(STATUS_RESOLVED);
+    if let Err(e) = change_bug_status(ctx, bug_id, &resolved, msg.author.id).await {
+        failure!(
+            ctx,
+            msg,
+            "The bug {} does not exist!",
+            tracker.display_id(bug_id)
+        );
+        return Err(e);
+    } else if let Some(version) = fixed_in {
+        set_fixed_in(ctx, bug_id, Some(&version), msg.author.id).await?;
+        termite_success!(
+            ctx,
+            msg,
+            "{} has been marked as resolved in version `{}`.",
+            tracker.display_id(bug_id),
+            version
+        );
+        notify_users(
+            ctx,
+            bug_id,
+            NotificationKind::of_status(&resolved),
+            format!(
+                "A bug you are subscribed to has been marked as resolved in version `{}`.",
+                version
+            ),
+        )
+        .await?;
+        update_bug_thread(ctx, bug_id, &resolved).await?;
+    } else {
+        termite_success!(
+            ctx,
+            msg,
+            "{} has been marked as resolved.",
+            tracker.display_id(bug_id)
+        );
+        notify_users(
+            ctx,
+            bug_id,
+            NotificationKind::of_status(&resolved),
+            "A bug you are subscribed to has been marked as resolved.",
+        )
+        .await?;
+        update_bug_thread(ctx, bug_id, &resolved).await?;
     }
     Ok(())
 }
 
 #[command]
-#[checks(is_lotr_discord, is_admin)]
-#[aliases(rename)]
-pub async fn bug_rename(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    if let Ok(bug_id) = args.single::<String>() {
-        if let Ok(bug_id) = bug_id
-            .to_uppercase()
-            .trim_start_matches("EoA-")
-            .parse::<u64>()
-        {
-            let new_title = args.rest();
-            if new_title.is_empty() {
-                failure!(ctx, msg, "You must specify a new title for EoA-{}", bug_id);
-            } else if change_title(ctx, bug_id, new_title).await.is_ok() {
-                termite_success!(ctx, msg, "Successfully changed the title of EoA-{}", bug_id);
-                notify_users(
-                    ctx,
-                    bug_id,
-                    "The title of a bug you are subscribed to has been changed",
-                )
-                .await?
-            } else {
-                failure!(ctx, msg, "EoA-{} does not exist!", bug_id);
-            }
-        } else {
-            failure!(ctx, msg, "`{}` is not a valid bug id!", bug_id);
-        }
+#[checks(has_bugtracker, is_bugtracker_moderator)]
+#[aliases("close")]
+#[only_in(guilds)]
+pub async fn bug_close(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let tracker = get_tracker!(ctx, msg);
+    let bug_id = get_bug_id!(ctx, msg, tracker, args);
+
+    let closed = tracker.status_or_unknown(STATUS_CLOSED);
+    if let Err(e) = change_bug_status(ctx, bug_id, &closed, msg.author.id).await {
+        failure!(
+            ctx,
+            msg,
+            "The bug {} does not exist!",
+            tracker.display_id(bug_id)
+        );
+        return Err(e);
     } else {
-        failure!(ctx, msg, "The first argument must be a bug id.");
+        termite_success!(
+            ctx,
+            msg,
+            "{} has been marked as closed.",
+            tracker.display_id(bug_id)
+        );
+        notify_users(
+            ctx,
+            bug_id,
+            NotificationKind::of_status(&closed),
+            "A bug you are subscribed to has been marked as closed.",
+        )
+        .await?;
+        update_bug_thread(ctx, bug_id, &closed).await?;
     }
     Ok(())
 }
 
-#[command]
-#[aliases(statistics)]
-pub async fn stats(ctx: &Context, msg: &Message) -> CommandResult {
-    if let Some(counts) = get_bug_statistics(ctx).await {
-        msg.channel_id
+/// Pings the reporter of a bug in its discussion thread, or else in a reply to the reported message
+async fn ping_reporter(
+    ctx: &Context,
+    bug: &BugReport,
+    reporter: UserId,
+    content: impl std::fmt::Display,
+) -> CommandResult {
+    let content = format!("{} {}", reporter.mention(), content);
+    if let Some(thread_id) = bug.thread_id {
+        thread_id
             .send_message(ctx, |m| {
-                m.embed(|e| {
-                    e.author(|a| {
-                        a.name("Eras of Arda Bugtracker");
-                        a.icon_url(crate::constants::TERMITE_IMAGE);
-                        a
-                    });
-                    e.colour(serenity::utils::Colour::TEAL);
-                    e.field(
-                        "Bugtracker statistics",
-                        format!(
-                            "{} resolved
-{} closed
-{} forge or vanilla
-
-_Open bugs: {}_
-{} with low priority
-{} with medium priority
-{} with high priority
-{} critical bugs
-
-**Total: {} tracked bugs**
-_{} first age bugs, {} second age bugs_
-",
-                            counts.resolved,
-                            counts.closed,
-                            counts.forgevanilla,
-                            counts.total - counts.resolved - counts.closed - counts.forgevanilla,
-                            counts.low,
-                            counts.medium,
-                            counts.high,
-                            counts.critical,
-                            counts.total,
-                            counts.total - counts.second_age,
-                            counts.second_age
-                        ),
-                        false,
-                    );
-                    e
-                })
+                m.content(content).allowed_mentions(|f| f.users([reporter]))
             })
             .await?;
     } else {
-        failure!(ctx, msg, "Could not fetch bugtracker statistics");
+        bug.channel_id
+            .send_message(ctx, |m| {
+                m.content(content)
+                    .reference_message((bug.channel_id, bug.message_id))
+                    .allowed_mentions(|f| f.users([reporter]).replied_user(false))
+            })
+            .await?;
     }
     Ok(())
 }
 
-#[command]
-#[checks(is_admin, is_lotr_discord)]
-#[aliases("help")]
-pub async fn bugtracker_help(ctx: &Context, msg: &Message) -> CommandResult {
-    crate::commands::help::display_bugtracker_help(ctx, msg).await
+/// Explains when a bug waiting for more information will be closed
+fn info_close_notice(tracker: &BugTracker) -> String {
+    if tracker.info_close_days == 0 {
+        String::new()
+    } else {
+        format!(
+            "\nWithout any activity, the bug will be closed after {} days.",
+            tracker.info_close_days
+        )
+    }
 }
 
-#[command]
-pub async fn notifications(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    let closed = args
-        .single::<String>()
-        .map(|s| s.eq_ignore_ascii_case("closed") || s.eq_ignore_ascii_case("all"))
-        .unwrap_or_default();
+/// Asks the reporter of a bug, the author of the reported message, for more information.
+///
+/// They are reminded if the bug has no activity for a while, and the bug is closed if it
+/// stays inactive, see [`process_info_requests`].
+async fn request_more_info(
+    ctx: &Context,
+    tracker: &BugTracker,
+    bug_id: u64,
+    moderator: UserId,
+    question: Option<&str>,
+) -> CommandResult {
+    let bug = get_bug_from_id(ctx, bug_id).await?;
+    let reporter = bug
+        .channel_id
+        .message(ctx, bug.message_id)
+        .await
+        .map_err(|_| "the reported message was deleted")?
+        .author
+        .id;
 
-    let list = get_notifications_for_user(ctx, msg.author.id, closed).await?;
+    add_info_request(ctx, bug_id, reporter).await?;
+    ping_reporter(
+        ctx,
+        &bug,
+        reporter,
+        format!(
+            "{} needs more information about your bug report {}: **{}**{}
+Please reply {}.{}",
+            moderator.mention(),
+            tracker.display_id(bug_id),
+            bug.title,
+            question.map_or_else(String::new, |q| format!("\n> {}", q)),
+            if bug.thread_id.is_some() {
+                "in this thread"
+            } else {
+                "to your message"
+            },
+            info_close_notice(tracker)
+        ),
+    )
+    .await
+}
 
-    if list.is_empty() {
-        msg.reply(
+#[command]
+#[checks(has_bugtracker, is_bugtracker_moderator)]
+#[aliases("needinfo", "info")]
+#[only_in(guilds)]
+pub async fn bug_needinfo(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let tracker = get_tracker!(ctx, msg);
+    let bug_id = get_bug_id!(ctx, msg, tracker, args);
+    let question = args.rest().trim();
+
+    let needs_info = tracker.status_or_unknown(STATUS_NEEDS_INFO);
+    let old_status = match change_bug_status(ctx, bug_id, &needs_info, msg.author.id).await {
+        Ok(old_status) => old_status,
+        Err(e) => {
+            failure!(
+                ctx,
+                msg,
+                "The bug {} does not exist!",
+                tracker.display_id(bug_id)
+            );
+            return Err(e);
+        }
+    };
+    if old_status != needs_info {
+        update_bug_thread(ctx, bug_id, &needs_info).await?;
+    }
+
+    let question = if question.is_empty() {
+        None
+    } else {
+        Some(question)
+    };
+    if let Err(e) = request_more_info(ctx, &tracker, bug_id, msg.author.id, question).await {
+        failure!(
             ctx,
-            if closed {
-                "You have no notifications for now.
-Subscribe to a bug report to receive notifications."
-            } else {
-                "You have no active notifications for now.
-Type  `!bug notifications all` to see all your notifications, \
-including those from closed or resolved bugs."
-            },
+            msg,
+            "{} now needs more information, but its reporter could not be asked for it: {}",
+            tracker.display_id(bug_id),
+            e
+        );
+    } else {
+        termite_success!(
+            ctx,
+            msg,
+            "{} now needs more information, its reporter has been asked for it.",
+            tracker.display_id(bug_id)
+        );
+    }
+
+    if old_status != needs_info {
+        notify_users(
+            ctx,
+            bug_id,
+            NotificationKind::of_status(&needs_info),
+            format!(
+                "A bug you are subscribed to has been changed from `{}` to `{}`",
+                old_status, needs_info
+            ),
         )
         .await?;
-    } else {
-        msg.channel_id
-            .send_message(ctx, |m| {
-                m.embed(|e| {
-                    e.author(|a| {
-                        a.name("Eras of Arda Bugtracker")
-                            .icon_url(crate::constants::TERMITE_IMAGE)
-                    })
-                    .colour(serenity::utils::Colour::TEAL)
-                    .title("Bug notifications")
-                    .description(format!(
-                        "_List of bugs you are subscribed to_\n\n{}",
-                        list.iter()
-                            .map(|id| format!("EoA-{id}"))
-                            .collect::<Vec<_>>()
-                            .join(", "),
-                    ))
-                    .footer(|f| {
-                        f.text(if closed {
-                            "Including closed and resolved bugs"
-                        } else {
-                            "To see closed and resolved bugs, use  !bug notifications all"
-                        })
-                    });
+    }
+    Ok(())
+}
 
-                    e
-                })
+/// Restarts the reminder delay of a bug waiting for more information when its reporter
+/// replies in its thread, or to the reported message
+pub async fn handle_info_reply(ctx: &Context, message: &Message) -> CommandResult {
+    if message.author.bot {
+        return Ok(());
+    }
+    // only replies and thread messages can answer a request, this avoids a query
+    // for every message
+    if message.referenced_message.is_none() && !is_thread_message(ctx, message) {
+        return Ok(());
+    }
+    record_info_reply(ctx, message).await?;
+    Ok(())
+}
+
+/// Whether a message was sent in a thread, or may have been if its guild is not cached
+fn is_thread_message(ctx: &Context, message: &Message) -> bool {
+    message.guild_id.map_or(false, |guild_id| {
+        ctx.cache
+            .guild_field(guild_id, |g| {
+                g.threads
+                    .iter()
+                    .any(|thread| thread.id == message.channel_id)
             })
-            .await?;
+            .unwrap_or(true)
+    })
+}
+
+async fn process_info_request(ctx: &Context, request: InfoRequest) -> CommandResult {
+    let tracker = get_bug_tracker(ctx, request.guild_id)
+        .await
+        .ok_or("The bug tracker was removed")?;
+    let bug_id = request.bug_id;
+
+    if request.stale {
+        let closed = tracker.status_or_unknown(STATUS_CLOSED);
+        change_bug_status(ctx, bug_id, &closed, ctx.cache.current_user_id()).await?;
+        update_bug_thread(ctx, bug_id, &closed).await?;
+        notify_users(
+            ctx,
+            bug_id,
+            NotificationKind::of_status(&closed),
+            format!(
+                "A bug you are subscribed to has been closed, as the information it needed \
+was not given for {} days.",
+                tracker.info_close_days
+            ),
+        )
+        .await?;
+    } else {
+        // marked first, so that a failing reminder is not sent again every hour
+        set_info_reminded(ctx, bug_id).await?;
+        let bug = get_bug_from_id(ctx, bug_id).await?;
+        ping_reporter(
+            ctx,
+            &bug,
+            request.reporter,
+            format!(
+                "Reminder: your bug report {} still needs more information.{}",
+                tracker.display_id(bug_id),
+                info_close_notice(&tracker)
+            ),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Reminds the reporters of the bugs that still need more information, and closes
+/// the bugs left without activity.
+///
+/// Called periodically by the [scheduler][crate::scheduler].
+pub async fn process_info_requests(ctx: &Context) -> CommandResult {
+    for request in get_due_info_requests(ctx).await? {
+        if let Err(e) = process_info_request(ctx, request).await {
+            println!(
+                "=== ERROR ===
+Could not process the info request of bug {} (guild {})
+Error: {}
+=== END ===",
+                request.bug_id, request.guild_id, e
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// New status of a mirrored bug whose issue changed state, unless its status already matches
+fn mirrored_status(
+    tracker: &BugTracker,
+    bug: &MirroredIssue,
+    state: IssueState,
+) -> Option<BugStatus> {
+    let status = tracker.status_or_unknown(&bug.status);
+    match state {
+        IssueState::Open if !status.open => Some(tracker.default_status()),
+        IssueState::Completed if status.name != STATUS_RESOLVED => {
+            Some(tracker.status_or_unknown(STATUS_RESOLVED))
+        }
+        IssueState::NotPlanned if status.open => Some(tracker.status_or_unknown(STATUS_CLOSED)),
+        _ => None,
+    }
+}
+
+/// Pulls the state changes of the issues mirroring the bugs of a guild
+async fn sync_issue_states(ctx: &Context, issue_sync: &IssueSync) -> CommandResult {
+    let tracker = get_bug_tracker(ctx, issue_sync.guild_id)
+        .await
+        .ok_or("The bug tracker was removed")?;
+    let client = get_reqwest_client!(ctx);
+
+    // taken before the request, so that no update is missed by the next synchronisation
+    let sync_time = Utc::now();
+    let issues = issue_sync
+        .issue_tracker()?
+        .updated_issues(&client, issue_sync.last_sync)
+        .await?;
+
+    if !issues.is_empty() {
+        let mirrored_bugs = get_mirrored_issues(ctx, tracker.guild_id).await?;
+        for issue in issues {
+            // only state changes are synchronised, not the issues that were just commented
+            let bug = match mirrored_bugs
+                .iter()
+                .find(|bug| bug.issue_number == issue.number)
+            {
+                Some(bug) if bug.state != issue.state => bug,
+                _ => continue,
+            };
+
+            if let Some(new_status) = mirrored_status(&tracker, bug, issue.state) {
+                let old_status =
+                    change_bug_status(ctx, bug.bug_id, &new_status, ctx.cache.current_user_id())
+                        .await?;
+                update_bug_thread(ctx, bug.bug_id, &new_status).await?;
+                notify_users(
+                    ctx,
+                    bug.bug_id,
+                    NotificationKind::of_status(&new_status),
+                    format!(
+                        "A bug you are subscribed to has been changed from `{}` to `{}`, \
+following its issue: <{}>",
+                        old_status, new_status, issue.url
+                    ),
+                )
+                .await?;
+            }
+            // stored last, so that a failed change is tried again by the next synchronisation
+            set_mirrored_issue(ctx, bug.bug_id, issue.number, issue.state).await?;
+        }
+    }
+
+    set_issue_sync_time(ctx, tracker.guild_id, sync_time).await
+}
+
+/// Resolves, closes or reopens the mirrored bugs whose issues were.
+///
+/// Called periodically by the [scheduler][crate::scheduler].
+pub async fn sync_mirrored_issues(ctx: &Context) -> CommandResult {
+    for issue_sync in get_issue_syncs(ctx).await? {
+        if let Err(e) = sync_issue_states(ctx, &issue_sync).await {
+            println!(
+                "=== ERROR ===
+Could not synchronise the issues of guild {} from {}
+Error: {}
+=== END ===",
+                issue_sync.guild_id, issue_sync.repository, e
+            );
+        }
     }
+
     Ok(())
 }
 
+/// Whether a user can edit the bugs of a tracker: the owner, the bot admins, the users
+/// with the bot management permissions and the tracker moderators
+async fn is_tracker_moderator(ctx: &Context, guild_id: GuildId, user_id: UserId) -> bool {
+    user_id == OWNER_ID
+        || is_admin_function(ctx, guild_id, user_id)
+            .await
+            .unwrap_or_default()
+        || crate::utils::has_permission(ctx, guild_id, user_id, MANAGE_BOT_PERMS).await
+        || is_bug_moderator(ctx, guild_id, user_id)
+            .await
+            .unwrap_or_default()
+}
+
+fn bug_message_link(guild_id: GuildId, channel_id: ChannelId, message_id: MessageId) -> String {
+    format!(
+        "https://discord.com/channels/{}/{}/{}",
+        guild_id.0, channel_id.0, message_id.0
+    )
+}
+
 #[command]
-pub async fn unsubscribe(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    let bug_id = match args.single::<u64>() {
-        Ok(bug_id) => bug_id,
-        _ => {
-            failure!(ctx, msg, "The first argument must be a bug id!");
+#[checks(has_bugtracker, is_bugtracker_moderator)]
+#[aliases("duplicate")]
+#[only_in(guilds)]
+pub async fn bug_duplicate(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let tracker = get_tracker!(ctx, msg);
+    if args.len() < 2 {
+        failure!(
+            ctx,
+            msg,
+            "You must specify the duplicate bug id, followed by the original bug id."
+        );
+        return Ok(());
+    }
+    let bug_id = get_bug_id!(ctx, msg, tracker, args);
+    let original_id = get_bug_id!(ctx, msg, tracker, args);
+
+    if bug_id == original_id {
+        failure!(ctx, msg, "A bug cannot be a duplicate of itself!");
+        return Ok(());
+    }
+
+    let bug = match get_bug_from_id(ctx, bug_id).await {
+        Ok(bug) => bug,
+        Err(_) => {
+            failure!(
+                ctx,
+                msg,
+                "The bug {} does not exist!",
+                tracker.display_id(bug_id)
+            );
+            return Ok(());
+        }
+    };
+    let original = match get_bug_from_id(ctx, original_id).await {
+        Ok(original) => original,
+        Err(_) => {
+            failure!(
+                ctx,
+                msg,
+                "The bug {} does not exist!",
+                tracker.display_id(original_id)
+            );
             return Ok(());
         }
     };
 
-    if is_notified_user(ctx, bug_id, msg.author.id).await != Some(true) {
-        failure!(ctx, msg, "You are not subscribed to this bug!");
+    if let Some(duplicate_of) = bug.duplicate_of {
+        failure!(
+            ctx,
+            msg,
+            "{} is already marked as a duplicate of {}!",
+            tracker.display_id(bug_id),
+            tracker.display_id(duplicate_of)
+        );
         return Ok(());
     }
-
-    if let Err(e) =
-        crate::database::bug_reports::remove_notified_user(ctx, bug_id, msg.author.id).await
-    {
-        failure!(ctx, msg, "Could not unsubscribe from EoA-{}", bug_id);
-        return Err(e);
+    if let Some(duplicate_of) = original.duplicate_of {
+        failure!(
+            ctx,
+            msg,
+            "{} is itself a duplicate of {}. Use the original bug instead.",
+            tracker.display_id(original_id),
+            tracker.display_id(duplicate_of)
+        );
+        return Ok(());
     }
 
-    crate::success!(
+    // marked first, so that the command cannot be repeated if a later step fails. The
+    // subscribers are moved to the original bug, they are notified from this list
+    let subscribers = get_notified_users(ctx, bug_id).await?;
+    mark_duplicate(ctx, bug_id, original_id, msg.author.id).await?;
+
+    let closed = tracker.status_or_unknown(STATUS_CLOSED);
+    let old_status = change_bug_status(ctx, bug_id, &closed, msg.author.id).await?;
+    update_bug_thread(ctx, bug_id, &closed).await?;
+
+    add_link(
+        ctx,
+        bug_id,
+        &bug_message_link(tracker.guild_id, original.channel_id, original.message_id),
+        &format!("Duplicate of {}", tracker.display_id(original_id)),
+        msg.author.id,
+    )
+    .await
+    .ok_or("Could not add the cross-reference link to the duplicate")?;
+    add_link(
+        ctx,
+        original_id,
+        &bug_message_link(tracker.guild_id, bug.channel_id, bug.message_id),
+        &format!("Duplicate: {}", tracker.display_id(bug_id)),
+        msg.author.id,
+    )
+    .await
+    .ok_or("Could not add the cross-reference link to the original")?;
+
+    notify_subscribers(
+        ctx,
+        bug_id,
+        subscribers,
+        NotificationKind::of_status(&closed),
+        format!(
+            "A bug you are subscribed to has been changed from `{}` to `{}` \
+as a duplicate of {}.
+You are now subscribed to {} instead.",
+            old_status,
+            closed,
+            tracker.display_id(original_id),
+            tracker.display_id(original_id)
+        ),
+    )
+    .await?;
+
+    termite_success!(
         ctx,
         msg,
-        "Successfully unsubscribed from EoA-{}.
-You will no longer be notified if this bug is edited, closed or resolved.",
-        bug_id
+        "{} has been closed as a duplicate of {}.",
+        tracker.display_id(bug_id),
+        tracker.display_id(original_id)
     );
 
-    Ok(())
+    notify_users(
+        ctx,
+        original_id,
+        NotificationKind::Update,
+        format!(
+            "{} has been marked as a duplicate of a bug you are subscribed to",
+            tracker.display_id(bug_id)
+        ),
+    )
+    .await
 }
 
 #[command]
-pub async fn subscribe(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    let bug_id = match args.single::<u64>() {
-        Ok(bug_id) => bug_id,
-        _ => {
-            failure!(ctx, msg, "The first argument must be a bug id!");
+#[checks(has_bugtracker, is_bugtracker_moderator)]
+#[aliases("assign")]
+#[only_in(guilds)]
+pub async fn bug_assign(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let tracker = get_tracker!(ctx, msg);
+    let bug_id = get_bug_id!(ctx, msg, tracker, args);
+    let assignee = match args.single::<UserId>() {
+        Ok(assignee) => assignee,
+        Err(_) => {
+            failure!(ctx, msg, "The second argument must be a user mention.");
             return Ok(());
         }
     };
 
-    if is_notified_user(ctx, bug_id, msg.author.id).await != Some(false) {
-        failure!(ctx, msg, "You are already subscribed to this bug!");
+    let old_assignee = match set_assignee(ctx, bug_id, Some(assignee), msg.author.id).await {
+        Ok(old_assignee) => old_assignee,
+        Err(e) => {
+            failure!(
+                ctx,
+                msg,
+                "The bug {} does not exist!",
+                tracker.display_id(bug_id)
+            );
+            return Err(e);
+        }
+    };
+
+    if old_assignee == Some(assignee) {
+        failure!(
+            ctx,
+            msg,
+            "{} is already assigned to this user!",
+            tracker.display_id(bug_id)
+        );
         return Ok(());
     }
 
-    if let Err(e) =
-        crate::database::bug_reports::add_notified_user(ctx, bug_id, msg.author.id).await
-    {
-        failure!(ctx, msg, "Could not subscribe to EoA-{}", bug_id);
-        return Err(e);
+    if is_notified_user(ctx, bug_id, assignee).await == Some(false) {
+        add_notified_user(ctx, bug_id, assignee).await?;
     }
 
-    crate::success!(
+    termite_success!(
         ctx,
         msg,
-        "Successfully subscribed to EoA-{}.
-You will be notified if this bug is edited, closed or resolved.",
-        bug_id
+        "{} has been assigned to <@{}>.",
+        tracker.display_id(bug_id),
+        assignee.0
     );
 
+    notify_users(
+        ctx,
+        bug_id,
+        NotificationKind::Update,
+        format!(
+            "A bug you are subscribed to has been assigned to <@{}>",
+            assignee.0
+        ),
+    )
+    .await
+}
+
+#[command]
+#[checks(has_bugtracker, is_bugtracker_moderator)]
+#[aliases("unassign")]
+#[only_in(guilds)]
+pub async fn bug_unassign(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let tracker = get_tracker!(ctx, msg);
+    let bug_id = get_bug_id!(ctx, msg, tracker, args);
+
+    match set_assignee(ctx, bug_id, None, msg.author.id).await {
+        Ok(Some(_)) => {
+            termite_success!(
+                ctx,
+                msg,
+                "{} is no longer assigned.",
+                tracker.display_id(bug_id)
+            );
+            notify_users(
+                ctx,
+                bug_id,
+                NotificationKind::Update,
+                "A bug you are subscribed to is no longer assigned to anyone",
+            )
+            .await
+        }
+        Ok(None) => {
+            failure!(
+                ctx,
+                msg,
+                "{} is not assigned to anyone!",
+                tracker.display_id(bug_id)
+            );
+            Ok(())
+        }
+        Err(e) => {
+            failure!(
+                ctx,
+                msg,
+                "The bug {} does not exist!",
+                tracker.display_id(bug_id)
+            );
+            Err(e)
+        }
+    }
+}
+
+#[command]
+#[checks(has_bugtracker, is_bugtracker_moderator)]
+#[aliases("version")]
+#[only_in(guilds)]
+pub async fn bug_version(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let tracker = get_tracker!(ctx, msg);
+    let bug_id = get_bug_id!(ctx, msg, tracker, args);
+    let version = match args.rest() {
+        "" => {
+            failure!(
+                ctx,
+                msg,
+                "You must provide a version, or `none` to remove the affected version."
+            );
+            return Ok(());
+        }
+        version if version.trim().eq_ignore_ascii_case("none") => None,
+        version => match normalize_version(version) {
+            Some(version) => Some(version),
+            None => {
+                failure!(
+                    ctx,
+                    msg,
+                    "Versions can only contain letters, digits, `.`, `-`, `_` and `+`, \
+and must be at most {} characters long.",
+                    MAX_VERSION_LENGTH
+                );
+                return Ok(());
+            }
+        },
+    };
+
+    if let Err(e) = set_affected_version(ctx, bug_id, version.as_deref(), msg.author.id).await {
+        failure!(
+            ctx,
+            msg,
+            "The bug {} does not exist!",
+            tracker.display_id(bug_id)
+        );
+        return Err(e);
+    }
+
+    match version {
+        Some(version) => termite_success!(
+            ctx,
+            msg,
+            "{} now affects version `{}`.",
+            tracker.display_id(bug_id),
+            version
+        ),
+        None => termite_success!(
+            ctx,
+            msg,
+            "Removed the affected version of {}.",
+            tracker.display_id(bug_id)
+        ),
+    }
+
     Ok(())
 }
+
+#[command]
+#[checks(has_bugtracker)]
+#[aliases("changelog")]
+#[only_in(guilds)]
+pub async fn bug_changelog(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let tracker = get_tracker!(ctx, msg);
+    let version = match normalize_version(args.rest()) {
+        Some(version) => version,
+        None => {
+            failure!(ctx, msg, "You must provide a valid version.");
+            return Ok(());
+        }
+    };
+
+    let bugs = match get_fixed_bugs(ctx, tracker.guild_id, &version).await {
+        Ok(bugs) => bugs,
+        Err(e) => {
+            failure!(ctx, msg, "Could not get bugs from the database!");
+            return Err(e);
+        }
+    };
+    if bugs.is_empty() {
+        failure!(ctx, msg, "No bugs were resolved in version `{}`.", version);
+        return Ok(());
+    }
+
+    // bugs are sorted by edition, which gets a heading if there are several
+    let several_editions = bugs.iter().any(|b| b.category != bugs[0].category);
+    let mut changelog = format!("## Bug fixes in {}\n", version);
+    for (i, bug) in bugs.iter().enumerate() {
+        if several_editions && (i == 0 || bugs[i - 1].category != bug.category) {
+            changelog.push_str(&format!("\n### {}\n", bug.category));
+        }
+        changelog.push_str(&format!(
+            "- {} ({})\n",
+            bug.title,
+            tracker.display_id(bug.bug_id)
+        ));
+    }
+
+    let content = format!("```md\n{}```", changelog);
+    if content.len() <= 2000 {
+        msg.reply(ctx, content).await?;
+    } else {
+        let filename = format!("changelog-{}.md", version);
+        msg.channel_id
+            .send_message(ctx, |m| {
+                m.content(format!(
+                    "Changelog for version `{}` ({} bugs).",
+                    version,
+                    bugs.len()
+                ))
+                .reference_message(msg)
+                .add_file((changelog.as_bytes(), filename.as_str()))
+            })
+            .await?;
+    }
+
+    Ok(())
+}
+
+#[command]
+#[checks(has_bugtracker)]
+#[aliases("label", "labels")]
+#[sub_commands(bug_label_add, bug_label_remove)]
+#[only_in(guilds)]
+pub async fn bug_label(ctx: &Context, msg: &Message) -> CommandResult {
+    let tracker = get_tracker!(ctx, msg);
+    let labels = get_label_statistics(ctx, tracker.guild_id)
+        .await
+        .ok_or("Could not get labels from the database")?;
+
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.author(|a| {
+                    a.name(tracker_name(ctx, tracker.guild_id));
+                    a.icon_url(crate::constants::TERMITE_IMAGE);
+                    a
+                });
+                e.colour(serenity::utils::Colour::TEAL);
+                e.title("Bug labels");
+                e.description(if labels.is_empty() {
+                    "_No labels yet!_".into()
+                } else {
+                    labels
+                        .iter()
+                        .map(|(label, open, total)| {
+                            format!("`{}`: {} open, {} total", label, open, total)
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                });
+                e
+            })
+        })
+        .await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(has_bugtracker, is_bugtracker_moderator)]
+#[aliases("add")]
+#[only_in(guilds)]
+pub async fn bug_label_add(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let tracker = get_tracker!(ctx, msg);
+    let bug_id = get_bug_id!(ctx, msg, tracker, args);
+    let label = match normalize_label(args.rest()) {
+        Some(label) => label,
+        None => {
+            failure!(
+                ctx,
+                msg,
+                "Labels can only contain letters, digits, `-` and `_`, \
+and must be at most {} characters long.",
+                MAX_LABEL_LENGTH
+            );
+            return Ok(());
+        }
+    };
+
+    match add_label(ctx, bug_id, &label, msg.author.id).await {
+        Ok(true) => {
+            termite_success!(
+                ctx,
+                msg,
+                "Added the label `{}` to {}",
+                label,
+                tracker.display_id(bug_id)
+            );
+            Ok(())
+        }
+        Ok(false) => {
+            failure!(
+                ctx,
+                msg,
+                "{} already has the label `{}`",
+                tracker.display_id(bug_id),
+                label
+            );
+            Ok(())
+        }
+        Err(e) => {
+            failure!(
+                ctx,
+                msg,
+                "Could not add the label to {}",
+                tracker.display_id(bug_id)
+            );
+            Err(e)
+        }
+    }
+}
+
+#[command]
+#[checks(has_bugtracker, is_bugtracker_moderator)]
+#[aliases("remove")]
+#[only_in(guilds)]
+pub async fn bug_label_remove(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let tracker = get_tracker!(ctx, msg);
+    let bug_id = get_bug_id!(ctx, msg, tracker, args);
+    let label = normalize_label(args.rest()).unwrap_or_default();
+
+    match remove_label(ctx, bug_id, &label, msg.author.id).await {
+        Ok(true) => {
+            termite_success!(
+                ctx,
+                msg,
+                "Removed the label `{}` from {}",
+                label,
+                tracker.display_id(bug_id)
+            );
+            Ok(())
+        }
+        Ok(false) => {
+            failure!(
+                ctx,
+                msg,
+                "{} does not have this label!",
+                tracker.display_id(bug_id)
+            );
+            Ok(())
+        }
+        Err(e) => {
+            failure!(
+                ctx,
+                msg,
+                "Could not remove the label from {}",
+                tracker.display_id(bug_id)
+            );
+            Err(e)
+        }
+    }
+}
+
+#[command]
+#[checks(has_bugtracker, is_bugtracker_moderator)]
+#[sub_commands(bug_link_remove)]
+#[aliases("link")]
+#[only_in(guilds)]
+pub async fn bug_link(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let tracker = get_tracker!(ctx, msg);
+    let bug_id = get_bug_id!(ctx, msg, tracker, args);
+
+    let link = if let Some(message) = &msg.referenced_message {
+        message.link()
+    } else if let Some(link) = args
+        .single::<String>()
+        .ok()
+        .filter(|s| s.starts_with("http"))
+    {
+        link
+    } else {
+        failure!(
+            ctx,
+            msg,
+            "You need to either reference a message or specify a link to add to the bug report."
+        );
+        return Ok(());
+    };
+
+    let title = args.rest();
+    if title.is_empty() {
+        failure!(ctx, msg, "Specify a title for your message link!");
+        return Ok(());
+    }
+    if let Some(link_id) = add_link(ctx, bug_id, &link, title, msg.author.id).await {
+        termite_success!(
+            ctx,
+            msg,
+            "Added link #{} to {}",
+            link_id,
+            tracker.display_id(bug_id)
+        );
+        notify_users(
+            ctx,
+            bug_id,
+            NotificationKind::Update,
+            format!("Link #{link_id} has been added to a bug you are subscribed to"),
+        )
+        .await?;
+    } else {
+        failure!(ctx, msg, "{} does not exist!", tracker.display_id(bug_id));
+    }
+    Ok(())
+}
+
+#[command]
+#[checks(has_bugtracker, is_bugtracker_moderator)]
+#[aliases("remove")]
+#[only_in(guilds)]
+pub async fn bug_link_remove(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let tracker = get_tracker!(ctx, msg);
+    let bug_id = get_bug_id!(ctx, msg, tracker, args);
+
+    let link_id = args.single::<String>();
+    if let Ok(link_id) = link_id {
+        if let Ok(link_id) = link_id.trim_start_matches('#').parse::<u64>() {
+            if remove_link(ctx, bug_id, link_id, msg.author.id)
+                .await
+                .is_ok()
+            {
+                termite_success!(
+                    ctx,
+                    msg,
+                    "Successfully removed link #{} from {}",
+                    link_id,
+                    tracker.display_id(bug_id)
+                );
+            } else {
+                failure!(
+                    ctx,
+                    msg,
+                    "Link #{} does not exist in {}",
+                    link_id,
+                    tracker.display_id(bug_id)
+                );
+            }
+        } else {
+            failure!(ctx, msg, "`{}` is not a valid link id!", link_id);
+        }
+    } else {
+        failure!(ctx, msg, "The second argument must be a valid link id.");
+    }
+    Ok(())
+}
+
+#[command]
+#[checks(has_bugtracker, is_bugtracker_moderator)]
+#[aliases("toggle")]
+#[only_in(guilds)]
+pub async fn bug_toggle_edition(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let tracker = get_tracker!(ctx, msg);
+    let bug_id = get_bug_id!(ctx, msg, tracker, args);
+
+    if let Some(category) = args
+        .single::<BugCategory>()
+        .ok()
+        .filter(|category| tracker.has_category(*category))
+    {
+        if let Some(old_category) = change_category(ctx, bug_id, category, msg.author.id).await {
+            if category != old_category {
+                termite_success!(
+                    ctx,
+                    msg,
+                    "{} has been changed from {} to {}",
+                    tracker.display_id(bug_id),
+                    old_category,
+                    category
+                );
+                notify_users(
+                    ctx,
+                    bug_id,
+                    NotificationKind::Update,
+                    format!(
+                        "A bug you are subscribed to has been changed from {} to {}",
+                        old_category, category
+                    ),
+                )
+                .await?;
+            }
+        } else {
+            failure!(
+                ctx,
+                msg,
+                "The bug {} does not exist!",
+                tracker.display_id(bug_id)
+            );
+        }
+    } else {
+        failure!(
+            ctx,
+            msg,
+            "The second argument must be one of {}",
+            tracker
+                .categories
+                .iter()
+                .map(|category| format!("`{}`", category.as_str()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    Ok(())
+}
+
+#[command]
+#[checks(has_bugtracker, is_bugtracker_moderator)]
+#[aliases(rename)]
+#[only_in(guilds)]
+pub async fn bug_rename(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let tracker = get_tracker!(ctx, msg);
+    let bug_id = get_bug_id!(ctx, msg, tracker, args);
+
+    let new_title = args.rest();
+    if new_title.is_empty() {
+        failure!(
+            ctx,
+            msg,
+            "You must specify a new title for {}",
+            tracker.display_id(bug_id)
+        );
+    } else if change_title(ctx, bug_id, new_title, msg.author.id)
+        .await
+        .is_ok()
+    {
+        termite_success!(
+            ctx,
+            msg,
+            "Successfully changed the title of {}",
+            tracker.display_id(bug_id)
+        );
+        notify_users(
+            ctx,
+            bug_id,
+            NotificationKind::Update,
+            "The title of a bug you are subscribed to has been changed",
+        )
+        .await?
+    } else {
+        failure!(ctx, msg, "{} does not exist!", tracker.display_id(bug_id));
+    }
+    Ok(())
+}
+
+/// Formats a duration in seconds as days and hours
+fn format_resolution_time(seconds: Option<i64>) -> String {
+    match seconds {
+        Some(seconds) => {
+            let hours = seconds / 3600;
+            if hours < 24 {
+                format!("{}h", hours)
+            } else {
+                format!("{}d {}h", hours / 24, hours % 24)
+            }
+        }
+        None => "-".into(),
+    }
+}
+
+#[command]
+#[checks(has_bugtracker)]
+#[aliases(statistics)]
+#[only_in(guilds)]
+pub async fn stats(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let tracker = get_tracker!(ctx, msg);
+    let weeks = args.single::<u32>().unwrap_or(8).clamp(1, 20);
+
+    if let Some(stats) = get_bug_statistics(ctx, tracker.guild_id, weeks).await {
+        let counts = &stats.counts;
+        let count_lines = |open: bool| {
+            tracker
+                .statuses
+                .iter()
+                .rev()
+                .filter(|status| status.open == open)
+                .map(|status| format!("{} {} {}", counts.get(&status.name), status.marker, status))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let labels = get_label_statistics(ctx, tracker.guild_id)
+            .await
+            .unwrap_or_default();
+        let second_age = stats.categories[BugCategory::SaRenewed.index()].total
+            + stats.categories[BugCategory::SaLegacy.index()].total;
+        msg.channel_id
+            .send_message(ctx, |m| {
+                m.embed(|e| {
+                    e.author(|a| {
+                        a.name(tracker_name(ctx, tracker.guild_id));
+                        a.icon_url(crate::constants::TERMITE_IMAGE);
+                        a
+                    });
+                    e.colour(serenity::utils::Colour::TEAL);
+                    e.field(
+                        "Bugtracker statistics",
+                        format!(
+                            "{}
+
+_Open bugs: {}_
+{}
+
+**Total: {} tracked bugs**
+_{} first age bugs, {} second age bugs_
+",
+                            count_lines(false),
+                            counts.open,
+                            count_lines(true),
+                            counts.total,
+                            counts.total - second_age,
+                            second_age
+                        ),
+                        false,
+                    );
+                    e.field(
+                        "Categories",
+                        BugCategory::ALL
+                            .iter()
+                            .zip(stats.categories.iter())
+                            .map(|(category, counts)| {
+                                format!(
+                                    "**{}**: {} open, {} resolved, {} total",
+                                    category,
+                                    counts.open,
+                                    counts.get(STATUS_RESOLVED),
+                                    counts.total
+                                )
+                            })
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                        false,
+                    );
+                    e.field(
+                        format!("Last {} weeks", weeks),
+                        stats
+                            .weeks
+                            .iter()
+                            .enumerate()
+                            .map(|(i, week)| {
+                                format!(
+                                    "{}: {} opened, {} resolved",
+                                    match i {
+                                        0 => "Past 7 days".to_string(),
+                                        1 => "1 week ago".to_string(),
+                                        i => format!("{} weeks ago", i),
+                                    },
+                                    week.opened,
+                                    week.resolved
+                                )
+                            })
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                        false,
+                    );
+                    e.field(
+                        "Median time to resolve",
+                        BugCategory::ALL
+                            .iter()
+                            .zip(stats.median_by_category.iter())
+                            .map(|(category, &median)| {
+                                format!("{}: {}", category, format_resolution_time(median))
+                            })
+                            .chain(stats.median_by_priority.iter().map(|(priority, median)| {
+                                format!(
+                                    "{} priority: {}",
+                                    priority,
+                                    format_resolution_time(*median)
+                                )
+                            }))
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                        false,
+                    );
+                    if !labels.is_empty() {
+                        e.field(
+                            "Labels",
+                            labels
+                                .iter()
+                                .map(|(label, open, total)| {
+                                    format!("`{}`: {} open, {} total", label, open, total)
+                                })
+                                .collect::<Vec<_>>()
+                                .join("\n"),
+                            false,
+                        );
+                    }
+                    e
+                })
+            })
+            .await?;
+    } else {
+        failure!(ctx, msg, "Could not fetch bugtracker statistics");
+    }
+    Ok(())
+}
+
+/// Formats a list of bugs for an embed field, truncated to fit the field size limit
+fn format_digest_list(bugs: &[PartialBugReport]) -> String {
+    if bugs.is_empty() {
+        return "None".into();
+    }
+    let mut content = String::new();
+    for (i, bug) in bugs.iter().enumerate() {
+        let line = format!("{} {}\n", bug.status.marker, bug);
+        if content.len() + line.len() > 1000 {
+            content.push_str(&format!("...and {} more", bugs.len() - i));
+            break;
+        }
+        content.push_str(&line);
+    }
+    content
+}
+
+async fn send_bug_digest(ctx: &Context, config: &DigestConfig) -> CommandResult {
+    let digest = get_bug_digest(ctx, config.guild_id, config.stale_days).await?;
+
+    config
+        .channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.author(|a| {
+                    a.name(tracker_name(ctx, config.guild_id));
+                    a.icon_url(crate::constants::TERMITE_IMAGE);
+                    a
+                });
+                e.colour(serenity::utils::Colour::TEAL);
+                e.title("Weekly bugtracker digest");
+                e.field(
+                    format!("New bugs ({})", digest.new.len()),
+                    format_digest_list(&digest.new),
+                    false,
+                );
+                e.field(
+                    format!("Resolved this week ({})", digest.resolved.len()),
+                    format_digest_list(&digest.resolved),
+                    false,
+                );
+                e.field(
+                    format!(
+                        "High priority bugs open for more than {} days ({})",
+                        config.stale_days,
+                        digest.stale.len()
+                    ),
+                    format_digest_list(&digest.stale),
+                    false,
+                );
+                e.timestamp(chrono::Utc::now());
+                e
+            })
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// Posts the weekly digest in every configured channel that is due for one.
+///
+/// Called periodically by the [scheduler][crate::scheduler].
+pub async fn post_bug_digests(ctx: &Context) -> CommandResult {
+    for config in get_due_digests(ctx).await? {
+        if let Err(e) = send_bug_digest(ctx, &config).await {
+            println!(
+                "=== ERROR ===
+Could not post the bug digest in {} (guild {})
+Error: {}
+=== END ===",
+                config.channel_id, config.guild_id, e
+            );
+            continue;
+        }
+        set_digest_sent(ctx, config.guild_id).await?;
+    }
+
+    Ok(())
+}
+
+#[command]
+#[checks(has_bugtracker, is_bugtracker_moderator)]
+#[aliases("digest")]
+#[only_in(guilds)]
+pub async fn bug_digest(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.ok_or(NotInGuild)?;
+
+    if args.is_empty() {
+        if let Some(config) = get_digest_config(ctx, guild_id).await {
+            msg.reply(
+                ctx,
+                format!(
+                    "The weekly bug digest is posted in <#{}>, listing high priority bugs open \
+for more than {} days.",
+                    config.channel_id, config.stale_days
+                ),
+            )
+            .await?;
+        } else {
+            msg.reply(ctx, "The weekly bug digest is disabled on this server.")
+                .await?;
+        }
+        return Ok(());
+    }
+
+    if args
+        .current()
+        .map_or(false, |arg| arg.eq_ignore_ascii_case("disable"))
+    {
+        if remove_digest_config(ctx, guild_id).await? {
+            termite_success!(ctx, msg, "The weekly bug digest has been disabled.");
+        } else {
+            failure!(
+                ctx,
+                msg,
+                "The weekly bug digest is not enabled on this server."
+            );
+        }
+        return Ok(());
+    }
+
+    let channel_id = match args.single::<ChannelId>() {
+        Ok(channel_id) => channel_id,
+        Err(_) => {
+            failure!(
+                ctx,
+                msg,
+                "The first argument must be a channel mention, or `disable`."
+            );
+            return Ok(());
+        }
+    };
+    let stale_days = args.single::<u32>().unwrap_or(30).max(1);
+
+    set_digest_config(
+        ctx,
+        DigestConfig {
+            guild_id,
+            channel_id,
+            stale_days,
+        },
+    )
+    .await?;
+
+    termite_success!(
+        ctx,
+        msg,
+        "The weekly bug digest will be posted in <#{}>, listing high priority bugs open for more \
+than {} days.",
+        channel_id,
+        stale_days
+    );
+
+    Ok(())
+}
+
+#[command]
+#[checks(has_bugtracker, is_bugtracker_moderator)]
+#[aliases("help")]
+#[only_in(guilds)]
+pub async fn bugtracker_help(ctx: &Context, msg: &Message) -> CommandResult {
+    crate::commands::help::display_bugtracker_help(ctx, msg).await
+}
+
+/// Embed descriptions are limited to 4096 characters
+const MAX_DIGEST_LENGTH: usize = 4000;
+
+/// Sends their daily digest of notifications to a user, in several messages if it is too
+/// long for one.
+///
+/// The notifications are removed once the message listing them is sent, so that the ones
+/// not sent are kept for the next digest.
+async fn send_notification_digest(ctx: &Context, user_id: UserId) -> CommandResult {
+    let notifications = get_queued_notifications(ctx, user_id).await?;
+    if notifications.is_empty() {
+        return Ok(());
+    }
+
+    // notifications are grouped by bug, in the order of their first notification
+    let mut bugs: Vec<(&QueuedNotification, Vec<&QueuedNotification>)> = Vec::new();
+    for notification in &notifications {
+        match bugs
+            .iter_mut()
+            .find(|(bug, _)| bug.bug_id == notification.bug_id)
+        {
+            Some((_, bug_notifications)) => bug_notifications.push(notification),
+            None => bugs.push((notification, vec![notification])),
+        }
+    }
+
+    // pages of the digest, with the notifications they list
+    let mut pages: Vec<(String, Vec<u64>)> = Vec::new();
+    for (bug, bug_notifications) in &bugs {
+        let mut entry = format!(
+            "**{}-{}: {}**\n{}\n\n",
+            bug.prefix,
+            bug.bug_id,
+            bug.title,
+            bug_notifications
+                .iter()
+                .map(|notification| format!("- {}", notification.message))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+        if entry.len() > MAX_DIGEST_LENGTH {
+            entry = entry
+                .chars()
+                .take(MAX_DIGEST_LENGTH - 3)
+                .collect::<String>()
+                + "...";
+        }
+        let queue_ids = bug_notifications.iter().map(|n| n.queue_id);
+        match pages.last_mut() {
+            Some((description, page_ids))
+                if description.len() + entry.len() <= MAX_DIGEST_LENGTH =>
+            {
+                description.push_str(&entry);
+                page_ids.extend(queue_ids);
+            }
+            _ => pages.push((entry, queue_ids.collect())),
+        }
+    }
+
+    let channel = user_id.create_dm_channel(ctx).await?;
+    let page_count = pages.len();
+    for (page, (description, queue_ids)) in pages.into_iter().enumerate() {
+        let title = if page_count > 1 {
+            format!(
+                "Daily digest of your bug notifications ({}) - {}/{}",
+                notifications.len(),
+                page + 1,
+                page_count
+            )
+        } else {
+            format!(
+                "Daily digest of your bug notifications ({})",
+                notifications.len()
+            )
+        };
+        channel
+            .send_message(ctx, |m| {
+                m.embed(|e| {
+                    e.author(|a| {
+                        a.name("Bugtracker")
+                            .icon_url(crate::constants::TERMITE_IMAGE)
+                    })
+                    .colour(serenity::utils::Colour::TEAL)
+                    .title(title)
+                    .description(description)
+                    .footer(|f| {
+                        f.text("Change how you are notified with  !bug notifications settings")
+                    })
+                })
+            })
+            .await?;
+        remove_queued_notifications(ctx, user_id, &queue_ids).await?;
+    }
+
+    set_notification_digest_sent(ctx, user_id).await?;
+
+    Ok(())
+}
+
+/// Sends the daily digests of the users in digest mode, and the notifications queued
+/// before a user left digest mode.
+///
+/// Called periodically by the [scheduler][crate::scheduler].
+pub async fn send_notification_digests(ctx: &Context) -> CommandResult {
+    for user_id in get_due_notification_digests(ctx).await? {
+        if let Err(e) = send_notification_digest(ctx, user_id).await {
+            println!(
+                "=== ERROR ===
+Could not send the notification digest of {}
+Error: {}
+=== END ===",
+                user_id, e
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[command]
+#[sub_commands(notifications_settings)]
+pub async fn notifications(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let closed = args
+        .single::<String>()
+        .map(|s| s.eq_ignore_ascii_case("closed") || s.eq_ignore_ascii_case("all"))
+        .unwrap_or_default();
+
+    let list = get_notifications_for_user(ctx, msg.author.id, closed).await?;
+
+    if list.is_empty() {
+        msg.reply(
+            ctx,
+            if closed {
+                "You have no notifications for now.
+Subscribe to a bug report to receive notifications."
+            } else {
+                "You have no active notifications for now.
+Type  `!bug notifications all` to see all your notifications, \
+including those from closed or resolved bugs."
+            },
+        )
+        .await?;
+    } else {
+        msg.channel_id
+            .send_message(ctx, |m| {
+                m.embed(|e| {
+                    e.author(|a| {
+                        a.name("Bugtracker")
+                            .icon_url(crate::constants::TERMITE_IMAGE)
+                    })
+                    .colour(serenity::utils::Colour::TEAL)
+                    .title("Bug notifications")
+                    .description(format!(
+                        "_List of bugs you are subscribed to_\n\n{}\n\n\
+Change how you are notified with  `!bug notifications settings`",
+                        list.iter()
+                            .map(|(id, prefix)| format!("{prefix}-{id}"))
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    ))
+                    .footer(|f| {
+                        f.text(if closed {
+                            "Including closed and resolved bugs"
+                        } else {
+                            "To see closed and resolved bugs, use  !bug notifications all"
+                        })
+                    });
+
+                    e
+                })
+            })
+            .await?;
+    }
+    Ok(())
+}
+
+#[command]
+#[aliases("settings", "mode")]
+pub async fn notifications_settings(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let modes = NotificationMode::ALL
+        .iter()
+        .map(|mode| format!("`{}`: {}", mode.as_str(), mode.description()))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if args.is_empty() {
+        let mode = get_notification_mode(ctx, msg.author.id)
+            .await
+            .ok_or("Could not get the notification mode")?;
+        msg.reply(
+            ctx,
+            format!(
+                "Your bug notification mode is `{}`: {}.
+Change it with  `!bug notifications settings <mode>`, the available modes being:
+{}",
+                mode.as_str(),
+                mode.description(),
+                modes
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    match args.rest().trim().parse::<NotificationMode>() {
+        Ok(mode) => {
+            set_notification_mode(ctx, msg.author.id, mode).await?;
+            crate::success!(
+                ctx,
+                msg,
+                "Your bug notification mode is now `{}`: {}.",
+                mode.as_str(),
+                mode.description()
+            );
+        }
+        Err(_) => {
+            failure!(
+                ctx,
+                msg,
+                "Unknown notification mode! The available modes are:\n{}",
+                modes
+            );
+        }
+    }
+    Ok(())
+}
+
+#[command]
+pub async fn unsubscribe(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let bug_id = match args.single::<u64>() {
+        Ok(bug_id) => bug_id,
+        _ => {
+            failure!(ctx, msg, "The first argument must be a bug id!");
+            return Ok(());
+        }
+    };
+
+    if is_notified_user(ctx, bug_id, msg.author.id).await != Some(true) {
+        failure!(ctx, msg, "You are not subscribed to this bug!");
+        return Ok(());
+    }
+
+    if let Err(e) =
+        crate::database::bug_reports::remove_notified_user(ctx, bug_id, msg.author.id).await
+    {
+        failure!(
+            ctx,
+            msg,
+            "Could not unsubscribe from {}-{}",
+            get_bug_prefix(ctx, bug_id).await,
+            bug_id
+        );
+        return Err(e);
+    }
+
+    crate::success!(
+        ctx,
+        msg,
+        "Successfully unsubscribed from {}-{}.
+You will no longer be notified if this bug is edited, closed or resolved.",
+        get_bug_prefix(ctx, bug_id).await,
+        bug_id
+    );
+
+    Ok(())
+}
+
+#[command]
+pub async fn subscribe(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let bug_id = match args.single::<u64>() {
+        Ok(bug_id) => bug_id,
+        _ => {
+            failure!(ctx, msg, "The first argument must be a bug id!");
+            return Ok(());
+        }
+    };
+
+    if is_notified_user(ctx, bug_id, msg.author.id).await != Some(false) {
+        failure!(ctx, msg, "You are already subscribed to this bug!");
+        return Ok(());
+    }
+
+    if let Err(e) =
+        crate::database::bug_reports::add_notified_user(ctx, bug_id, msg.author.id).await
+    {
+        failure!(
+            ctx,
+            msg,
+            "Could not subscribe to {}-{}",
+            get_bug_prefix(ctx, bug_id).await,
+            bug_id
+        );
+        return Err(e);
+    }
+
+    crate::success!(
+        ctx,
+        msg,
+        "Successfully subscribed to {}-{}.
+You will be notified if this bug is edited, closed or resolved.",
+        get_bug_prefix(ctx, bug_id).await,
+        bug_id
+    );
+
+    Ok(())
+}
+
+/// Adds the bug status choices of a tracker to a slash command option
+fn add_status_choices<'a, 'b>(
+    option: &'a mut CreateApplicationCommandOption,
+    statuses: impl IntoIterator<Item = &'b BugStatus>,
+) -> &'a mut CreateApplicationCommandOption {
+    // Discord allows at most 25 choices
+    for status in statuses.into_iter().take(25) {
+        option.add_string_choice(status, status.as_str());
+    }
+    option
+}
+
+/// Adds the bug category choices of a tracker to a slash command option
+fn add_category_choices<'a>(
+    option: &'a mut CreateApplicationCommandOption,
+    categories: &[BugCategory],
+) -> &'a mut CreateApplicationCommandOption {
+    for category in categories {
+        option.add_string_choice(category, category.as_str());
+    }
+    option
+}
+
+/// Builds the `/bug` slash command, registered in each guild with a bug tracker
+pub fn create_bug_application_command<'a>(
+    c: &'a mut CreateApplicationCommand,
+    tracker: &BugTracker,
+) -> &'a mut CreateApplicationCommand {
+    c.name("bug")
+        .description(format!("{} bugtracker", tracker.prefix))
+        .create_option(|o| {
+            o.name("track")
+                .description("Track a new bug report")
+                .kind(ApplicationCommandOptionType::SubCommand)
+                .create_sub_option(|s| {
+                    s.name("message")
+                        .description("Link to the message of the bug report")
+                        .kind(ApplicationCommandOptionType::String)
+                        .required(true)
+                })
+                .create_sub_option(|s| {
+                    s.name("title")
+                        .description("Title of the bug report")
+                        .kind(ApplicationCommandOptionType::String)
+                        .required(true)
+                })
+                .create_sub_option(|s| {
+                    add_status_choices(
+                        s.name("status")
+                            .description(format!(
+                                "Priority of the bug ({} by default)",
+                                tracker.default_status()
+                            ))
+                            .kind(ApplicationCommandOptionType::String),
+                        tracker
+                            .statuses
+                            .iter()
+                            .filter(|status| !status.is_reserved()),
+                    )
+                })
+                .create_sub_option(|s| {
+                    add_category_choices(
+                        s.name("category")
+                            .description(format!(
+                                "Edition affected by the bug ({} by default)",
+                                tracker.default_category()
+                            ))
+                            .kind(ApplicationCommandOptionType::String),
+                        &tracker.categories,
+                    )
+                })
+                .create_sub_option(|s| {
+                    s.name("thread")
+                        .description("Open a discussion thread on the reported message")
+                        .kind(ApplicationCommandOptionType::Boolean)
+                })
+                .create_sub_option(|s| {
+                    s.name("mirror")
+                        .description(
+                            "Open an issue mirroring the bug, if the tracker is set up for it",
+                        )
+                        .kind(ApplicationCommandOptionType::Boolean)
+                })
+        })
+        .create_option(|o| {
+            o.name("view")
+                .description("Display a bug report")
+                .kind(ApplicationCommandOptionType::SubCommand)
+                .create_sub_option(|s| {
+                    s.name("bug")
+                        .description("Bug id or title")
+                        .kind(ApplicationCommandOptionType::String)
+                        .required(true)
+                        .set_autocomplete(true)
+                })
+        })
+        .create_option(|o| {
+            o.name("status")
+                .description("Change the status of a bug report")
+                .kind(ApplicationCommandOptionType::SubCommand)
+                .create_sub_option(|s| {
+                    s.name("bug")
+                        .description("Bug id or title")
+                        .kind(ApplicationCommandOptionType::String)
+                        .required(true)
+                        .set_autocomplete(true)
+                })
+                .create_sub_option(|s| {
+                    add_status_choices(
+                        s.name("status")
+                            .description("New status of the bug")
+                            .kind(ApplicationCommandOptionType::String)
+                            .required(true),
+                        &tracker.statuses,
+                    )
+                })
+        })
+        .create_option(|o| {
+            o.name("link")
+                .description("Add a link to a bug report")
+                .kind(ApplicationCommandOptionType::SubCommand)
+                .create_sub_option(|s| {
+                    s.name("bug")
+                        .description("Bug id or title")
+                        .kind(ApplicationCommandOptionType::String)
+                        .required(true)
+                        .set_autocomplete(true)
+                })
+                .create_sub_option(|s| {
+                    s.name("url")
+                        .description("Url of the link")
+                        .kind(ApplicationCommandOptionType::String)
+                        .required(true)
+                })
+                .create_sub_option(|s| {
+                    s.name("title")
+                        .description("Title of the link")
+                        .kind(ApplicationCommandOptionType::String)
+                        .required(true)
+                })
+        })
+        .create_option(|o| {
+            o.name("list")
+                .description("Display a list of bug reports")
+                .kind(ApplicationCommandOptionType::SubCommand)
+                .create_sub_option(|s| {
+                    add_status_choices(
+                        s.name("status")
+                            .description(
+                                "Only display bugs with this status (open bugs by default)",
+                            )
+                            .kind(ApplicationCommandOptionType::String),
+                        &tracker.statuses,
+                    )
+                })
+                .create_sub_option(|s| {
+                    add_category_choices(
+                        s.name("category")
+                            .description("Only display bugs of this edition")
+                            .kind(ApplicationCommandOptionType::String),
+                        &tracker.categories,
+                    )
+                })
+                .create_sub_option(|s| {
+                    s.name("order")
+                        .description("Display order of the bugs")
+                        .kind(ApplicationCommandOptionType::String)
+                        .add_string_choice("Latest", "latest")
+                        .add_string_choice("Oldest", "oldest")
+                        .add_string_choice("Highest priority", "highest")
+                        .add_string_choice("Lowest priority", "lowest")
+                        .add_string_choice("Most affected users", "affected")
+                })
+                .create_sub_option(|s| {
+                    s.name("page")
+                        .description("Page number")
+                        .kind(ApplicationCommandOptionType::Integer)
+                        .min_int_value(1)
+                })
+        })
+}
+
+fn get_option<'a>(
+    options: &'a [ApplicationCommandInteractionDataOption],
+    name: &str,
+) -> Option<&'a serde_json::Value> {
+    options
+        .iter()
+        .find(|option| option.name == name)
+        .and_then(|option| option.value.as_ref())
+}
+
+fn get_str_option<'a>(
+    options: &'a [ApplicationCommandInteractionDataOption],
+    name: &str,
+) -> Option<&'a str> {
+    get_option(options, name).and_then(|value| value.as_str())
+}
+
+async fn respond(
+    ctx: &Context,
+    interaction: &ApplicationCommandInteraction,
+    content: impl ToString,
+) -> Result<(), SerenityError> {
+    interaction
+        .create_interaction_response(ctx, |r| {
+            r.kind(InteractionResponseType::ChannelMessageWithSource)
+                .interaction_response_data(|d| {
+                    d.content(content).allowed_mentions(|f| f.empty_parse())
+                })
+        })
+        .await
+}
+
+/// Handles the `/bug` slash command
+pub async fn bug_application_command(
+    ctx: &Context,
+    interaction: &ApplicationCommandInteraction,
+) -> CommandResult {
+    let subcommand = match interaction.data.options.first() {
+        Some(subcommand) => subcommand,
+        None => return Ok(()),
+    };
+    let options = subcommand.options.as_slice();
+    let reply_to = Either::Command(interaction);
+
+    let tracker = match interaction.guild_id {
+        Some(guild_id) => get_bug_tracker(ctx, guild_id).await,
+        None => None,
+    };
+    let tracker = match tracker {
+        Some(tracker) => tracker,
+        None => {
+            reply_to
+                .failure(ctx, "There is no bug tracker on this server!")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    if subcommand.name != "view"
+        && subcommand.name != "list"
+        && !is_tracker_moderator(ctx, tracker.guild_id, interaction.user.id).await
+    {
+        reply_to
+            .failure(ctx, "You are not a moderator of the bugtracker!")
+            .await?;
+        return Ok(());
+    }
+
+    if subcommand.name == "track" {
+        return slash_track(ctx, interaction, &tracker, options).await;
+    }
+    if subcommand.name == "list" {
+        return slash_list(ctx, interaction, &tracker, options).await;
+    }
+
+    let bug_id = match get_str_option(options, "bug").and_then(|s| tracker.parse_bug_id(s)) {
+        Some(bug_id) => bug_id,
+        None => {
+            reply_to.failure(ctx, "Invalid bug id!").await?;
+            return Ok(());
+        }
+    };
+    if !is_tracker_bug(ctx, tracker.guild_id, bug_id)
+        .await
+        .unwrap_or_default()
+    {
+        reply_to
+            .failure(
+                ctx,
+                &format!("The bug {} does not exist!", tracker.display_id(bug_id)),
+            )
+            .await?;
+        return Ok(());
+    }
+
+    match subcommand.name.as_str() {
+        "view" => {
+            let bug = match get_bug_from_id(ctx, bug_id).await {
+                Ok(bug) => bug,
+                Err(_) => {
+                    reply_to
+                        .failure(
+                            ctx,
+                            &format!("The bug {} does not exist!", tracker.display_id(bug_id)),
+                        )
+                        .await?;
+                    return Ok(());
+                }
+            };
+            let linked_message = bug
+                .channel_id
+                .message(ctx, bug.message_id)
+                .await
+                .map(|mut m| {
+                    m.guild_id = Some(bug.guild_id);
+                    m
+                });
+            let message_link = linked_message.as_ref().map(|m| m.link()).ok();
+
+            interaction
+                .create_interaction_response(ctx, |r| {
+                    r.kind(InteractionResponseType::ChannelMessageWithSource)
+                        .interaction_response_data(|d| {
+                            d.create_embed(create_bug_embed!(ctx, bug, linked_message))
+                                .components(|c| {
+                                    c.create_action_row(|a| {
+                                        a.create_button(|b| {
+                                            b.style(ButtonStyle::Primary)
+                                                .label("Subscribe")
+                                                .custom_id(format!("bug_subscribe__{bug_id}"))
+                                        });
+                                        a.create_button(|b| {
+                                            b.style(ButtonStyle::Secondary)
+                                                .label("I have this too")
+                                                .custom_id(format!("bug_affected__{bug_id}"))
+                                        });
+                                        if let Some(link) = message_link.as_ref() {
+                                            a.create_button(|b| {
+                                                b.style(ButtonStyle::Link)
+                                                    .label("Message link")
+                                                    .url(link)
+                                            });
+                                        }
+                                        a
+                                    })
+                                })
+                        })
+                })
+                .await?;
+        }
+        "status" => {
+            let new_status =
+                match get_str_option(options, "status").and_then(|s| tracker.parse_status(s)) {
+                    Some(status) => status,
+                    None => {
+                        reply_to.failure(ctx, "Invalid bug status!").await?;
+                        return Ok(());
+                    }
+                };
+            let old_status =
+                match change_bug_status(ctx, bug_id, &new_status, interaction.user.id).await {
+                    Ok(old_status) => old_status,
+                    Err(_) => {
+                        reply_to
+                            .failure(
+                                ctx,
+                                &format!("The bug {} does not exist!", tracker.display_id(bug_id)),
+                            )
+                            .await?;
+                        return Ok(());
+                    }
+                };
+            respond(
+                ctx,
+                interaction,
+                format!(
+                    "Status changed for {} from `{}` to `{}`!",
+                    tracker.display_id(bug_id),
+                    old_status,
+                    new_status
+                ),
+            )
+            .await?;
+
+            if old_status != new_status {
+                notify_users(
+                    ctx,
+                    bug_id,
+                    NotificationKind::of_status(&new_status),
+                    format!(
+                        "A bug you are subscribed to has been changed from `{}` to `{}`",
+                        old_status, new_status
+                    ),
+                )
+                .await?;
+                update_bug_thread(ctx, bug_id, &new_status).await?;
+            }
+            if new_status.name == STATUS_NEEDS_INFO {
+                if let Err(e) =
+                    request_more_info(ctx, tracker, bug_id, interaction.user.id, None).await
+                {
+                    println!(
+                        "=== ERROR ===\nCould not ask the reporter of bug {} for more information: \
+{}\n=== END ===",
+                        tracker.display_id(bug_id),
+                        e
+                    );
+                }
+            }
+        }
+        "link" => {
+            let url = get_str_option(options, "url").unwrap_or_default();
+            if !url.starts_with("http") {
+                reply_to
+                    .failure(ctx, "The link must be a valid url!")
+                    .await?;
+                return Ok(());
+            }
+            let title = get_str_option(options, "title").unwrap_or_default();
+            if let Some(link_id) = add_link(ctx, bug_id, url, title, interaction.user.id).await {
+                respond(
+                    ctx,
+                    interaction,
+                    format!("Added link #{} to {}", link_id, tracker.display_id(bug_id)),
+                )
+                .await?;
+                notify_users(
+                    ctx,
+                    bug_id,
+                    NotificationKind::Update,
+                    format!("Link #{link_id} has been added to a bug you are subscribed to"),
+                )
+                .await?;
+            } else {
+                reply_to
+                    .failure(
+                        ctx,
+                        &format!("{} does not exist!", tracker.display_id(bug_id)),
+                    )
+                    .await?;
+            }
+        }
+        _ => (),
+    }
+
+    Ok(())
+}
+
+async fn slash_track(
+    ctx: &Context,
+    interaction: &ApplicationCommandInteraction,
+    tracker: &BugTracker,
+    options: &[ApplicationCommandInteractionDataOption],
+) -> CommandResult {
+    let reply_to = Either::Command(interaction);
+
+    let (channel_id, message_id) =
+        match get_str_option(options, "message").and_then(parse_message_link) {
+            Some(ids) => ids,
+            None => {
+                reply_to
+                    .failure(ctx, "The message must be a valid message link!")
+                    .await?;
+                return Ok(());
+            }
+        };
+    let reported_message = match channel_id.message(ctx, message_id).await {
+        Ok(message) => message,
+        Err(_) => {
+            reply_to
+                .failure(ctx, "Could not find the reported message!")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let title = get_str_option(options, "title").unwrap_or_default().trim();
+    if title.is_empty() {
+        reply_to
+            .failure(ctx, "You must provide a title for the bug report!")
+            .await?;
+        return Ok(());
+    }
+    let status = get_str_option(options, "status")
+        .and_then(|s| tracker.parse_status(s))
+        .unwrap_or_else(|| tracker.default_status());
+    let category = get_str_option(options, "category")
+        .and_then(|s| s.parse().ok())
+        .filter(|category| tracker.has_category(*category))
+        .unwrap_or_else(|| tracker.default_category());
+    let open_thread = get_option(options, "thread")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false);
+    let issue_sync = if get_option(options, "mirror")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+    {
+        match get_issue_sync(ctx, tracker.guild_id).await {
+            Some(issue_sync) => Some(issue_sync),
+            None => {
+                reply_to
+                    .failure(ctx, "Bugs are not mirrored on this server!")
+                    .await?;
+                return Ok(());
+            }
+        }
+    } else {
+        None
+    };
+
+    let bug_id = match add_bug_report(
+        ctx,
+        tracker.guild_id,
+        &reported_message,
+        title.to_string(),
+        &status,
+        category,
+    )
+    .await
+    {
+        Ok(bug_id) => bug_id,
+        Err(e) => {
+            reply_to
+                .failure(ctx, "Could not submit the bug report!")
+                .await?;
+            return Err(e);
+        }
+    };
+
+    interaction
+        .create_interaction_response(ctx, |r| {
+            r.kind(InteractionResponseType::ChannelMessageWithSource)
+                .interaction_response_data(|d| {
+                    d.content(format!(
+                        "Tracking bug {} (priority: `{}`) [{}]",
+                        tracker.display_id(bug_id),
+                        status,
+                        category
+                    ))
+                    .components(|c| {
+                        c.create_action_row(|a| {
+                            a.create_button(|b| {
+                                b.style(ButtonStyle::Primary)
+                                    .label("Subscribe")
+                                    .custom_id(format!("bug_subscribe__{bug_id}"))
+                            })
+                        })
+                    })
+                })
+        })
+        .await?;
+
+    if open_thread {
+        if let Err(e) = open_bug_thread(ctx, tracker, bug_id, title, &reported_message).await {
+            println!(
+                "=== ERROR ===
+Could not create a discussion thread for bug {}
+Error: {e}
+=== END ===",
+                tracker.display_id(bug_id)
+            );
+        }
+    }
+
+    // mirrored after the response, as interactions must be answered within 3 seconds
+    if let Some(issue_sync) = &issue_sync {
+        if let Err(e) = mirror_bug(
+            ctx,
+            tracker,
+            issue_sync,
+            bug_id,
+            title,
+            &reported_message,
+            interaction.user.id,
+        )
+        .await
+        {
+            println!(
+                "=== ERROR ===
+Could not mirror bug {} in {}
+Error: {e}
+=== END ===",
+                tracker.display_id(bug_id),
+                issue_sync.repository
+            );
+        }
+    }
+
+    if tracker.channel_id != Some(interaction.channel_id) {
+        announce_bug(ctx, tracker, bug_id, title, &reported_message).await;
+    }
+
+    subscribe_reporter(ctx, bug_id, reported_message.author.id).await
+}
+
+async fn slash_list(
+    ctx: &Context,
+    interaction: &ApplicationCommandInteraction,
+    tracker: &BugTracker,
+    options: &[ApplicationCommandInteractionDataOption],
+) -> CommandResult {
+    let filter = BugFilter {
+        guild_id: Some(tracker.guild_id),
+        status: get_str_option(options, "status").and_then(|s| tracker.parse_status(s)),
+        category: get_str_option(options, "category").and_then(|s| s.parse().ok()),
+        ..Default::default()
+    };
+    let display_order = match get_str_option(options, "order") {
+        Some("oldest") => BugOrder::Chronological(true),
+        Some("highest") => BugOrder::Priority(false),
+        Some("lowest") => BugOrder::Priority(true),
+        Some("affected") => BugOrder::Affected(false),
+        _ => BugOrder::Chronological(false),
+    };
+    let page = get_option(options, "page")
+        .and_then(|value| value.as_u64())
+        .unwrap_or(1)
+        .max(1) as u32;
+    let limit = 10;
+
+    let response_message = match display_bugs(
+        ctx,
+        &filter,
+        limit,
+        display_order,
+        page,
+        Either::Command(interaction),
+    )
+    .await
+    {
+        Ok(Some(msg)) => msg,
+        Ok(None) => unreachable!(),
+        Err(SerenityError::Other("page_too_high" | "too_many_bugs")) => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+
+    paginate_bug_list(
+        ctx,
+        response_message,
+        interaction.user.id,
+        &filter,
+        limit,
+        display_order,
+        page,
+    )
+    .await
+}
+
+/// Suggests bugs by id or title for the `bug` options of the `/bug` slash command
+pub async fn bug_autocomplete(
+    ctx: &Context,
+    interaction: &AutocompleteInteraction,
+) -> CommandResult {
+    let partial = interaction
+        .data
+        .options
+        .iter()
+        .flat_map(|subcommand| subcommand.options.iter())
+        .find(|option| option.focused)
+        .and_then(|option| option.value.as_ref())
+        .and_then(|value| value.as_str())
+        .unwrap_or_default();
+
+    let tracker = match interaction.guild_id {
+        Some(guild_id) => get_bug_tracker(ctx, guild_id).await,
+        None => None,
+    };
+    let bugs = match &tracker {
+        Some(tracker) => autocomplete_bugs(ctx, tracker, partial, 25).await,
+        None => vec![],
+    };
+
+    interaction
+        .create_autocomplete_response(ctx, |r| {
+            for (bug_id, title) in bugs {
+                // choice names are limited to 100 characters
+                let name: String = format!(
+                    "{}: {}",
+                    tracker
+                        .as_ref()
+                        .map_or_else(String::new, |t| t.display_id(bug_id)),
+                    title
+                )
+                .chars()
+                .take(100)
+                .collect();
+                r.add_string_choice(name, bug_id.to_string());
+            }
+            r
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// Maps the free-form edition of the bug report form to a bug category of the tracker
+fn parse_edition(tracker: &BugTracker, edition: &str) -> BugCategory {
+    let edition = edition.to_lowercase();
+    let second_age = edition.contains("sa") || edition.contains("second");
+    let legacy = edition.contains("legacy") || edition.contains("1.7");
+    let category = match (second_age, legacy) {
+        (false, false) => BugCategory::FaRenewed,
+        (false, true) => BugCategory::FaLegacy,
+        (true, false) => BugCategory::SaRenewed,
+        (true, true) => BugCategory::SaLegacy,
+    };
+    if tracker.has_category(category) {
+        category
+    } else {
+        tracker.default_category()
+    }
+}
+
+/// Opens the bug report form, from the button added by the `bug_report_button`
+/// field of an [announcement][crate::announcement::Announcement]
+pub async fn open_bug_report_form(
+    ctx: &Context,
+    interaction: &MessageComponentInteraction,
+    channel_id: ChannelId,
+) -> CommandResult {
+    interaction
+        .create_interaction_response(ctx, |r| {
+            r.kind(InteractionResponseType::Modal)
+                .interaction_response_data(|d| {
+                    d.custom_id(format!("bug_report_modal__{}", channel_id.0))
+                        .title("Report a bug")
+                        .components(|c| {
+                            c.create_action_row(|a| {
+                                a.create_input_text(|t| {
+                                    t.custom_id("title")
+                                        .label("Title")
+                                        .style(InputTextStyle::Short)
+                                        .placeholder("Short description of the bug")
+                                        .max_length(200)
+                                        .required(true)
+                                })
+                            })
+                            .create_action_row(|a| {
+                                a.create_input_text(|t| {
+                                    t.custom_id("edition")
+                                        .label("Mod edition")
+                                        .style(InputTextStyle::Short)
+                                        .placeholder("Renewed, Legacy, SA Renewed or SA Legacy")
+                                        .max_length(50)
+                                        .required(true)
+                                })
+                            })
+                            .create_action_row(|a| {
+                                a.create_input_text(|t| {
+                                    t.custom_id("versions")
+                                        .label("Minecraft and Forge versions")
+                                        .style(InputTextStyle::Short)
+                                        .placeholder("Minecraft 1.16.5, Forge 36.2.0")
+                                        .max_length(100)
+                                        .required(true)
+                                })
+                            })
+                            .create_action_row(|a| {
+                                a.create_input_text(|t| {
+                                    t.custom_id("steps")
+                                        .label("Steps to reproduce")
+                                        .style(InputTextStyle::Paragraph)
+                                        .max_length(1200)
+                                        .required(true)
+                                })
+                            })
+                            .create_action_row(|a| {
+                                a.create_input_text(|t| {
+                                    t.custom_id("crash_log")
+                                        .label("Crash log link")
+                                        .style(InputTextStyle::Short)
+                                        .placeholder("https://pastebin.com/...")
+                                        .max_length(200)
+                                        .required(false)
+                                })
+                            })
+                        })
+                })
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// Creates a bug report from a submitted bug report form, and posts it in the bug channel
+pub async fn submit_bug_report_form(
+    ctx: &Context,
+    interaction: &ModalSubmitInteraction,
+    channel_id: ChannelId,
+) -> CommandResult {
+    let tracker = match interaction.guild_id {
+        Some(guild_id) => get_bug_tracker(ctx, guild_id).await,
+        None => None,
+    };
+    let tracker = match tracker {
+        Some(tracker) => tracker,
+        None => {
+            interaction
+                .create_interaction_response(ctx, |r| {
+                    r.kind(InteractionResponseType::ChannelMessageWithSource)
+                        .interaction_response_data(|d| {
+                            d.flags(InteractionApplicationCommandCallbackDataFlags::EPHEMERAL)
+                                .content(":x: There is no bug tracker on this server!")
+                        })
+                })
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let field = |custom_id: &str| {
+        interaction
+            .data
+            .components
+            .iter()
+            .flat_map(|row| row.components.iter())
+            .find_map(|component| match component {
+                ActionRowComponent::InputText(input) if input.custom_id == custom_id => {
+                    Some(input.value.trim())
+                }
+                _ => None,
+            })
+            .unwrap_or_default()
+    };
+
+    let title = field("title");
+    let category = parse_edition(&tracker, field("edition"));
+    let crash_log = field("crash_log");
+
+    let format_report = |steps: &str| {
+        format!(
+            "**{}**
+Reported by <@{}>
+
+**Edition:** {}
+**Minecraft and Forge versions:** {}
+**Steps to reproduce:**
+{}{}",
+            title,
+            interaction.user.id.0,
+            category,
+            field("versions"),
+            steps,
+            if crash_log.is_empty() {
+                String::new()
+            } else {
+                format!("\n**Crash log:** {}", crash_log)
+            }
+        )
+    };
+
+    // leaves room for the tracking header added once the bug is saved,
+    // as a message can't be longer than 2000 characters
+    const MAX_REPORT_LENGTH: usize = 1900;
+    let steps = field("steps");
+    let mut content = format_report(steps);
+    let overflow = content.chars().count().saturating_sub(MAX_REPORT_LENGTH);
+    if overflow > 0 {
+        let kept = steps.chars().count().saturating_sub(overflow + 3);
+        content = format_report(&format!(
+            "{}...",
+            steps.chars().take(kept).collect::<String>()
+        ));
+    }
+
+    let mut report_message = match channel_id
+        .send_message(ctx, |m| {
+            m.content(content).allowed_mentions(|f| f.empty_parse())
+        })
+        .await
+    {
+        Ok(report_message) => report_message,
+        Err(e) => {
+            interaction
+                .create_interaction_response(ctx, |r| {
+                    r.kind(InteractionResponseType::ChannelMessageWithSource)
+                        .interaction_response_data(|d| {
+                            d.flags(InteractionApplicationCommandCallbackDataFlags::EPHEMERAL)
+                                .content(":x: Could not post the bug report!")
+                        })
+                })
+                .await?;
+            return Err(e.into());
+        }
+    };
+
+    let status = tracker.default_status();
+    let bug_id = match add_bug_report(
+        ctx,
+        tracker.guild_id,
+        &report_message,
+        title.to_string(),
+        &status,
+        category,
+    )
+    .await
+    {
+        Ok(bug_id) => bug_id,
+        Err(e) => {
+            interaction
+                .create_interaction_response(ctx, |r| {
+                    r.kind(InteractionResponseType::ChannelMessageWithSource)
+                        .interaction_response_data(|d| {
+                            d.flags(InteractionApplicationCommandCallbackDataFlags::EPHEMERAL)
+                                .content(":x: Could not submit the bug report!")
+                        })
+                })
+                .await?;
+            return Err(e);
+        }
+    };
+
+    let report = format!(
+        "Tracking bug {} (priority: `{}`) [{}]\n\n{}",
+        tracker.display_id(bug_id),
+        status,
+        category,
+        report_message.content
+    );
+    report_message
+        .edit(ctx, |m| {
+            m.content(report).components(|c| {
+                c.create_action_row(|a| {
+                    a.create_button(|b| {
+                        b.style(ButtonStyle::Primary)
+                            .label("Subscribe")
+                            .custom_id(format!("bug_subscribe__{bug_id}"))
+                    })
+                })
+            })
+        })
+        .await?;
+
+    interaction
+        .create_interaction_response(ctx, |r| {
+            r.kind(InteractionResponseType::ChannelMessageWithSource)
+                .interaction_response_data(|d| {
+                    d.flags(InteractionApplicationCommandCallbackDataFlags::EPHEMERAL)
+                        .content(format!(
+                            "Thank you! Your bug report is being tracked as {}: {}",
+                            tracker.display_id(bug_id),
+                            bug_message_link(tracker.guild_id, channel_id, report_message.id)
+                        ))
+                })
+        })
+        .await?;
+
+    subscribe_reporter(ctx, bug_id, interaction.user.id).await
+}
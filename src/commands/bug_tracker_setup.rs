@@ -0,0 +1,731 @@
+use serenity::framework::standard::{macros::command, Args, CommandResult};
+use serenity::futures::future::join3;
+use serenity::model::prelude::*;
+use serenity::prelude::*;
+
+use crate::api::issues::GitHubTracker;
+use crate::check::*;
+use crate::commands::bug_reports::create_bug_application_command;
+use crate::database::bug_reports::{normalize_status, BugCategory, BugStatus, MAX_STATUS_LENGTH};
+use crate::database::bug_trackers::{
+    add_bug_moderator, add_bug_statuses, count_bugs_with_status, get_bug_moderators,
+    get_bug_tracker, get_issue_sync, is_bug_moderator, is_valid_prefix, remove_bug_moderator,
+    remove_bug_status, remove_bug_tracker, remove_issue_sync, set_bug_status, set_bug_tracker,
+    set_issue_sync, BugTracker, DEFAULT_BUG_PREFIX, MAX_PREFIX_LENGTH,
+};
+
+/// Maximum length of the emoji or text displayed before the bugs with a status
+const MAX_MARKER_LENGTH: usize = 64;
+
+/// Registers the `/bug` slash command of a tracker, or updates its choices
+async fn register_slash_command(ctx: &Context, tracker: &BugTracker) {
+    if let Err(e) = tracker
+        .guild_id
+        .create_application_command(ctx, |c| create_bug_application_command(c, tracker))
+        .await
+    {
+        println!(
+            "=== ERROR ===
+Could not register the bug slash command in guild {}
+Error: {e}
+=== END ===",
+            tracker.guild_id
+        );
+    }
+}
+
+#[command]
+#[checks(is_admin)]
+#[only_in(guilds)]
+#[sub_commands(
+    bug_tracker_enable,
+    bug_tracker_prefix,
+    bug_tracker_channel,
+    bug_tracker_categories,
+    bug_tracker_status,
+    bug_tracker_needinfo,
+    bug_tracker_mirror,
+    bug_tracker_moderator,
+    bug_tracker_disable
+)]
+#[aliases("tracker")]
+pub async fn bug_tracker(ctx: &Context, msg: &Message) -> CommandResult {
+    let guild_id = msg.guild_id.expect("Should be only used in guilds");
+
+    let (tracker, moderators, issue_sync) = join3(
+        get_bug_tracker(ctx, guild_id),
+        get_bug_moderators(ctx, guild_id),
+        get_issue_sync(ctx, guild_id),
+    )
+    .await;
+
+    let tracker = match tracker {
+        Some(tracker) => tracker,
+        None => {
+            msg.reply(
+                ctx,
+                "There is no bug tracker on this server. Enable it with `!bug tracker enable [prefix]`.",
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.title("Current bug tracker setup");
+                e.colour(serenity::utils::Colour::TEAL);
+                e.author(|a| {
+                    a.name("Bugtracker")
+                        .icon_url(crate::constants::TERMITE_IMAGE)
+                });
+                e.fields([
+                    (
+                        "Bug id prefix",
+                        format!("`{}`, e.g. `{}`", tracker.prefix, tracker.display_id(42)),
+                        false,
+                    ),
+                    (
+                        "Announcement channel",
+                        if let Some(channel) = tracker.channel_id {
+                            channel.mention().to_string()
+                        } else {
+                            "None, set it up with `!bug tracker channel <channel mention>`."
+                                .to_string()
+                        },
+                        false,
+                    ),
+                    (
+                        "Categories",
+                        tracker
+                            .categories
+                            .iter()
+                            .enumerate()
+                            .map(|(i, category)| {
+                                if i == 0 {
+                                    format!("`{}` (default)", category.as_str())
+                                } else {
+                                    format!("`{}`", category.as_str())
+                                }
+                            })
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                        false,
+                    ),
+                    ("Statuses", format_statuses(&tracker), false),
+                    (
+                        "Bugs needing more information",
+                        format!(
+                            "Reporters are reminded {}, and bugs are closed {}.",
+                            format_delay(tracker.info_reminder_days),
+                            format_delay(tracker.info_close_days)
+                        ),
+                        false,
+                    ),
+                    (
+                        "Issue mirroring",
+                        match issue_sync {
+                            Some(issue_sync) => format!(
+                                "Bugs tracked with `!track mirror` are mirrored in `{}`{}.",
+                                issue_sync.repository,
+                                issue_sync
+                                    .api_url
+                                    .map(|url| format!(" on <{}>", url))
+                                    .unwrap_or_default()
+                            ),
+                            None => "None, set it up with `!bug tracker mirror <owner/repo>`."
+                                .to_string(),
+                        },
+                        false,
+                    ),
+                    (
+                        "Bug tracker moderators",
+                        match moderators {
+                            Some(mods) if !mods.is_empty() => mods
+                                .iter()
+                                .map(|user_id| user_id.mention().to_string())
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                            _ => {
+                                "None, add moderators with `!bug tracker moderator <user mention>`."
+                                    .to_string()
+                            }
+                        },
+                        false,
+                    ),
+                ]);
+                e
+            })
+        })
+        .await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(is_admin)]
+#[only_in(guilds)]
+#[aliases("enable")]
+pub async fn bug_tracker_enable(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.expect("Should be only used in guilds");
+
+    if let Some(tracker) = get_bug_tracker(ctx, guild_id).await {
+        crate::failure!(
+            ctx,
+            msg,
+            "The bug tracker is already enabled on this server, with the prefix `{}`.",
+            tracker.prefix
+        );
+        return Ok(());
+    }
+
+    let prefix = match args.rest().trim() {
+        "" => DEFAULT_BUG_PREFIX,
+        prefix => prefix,
+    };
+    if !is_valid_prefix(prefix) {
+        crate::failure!(
+            ctx,
+            msg,
+            "The prefix must only contain letters and digits, and be at most {} characters long.",
+            MAX_PREFIX_LENGTH
+        );
+        return Ok(());
+    }
+
+    let mut tracker = BugTracker::new(guild_id, prefix.to_string());
+    set_bug_tracker(ctx, &tracker).await?;
+    // the statuses of a tracker that was disabled are kept
+    add_bug_statuses(ctx, guild_id, &tracker.statuses).await?;
+    if let Some(existing) = get_bug_tracker(ctx, guild_id).await {
+        tracker = existing;
+    }
+    register_slash_command(ctx, &tracker).await;
+
+    crate::success!(
+        ctx,
+        msg,
+        "Successfully enabled the bug tracker on this server! Bugs will be tracked as `{}`.",
+        tracker.display_id(1)
+    );
+
+    Ok(())
+}
+
+#[command]
+#[checks(is_admin, has_bugtracker)]
+#[only_in(guilds)]
+#[aliases("prefix")]
+pub async fn bug_tracker_prefix(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.expect("Should be only used in guilds");
+    let mut tracker = get_bug_tracker(ctx, guild_id)
+        .await
+        .ok_or("The bug tracker was removed")?;
+
+    let prefix = args.rest().trim();
+    if !is_valid_prefix(prefix) {
+        crate::failure!(
+            ctx,
+            msg,
+            "The prefix must only contain letters and digits, and be at most {} characters long.",
+            MAX_PREFIX_LENGTH
+        );
+        return Ok(());
+    }
+
+    tracker.prefix = prefix.to_string();
+    set_bug_tracker(ctx, &tracker).await?;
+    register_slash_command(ctx, &tracker).await;
+
+    crate::success!(
+        ctx,
+        msg,
+        "Bugs will now be tracked as `{}`.",
+        tracker.display_id(1)
+    );
+
+    Ok(())
+}
+
+#[command]
+#[checks(is_admin, has_bugtracker)]
+#[only_in(guilds)]
+#[aliases("channel")]
+pub async fn bug_tracker_channel(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.expect("Should be only used in guilds");
+    let mut tracker = get_bug_tracker(ctx, guild_id)
+        .await
+        .ok_or("The bug tracker was removed")?;
+
+    if args.rest().eq_ignore_ascii_case("none") {
+        tracker.channel_id = None;
+        set_bug_tracker(ctx, &tracker).await?;
+        crate::success!(ctx, msg, "New bugs will no longer be announced.");
+    } else if let Ok(channel_id) = args.parse::<ChannelId>() {
+        tracker.channel_id = Some(channel_id);
+        set_bug_tracker(ctx, &tracker).await?;
+        crate::success!(ctx, msg, "New bugs will be announced in <#{}>", channel_id);
+    } else {
+        crate::failure!(
+            ctx,
+            msg,
+            "The first argument must be a channel mention, or `none`!"
+        );
+    }
+    Ok(())
+}
+
+#[command]
+#[checks(is_admin, has_bugtracker)]
+#[only_in(guilds)]
+#[aliases("categories")]
+pub async fn bug_tracker_categories(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.expect("Should be only used in guilds");
+    let mut tracker = get_bug_tracker(ctx, guild_id)
+        .await
+        .ok_or("The bug tracker was removed")?;
+
+    let mut categories = Vec::new();
+    for arg in args.iter::<String>().flatten() {
+        match arg.parse::<BugCategory>() {
+            Ok(category) if !categories.contains(&category) => categories.push(category),
+            Ok(_) => (),
+            Err(_) => {
+                crate::failure!(
+                    ctx,
+                    msg,
+                    "`{}` is not a bug category! Available categories are {}",
+                    arg,
+                    BugCategory::ALL
+                        .iter()
+                        .map(|category| format!("`{}`", category.as_str()))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+                return Ok(());
+            }
+        }
+    }
+    if categories.is_empty() {
+        crate::failure!(
+            ctx,
+            msg,
+            "You must list the categories of the bug tracker, the first one being the default."
+        );
+        return Ok(());
+    }
+
+    tracker.categories = categories;
+    set_bug_tracker(ctx, &tracker).await?;
+    register_slash_command(ctx, &tracker).await;
+
+    crate::success!(
+        ctx,
+        msg,
+        "Successfully changed the bug categories. New bugs will be `{}` by default.",
+        tracker.default_category().as_str()
+    );
+
+    Ok(())
+}
+
+/// Formats a delay of the bugs needing more information, 0 disabling it
+fn format_delay(days: u32) -> String {
+    match days {
+        0 => "never".to_string(),
+        1 => "after 1 day without activity".to_string(),
+        days => format!("after {} days without activity", days),
+    }
+}
+
+/// Lists the statuses of a tracker, from the highest weight
+fn format_statuses(tracker: &BugTracker) -> String {
+    tracker
+        .statuses
+        .iter()
+        .map(|status| {
+            format!(
+                "{} `{}`: {}, weight {}, colour `#{:06x}`",
+                status.marker,
+                status,
+                if status.open { "open" } else { "closed" },
+                status.weight,
+                status.colour.0
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[command]
+#[checks(is_admin, has_bugtracker)]
+#[only_in(guilds)]
+#[sub_commands(bug_tracker_status_set, bug_tracker_status_remove)]
+#[aliases("status", "statuses")]
+pub async fn bug_tracker_status(ctx: &Context, msg: &Message) -> CommandResult {
+    let guild_id = msg.guild_id.expect("Should be only used in guilds");
+    let tracker = get_bug_tracker(ctx, guild_id)
+        .await
+        .ok_or("The bug tracker was removed")?;
+
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.title("Bug statuses");
+                e.colour(serenity::utils::Colour::TEAL);
+                e.description(format_statuses(&tracker));
+                e.footer(|f| {
+                    f.text(
+                        "Add or edit a status with !bug tracker status set <name> <open|closed> \
+<weight> <#colour> <marker>, and remove an unused one with !bug tracker status remove <name>",
+                    )
+                })
+            })
+        })
+        .await?;
+
+    Ok(())
+}
+
+#[command]
+#[checks(is_admin, has_bugtracker)]
+#[only_in(guilds)]
+#[aliases("set", "add")]
+pub async fn bug_tracker_status_set(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.expect("Should be only used in guilds");
+    let mut tracker = get_bug_tracker(ctx, guild_id)
+        .await
+        .ok_or("The bug tracker was removed")?;
+
+    let name = match args
+        .single::<String>()
+        .ok()
+        .and_then(|s| normalize_status(&s))
+    {
+        Some(name) => name,
+        None => {
+            crate::failure!(
+                ctx,
+                msg,
+                "Status names must only contain letters, digits, `-` and `_`, \
+and be at most {} characters long.",
+                MAX_STATUS_LENGTH
+            );
+            return Ok(());
+        }
+    };
+    let open = match args.single::<String>().ok().as_deref() {
+        Some("open") => true,
+        Some("closed") => false,
+        _ => {
+            crate::failure!(ctx, msg, "The second argument must be `open` or `closed`!");
+            return Ok(());
+        }
+    };
+    let weight = match args.single::<i32>() {
+        Ok(weight) => weight,
+        Err(_) => {
+            crate::failure!(
+                ctx,
+                msg,
+                "The third argument must be the weight of the status: bugs with a higher weight \
+are displayed first."
+            );
+            return Ok(());
+        }
+    };
+    let colour = match args
+        .single::<String>()
+        .ok()
+        .and_then(|s| u32::from_str_radix(s.trim_start_matches('#'), 16).ok())
+        .filter(|&colour| colour <= 0xffffff)
+    {
+        Some(colour) => colour,
+        None => {
+            crate::failure!(
+                ctx,
+                msg,
+                "The fourth argument must be a hexadecimal colour, like `#fd9a01`!"
+            );
+            return Ok(());
+        }
+    };
+    let marker = args.rest().trim();
+    if marker.is_empty() || marker.len() > MAX_MARKER_LENGTH {
+        crate::failure!(
+            ctx,
+            msg,
+            "You must give the emoji displayed before the bugs with this status!"
+        );
+        return Ok(());
+    }
+
+    let status = BugStatus::new(&name, colour, marker, weight, open);
+    if status.is_reserved() && open {
+        crate::failure!(ctx, msg, "The `{}` status cannot be open!", status);
+        return Ok(());
+    }
+    if !open && tracker.open_statuses().all(|s| s == &status) {
+        crate::failure!(
+            ctx,
+            msg,
+            "The bug tracker must have at least one open status!"
+        );
+        return Ok(());
+    }
+
+    set_bug_status(ctx, guild_id, &status).await?;
+    let existed = tracker.status(&name).is_some();
+    tracker.statuses.retain(|s| s != &status);
+    tracker.statuses.push(status);
+    tracker.statuses.sort_by(|a, b| b.weight.cmp(&a.weight));
+    register_slash_command(ctx, &tracker).await;
+
+    crate::success!(
+        ctx,
+        msg,
+        "Successfully {} the status `{}`.",
+        if existed { "updated" } else { "added" },
+        name
+    );
+
+    Ok(())
+}
+
+#[command]
+#[checks(is_admin, has_bugtracker)]
+#[only_in(guilds)]
+#[aliases("remove", "delete")]
+pub async fn bug_tracker_status_remove(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.expect("Should be only used in guilds");
+    let mut tracker = get_bug_tracker(ctx, guild_id)
+        .await
+        .ok_or("The bug tracker was removed")?;
+
+    let status = match tracker.parse_status(args.rest()) {
+        Some(status) => status,
+        None => {
+            crate::failure!(ctx, msg, "The bug tracker has no such status!");
+            return Ok(());
+        }
+    };
+    if status.is_reserved() {
+        crate::failure!(ctx, msg, "The `{}` status cannot be removed!", status);
+        return Ok(());
+    }
+    if status.open && tracker.open_statuses().count() == 1 {
+        crate::failure!(
+            ctx,
+            msg,
+            "The bug tracker must have at least one open status!"
+        );
+        return Ok(());
+    }
+    let in_use = count_bugs_with_status(ctx, guild_id, &status.name).await?;
+    if in_use != 0 {
+        crate::failure!(
+            ctx,
+            msg,
+            "{} bugs still have the `{}` status. Change their status before removing it.",
+            in_use,
+            status
+        );
+        return Ok(());
+    }
+
+    remove_bug_status(ctx, guild_id, &status.name).await?;
+    tracker.statuses.retain(|s| s != &status);
+    register_slash_command(ctx, &tracker).await;
+
+    crate::success!(ctx, msg, "Successfully removed the status `{}`.", status);
+
+    Ok(())
+}
+
+#[command]
+#[checks(is_admin, has_bugtracker)]
+#[only_in(guilds)]
+#[aliases("needinfo")]
+pub async fn bug_tracker_needinfo(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.expect("Should be only used in guilds");
+    let mut tracker = get_bug_tracker(ctx, guild_id)
+        .await
+        .ok_or("The bug tracker was removed")?;
+
+    let (reminder_days, close_days) = match (args.single::<u32>(), args.single::<u32>()) {
+        (Ok(reminder_days), Ok(close_days)) => (reminder_days, close_days),
+        _ => {
+            crate::failure!(
+                ctx,
+                msg,
+                "You must give the number of days without activity before reminding the \
+reporter of a bug that needs more information, and before closing it. Use 0 to disable them."
+            );
+            return Ok(());
+        }
+    };
+    if close_days != 0 && reminder_days >= close_days {
+        crate::failure!(
+            ctx,
+            msg,
+            "The reporter must be reminded before the bug is closed!"
+        );
+        return Ok(());
+    }
+
+    tracker.info_reminder_days = reminder_days;
+    tracker.info_close_days = close_days;
+    set_bug_tracker(ctx, &tracker).await?;
+
+    crate::success!(
+        ctx,
+        msg,
+        "Reporters of bugs needing more information will be reminded {}, and the bugs \
+will be closed {}.",
+        format_delay(reminder_days),
+        format_delay(close_days)
+    );
+
+    Ok(())
+}
+
+/// Checks that a repository path looks like `owner/repo`
+fn is_valid_repository(repository: &str) -> bool {
+    let is_valid_part = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+    repository.split_once('/').map_or(false, |(owner, repo)| {
+        is_valid_part(owner) && is_valid_part(repo)
+    })
+}
+
+#[command]
+#[owners_only]
+#[checks(has_bugtracker)]
+#[only_in(guilds)]
+#[aliases("mirror")]
+pub async fn bug_tracker_mirror(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.expect("Should be only used in guilds");
+
+    let repository = match args.single::<String>() {
+        Ok(repository) => repository,
+        Err(_) => {
+            crate::failure!(
+                ctx,
+                msg,
+                "You must give the repository to mirror the bugs in, like `owner/repo`, or `none`."
+            );
+            return Ok(());
+        }
+    };
+
+    if repository.eq_ignore_ascii_case("none") {
+        if remove_issue_sync(ctx, guild_id).await? {
+            crate::success!(
+                ctx,
+                msg,
+                "Bugs are not mirrored anymore. The existing issues stay linked to their bugs."
+            );
+        } else {
+            crate::failure!(ctx, msg, "Bugs are not mirrored on this server!");
+        }
+        return Ok(());
+    }
+
+    let repository = repository.trim_matches('/');
+    if !is_valid_repository(repository) {
+        crate::failure!(
+            ctx,
+            msg,
+            "`{}` is not a valid repository, it must look like `owner/repo`.",
+            repository
+        );
+        return Ok(());
+    }
+    let api_url = args.single::<String>().ok();
+    if let Some(url) = &api_url {
+        if !url.starts_with("https://") {
+            crate::failure!(ctx, msg, "The API url must be a https url!");
+            return Ok(());
+        }
+    }
+    if let Err(e) = GitHubTracker::new(api_url.as_deref(), repository) {
+        crate::failure!(ctx, msg, "Bugs cannot be mirrored: {}", e);
+        return Ok(());
+    }
+
+    set_issue_sync(ctx, guild_id, repository, api_url.as_deref()).await?;
+
+    crate::success!(
+        ctx,
+        msg,
+        "Bugs tracked with `!track mirror` will be mirrored in `{}`, and resolved or closed \
+with their issues.",
+        repository
+    );
+
+    Ok(())
+}
+
+#[command]
+#[checks(is_admin, has_bugtracker)]
+#[only_in(guilds)]
+#[aliases("moderator")]
+pub async fn bug_tracker_moderator(ctx: &Context, msg: &Message) -> CommandResult {
+    let guild_id = msg.guild_id.expect("Should be only used in guilds");
+
+    if msg.mentions.is_empty() {
+        crate::failure!(ctx, msg, "The first argument should be a user mention!");
+    }
+
+    for user in &msg.mentions {
+        if is_bug_moderator(ctx, guild_id, user.id)
+            .await
+            .unwrap_or_default()
+        {
+            remove_bug_moderator(ctx, guild_id, user.id).await?;
+            crate::success!(
+                ctx,
+                msg,
+                "Removed {} from bug tracker moderators",
+                user.name
+            );
+        } else {
+            add_bug_moderator(ctx, guild_id, user.id).await?;
+            crate::success!(ctx, msg, "Added {} to bug tracker moderators", user.name);
+        }
+    }
+    Ok(())
+}
+
+#[command]
+#[checks(is_admin)]
+#[only_in(guilds)]
+#[aliases("disable")]
+pub async fn bug_tracker_disable(ctx: &Context, msg: &Message) -> CommandResult {
+    let guild_id = msg.guild_id.expect("Should be only used in guilds");
+
+    if !remove_bug_tracker(ctx, guild_id).await? {
+        crate::failure!(ctx, msg, "There is no bug tracker on this server!");
+        return Ok(());
+    }
+
+    if let Ok(commands) = guild_id.get_application_commands(ctx).await {
+        for command in commands.iter().filter(|c| c.name == "bug") {
+            guild_id.delete_application_command(ctx, command.id).await?;
+        }
+    }
+
+    crate::success!(
+        ctx,
+        msg,
+        "Successfully disabled the bug tracker on this server. Its bug reports are kept, \
+and will be available again if you enable it again."
+    );
+
+    Ok(())
+}
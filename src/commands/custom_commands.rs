@@ -1,22 +1,36 @@
 use serde_json::Value;
+use serenity::builder::{CreateApplicationCommand, CreateApplicationCommandOption};
 use serenity::client::Context;
 use serenity::framework::standard::CommandError;
-use serenity::framework::standard::{macros::command, Args, CommandResult};
+use serenity::framework::standard::{macros::command, Args, CommandResult, Delimiter};
 use serenity::framework::Framework;
 use serenity::futures::future::join;
 use serenity::model::channel::Message;
+use serenity::model::id::{ChannelId, GuildId, UserId};
+use serenity::model::interactions::{
+    application_command::{
+        ApplicationCommandInteraction, ApplicationCommandInteractionDataOption,
+        ApplicationCommandOptionType,
+    },
+    InteractionApplicationCommandCallbackDataFlags, InteractionResponseType,
+};
+use serenity::model::user::User;
 use serenity::prelude::Mentionable;
 
-use crate::announcement::{announce, Announcement};
+use crate::announcement::{announce_with_mentions, AllowedMentions, Announcement};
+use crate::command_arguments::{ArgumentSchema, ArgumentType, ArgumentValue};
 use crate::constants::{MANAGE_BOT_PERMS, OWNER_ID, RESERVED_NAMES};
 use crate::database::{
     blacklist::check_blacklist,
+    config::get_prefix,
     custom_commands::{
-        add_custom_command, check_command_exists, get_command_data, get_custom_commands_list,
-        remove_custom_command,
+        add_custom_command, check_command_exists, get_command_data, get_command_revision,
+        get_command_revisions, get_command_template, get_custom_commands_list,
+        remove_custom_command, set_command_template,
     },
 };
-use crate::utils::{get_json_from_message, has_permission, to_json_safe_string, NotInGuild};
+use crate::template::{JsonTemplate, TemplateContext};
+use crate::utils::{get_json_from_message, has_permission, line_diff, NotInGuild};
 use crate::{check::*, FrameworkKey};
 use crate::{failure, handle_json_error, is_admin, success};
 
@@ -80,7 +94,14 @@ pub async fn manual_dispatch(
 
 #[command]
 #[aliases("command")]
-#[sub_commands(define, custom_command_remove, custom_command_display)]
+#[sub_commands(
+    define,
+    custom_command_remove,
+    custom_command_display,
+    custom_command_history,
+    custom_command_diff,
+    custom_command_rollback
+)]
 pub async fn custom_command(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
     let server_id = if let Some(id) = msg.guild_id {
         id
@@ -95,12 +116,11 @@ pub async fn custom_command(ctx: &Context, msg: &Message, mut args: Args) -> Com
     if let Some(command_data) = get_command_data(ctx, server_id, &name, false).await {
         println!("Custom command execution: {}", msg.content);
 
-        let mut message: Announcement =
-            serde_json::from_str(&command_data.body.replace("\\$", "$"))?;
-        let mut delete = message.extra["self_delete"].as_bool().unwrap_or_default();
+        let command: Value = serde_json::from_str(&command_data.body)?;
+        let mut delete = command["self_delete"].as_bool().unwrap_or_default();
 
-        let default_command_type = message.extra["type"].as_str();
-        let subcommands_object = &message.extra["subcommands"];
+        let default_command_type = command["type"].as_str();
+        let subcommands_object = &command["subcommands"];
         // early interrupt in case of blacklist / admin command
         let command_type = if let Some(subcommand) = subcommand {
             // optionnally overriding the command type
@@ -125,10 +145,7 @@ pub async fn custom_command(ctx: &Context, msg: &Message, mut args: Args) -> Com
             if s == "group" {
                 return Ok(());
             }
-            let is_admin = msg.author.id == OWNER_ID
-                || is_admin!(ctx, msg)
-                || has_permission(ctx, server_id, msg.author.id, MANAGE_BOT_PERMS).await;
-            if !is_admin {
+            if !is_custom_command_admin(ctx, server_id, msg.author.id).await {
                 if s == "meme"
                     && check_blacklist(ctx, server_id, msg.author.id, msg.channel_id)
                         .await
@@ -167,86 +184,82 @@ Channel: {:?}\nMessage: {}\n=== END ===",
             }
         }
 
-        let mut command_body = command_data.body;
+        let mut selected_subcommand = None;
         if let Some(subcommand) = subcommand {
             if subcommands_object[subcommand].is_object() {
-                command_body = serde_json::to_string(&subcommands_object[subcommand])?;
-                message = serde_json::from_str(&command_body)?;
-                args.advance();
+                selected_subcommand = Some(subcommand.to_string());
             } else if let Some(subcommand_alias) = subcommands_object[subcommand].as_str() {
                 if subcommands_object[subcommand_alias].is_object() {
-                    command_body = serde_json::to_string(&subcommands_object[subcommand_alias])?;
-                    message = serde_json::from_str(&command_body)?;
-                    args.advance();
+                    selected_subcommand = Some(subcommand_alias.to_string());
                 }
             }
         }
+        if selected_subcommand.is_some() {
+            args.advance();
+        }
 
-        if command_body.contains('$') {
-            let mut changed = false;
-
-            let mut b = command_body
-                .replace('$', "\u{200B}$")
-                .replace("\\\u{200B}$", "\\$");
-
-            if b.contains("\u{200B}$me")
-                || b.contains("\u{200B}$ping")
-                || b.contains("\u{200B}$channel")
-            {
-                changed = true;
-                b = b
-                    .replace("\u{200B}$me", &to_json_safe_string(&msg.author.name))
-                    .replace("\u{200B}$ping", &msg.author.mention().to_string())
-                    .replace("\u{200B}$channel", &msg.channel_id.mention().to_string());
-            }
-
-            if b.contains("\u{200B}$args") {
-                changed = true;
-                b = b.replace("\u{200B}$args", &to_json_safe_string(&args.rest()));
-            } else {
-                args.iter::<String>()
-                    .filter_map(Result::ok)
-                    .enumerate()
-                    .for_each(|(i, arg)| {
-                        let key = format!("\u{200B}${}", i);
-                        if b.contains(&key) {
-                            changed = true;
-                            b = b.replace(
-                                key.as_str(),
-                                &to_json_safe_string(
-                                    arg.replace('$', "\\$")
-                                        .replace('@', "@\u{200B}")
-                                        .trim_matches('"'),
-                                ),
-                            );
-                        }
-                    });
-            }
+        let selected_body = match &selected_subcommand {
+            Some(key) => &subcommands_object[key.as_str()],
+            None => &command,
+        };
 
-            let argc = args.len() - 1;
-            if changed {
-                message = serde_json::from_str(&b.replace("\\$", "$"))?;
-            }
-            changed = false;
-            if let Value::Array(a) = &message.extra["default_args"] {
-                for (i, arg) in a[argc.min(a.len())..]
-                    .iter()
-                    .filter_map(Value::as_str)
-                    .enumerate()
-                {
-                    changed = true;
-                    println!("Default argument '{}'", arg);
-                    b = b.replace(
-                        format!("\u{200B}${}", i + argc).as_str(),
-                        &arg.replace('$', "\\$"),
+        let arguments = args
+            .iter::<String>()
+            .filter_map(Result::ok)
+            .map(|arg| arg.trim_matches('"').to_string())
+            .collect::<Vec<_>>();
+        let mut context = template_context(
+            ctx,
+            &msg.author,
+            msg.channel_id,
+            msg.guild_id,
+            &arguments.iter().cloned().map(Some).collect::<Vec<_>>(),
+            &selected_body["default_args"],
+        )
+        .await;
+        let mut mentions = command_mentions(&command_data.body, msg.author.id);
+        if let Some(schema) = ArgumentSchema::from_command(selected_body)? {
+            match schema.parse(&arguments) {
+                Ok(values) => add_argument_variables(&schema, values, &mut context, &mut mentions),
+                Err(e) => {
+                    let prefix = get_prefix(ctx, server_id)
+                        .await
+                        .unwrap_or_else(|| "!".into());
+                    let command_name = match &selected_subcommand {
+                        Some(key) => format!("{} {}", name, key),
+                        None => name,
+                    };
+                    failure!(
+                        ctx,
+                        msg,
+                        "{}\nUsage: `{}{} {}`",
+                        e,
+                        prefix,
+                        command_name,
+                        schema.usage()
                     );
+                    return Ok(());
                 }
             }
-            if changed {
-                message = serde_json::from_str(&b.replace("\\$", "$"))?;
-            }
         }
 
+        let message = match render_custom_command(
+            ctx,
+            server_id,
+            &name,
+            &command,
+            selected_subcommand.as_deref(),
+            context,
+        )
+        .await
+        {
+            Ok(message) => message,
+            Err(e) => {
+                failure!(ctx, msg, "This custom command could not be rendered: {}", e);
+                return Ok(());
+            }
+        };
+
         if let Some(b) = message.extra["self_delete"].as_bool() {
             // optionally overriding the self delete behavior
             delete = b;
@@ -258,7 +271,7 @@ Channel: {:?}\nMessage: {}\n=== END ===",
                 return Ok(());
             }
         }
-        announce(ctx, msg.channel_id, &message).await?;
+        announce_with_mentions(ctx, msg.channel_id, &message, &mentions).await?;
         if delete {
             msg.delete(ctx).await?;
         }
@@ -273,6 +286,129 @@ Channel: {:?}\nMessage: {}\n=== END ===",
     Ok(())
 }
 
+/// Prevents the arguments of a custom command from pinging anyone
+fn neutralize_mentions(arg: &str) -> String {
+    arg.replace('@', "@\u{200B}")
+}
+
+/// Whether a user can use the admin custom commands, and is exempt from the blacklist
+async fn is_custom_command_admin(ctx: &Context, server_id: GuildId, user_id: UserId) -> bool {
+    user_id == OWNER_ID
+        || is_admin!(ctx, server_id, user_id)
+        || has_permission(ctx, server_id, user_id, MANAGE_BOT_PERMS).await
+}
+
+/// Renders the body of a custom command, or of one of its subcommands
+async fn render_custom_command(
+    ctx: &Context,
+    server_id: GuildId,
+    name: &str,
+    command: &Value,
+    subcommand: Option<&str>,
+    mut context: TemplateContext,
+) -> CommandResult<Announcement> {
+    let template = get_command_template(ctx, server_id, name, command).await;
+    let selected_template = match subcommand {
+        Some(key) => template
+            .get("subcommands")
+            .and_then(|s| s.get(key))
+            .ok_or("Missing subcommand template")?,
+        None => template.as_ref(),
+    };
+
+    template.add_variables(&mut context);
+    if subcommand.is_some() {
+        selected_template.add_variables(&mut context);
+    }
+    Ok(serde_json::from_value(selected_template.render(&context))?)
+}
+
+/// Adds the validated arguments of a custom command to the variables of its templates.
+/// The users given as arguments are allowed to be pinged, but not the roles.
+fn add_argument_variables(
+    schema: &ArgumentSchema,
+    mut values: Vec<Option<ArgumentValue>>,
+    context: &mut TemplateContext,
+    mentions: &mut AllowedMentions,
+) {
+    values
+        .iter_mut()
+        .flatten()
+        .filter(|value| value.id.is_none())
+        .for_each(|value| value.text = neutralize_mentions(&value.text));
+    mentions.users.extend(
+        schema
+            .0
+            .iter()
+            .zip(&values)
+            .filter(|(argument, _)| argument.kind == ArgumentType::User)
+            .filter_map(|(_, value)| Some(UserId(value.as_ref()?.id?))),
+    );
+    schema.add_variables(&values, context);
+}
+
+/// Mentions that can ping in the message of a custom command: the ones written in
+/// its body, and its user
+fn command_mentions(body: &str, user_id: UserId) -> AllowedMentions {
+    let mut mentions = AllowedMentions::from_text(body);
+    mentions.users.push(user_id);
+    mentions
+}
+
+/// Variables available in the templates of a custom command. Missing arguments
+/// are taken from the `"default_args"` array.
+///
+/// The arguments are given by position, an optional slash command argument left empty
+/// does not shift the next ones.
+async fn template_context(
+    ctx: &Context,
+    user: &User,
+    channel_id: ChannelId,
+    guild_id: Option<GuildId>,
+    arguments: &[Option<String>],
+    default_args: &Value,
+) -> TemplateContext {
+    let mut context = TemplateContext::new();
+    context
+        .set("user.name", user.name.as_str())
+        .set("user.tag", user.tag())
+        .set("user.mention", user.mention().to_string())
+        .set("user.id", user.id.to_string())
+        .set("channel.mention", channel_id.mention().to_string())
+        .set("channel.id", channel_id.to_string());
+    if let Some(name) = channel_id.name(ctx).await {
+        context.set("channel.name", name);
+    }
+    if let Some(guild_id) = guild_id {
+        context.set("guild.id", guild_id.to_string());
+        if let Some(name) = guild_id.name(ctx) {
+            context.set("guild.name", name);
+        }
+    }
+
+    let arguments = arguments
+        .iter()
+        .map(|arg| arg.as_deref().map(neutralize_mentions))
+        .collect::<Vec<_>>();
+    let default_args = default_args
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    for i in 0..arguments.len().max(default_args.len()) {
+        let arg = arguments.get(i).cloned().flatten().or_else(|| {
+            default_args
+                .get(i)
+                .and_then(Value::as_str)
+                .map(String::from)
+        });
+        if let Some(arg) = arg {
+            context.set(format!("arg{}", i), arg);
+        }
+    }
+    context.set("args", arguments.into_iter().flatten().collect::<Vec<_>>());
+    context
+}
+
 #[command]
 #[checks(is_admin)]
 #[only_in(guilds)]
@@ -343,18 +479,57 @@ pub async fn define(ctx: &Context, msg: &Message, mut args: Args) -> CommandResu
                     s
                 ));
             }
+            // validate the arguments declared by the command and its subcommands
+            let subcommands = message["subcommands"].as_object().into_iter().flatten();
+            for (key, body) in std::iter::once((&name, &message)).chain(subcommands) {
+                if let Err(e) = ArgumentSchema::from_command(body) {
+                    failure!(ctx, msg, "Invalid arguments of `{}`: {}", key, e);
+                    return Ok(());
+                }
+            }
+            if is_slash_command(&message) {
+                if let Err(e) = check_slash_command(&name, &message) {
+                    failure!(ctx, msg, "{}", e);
+                    return Ok(());
+                }
+            }
+            let template = match JsonTemplate::parse(&message) {
+                Ok(template) => template,
+                Err(e) => {
+                    failure!(ctx, msg, "Template syntax error {}", e);
+                    return Ok(());
+                }
+            };
             let body = serde_json::to_string_pretty(&message)?;
+            let was_slash_command = is_stored_slash_command(ctx, server_id, &name).await;
             println!(
                 "adding custom command \"{}\": {}\n({:?})",
                 name, body, documentation
             );
-            let db_res =
-                add_custom_command(ctx, server_id, &name, &body, documentation.as_str()).await;
+            let db_res = add_custom_command(
+                ctx,
+                server_id,
+                &name,
+                &body,
+                documentation.as_str(),
+                msg.author.id,
+            )
+            .await;
             if db_res.is_ok()
                 && check_command_exists(ctx, server_id, &name)
                     .await
                     .unwrap_or_default()
             {
+                set_command_template(ctx, server_id, &name, template).await;
+                if let Err(e) = sync_slash_command(ctx, server_id, &name, was_slash_command).await {
+                    failure!(
+                        ctx,
+                        msg,
+                        "The command was saved, but its slash command could not be updated: {}",
+                        e
+                    );
+                    return Ok(());
+                }
                 success!(ctx, msg);
             } else {
                 println!("{:?}", db_res.err());
@@ -373,6 +548,7 @@ async fn custom_command_remove(ctx: &Context, msg: &Message, mut args: Args) ->
     let server_id = msg.guild_id.ok_or(NotInGuild)?;
 
     let name: String = args.single()?;
+    let was_slash_command = is_stored_slash_command(ctx, server_id, &name).await;
 
     if check_command_exists(ctx, server_id, &name)
         .await
@@ -382,6 +558,12 @@ async fn custom_command_remove(ctx: &Context, msg: &Message, mut args: Args) ->
             .await
             .unwrap_or_default()
     {
+        if let Err(e) = sync_slash_command(ctx, server_id, &name, was_slash_command).await {
+            println!(
+                "=== ERROR ===\nCould not remove the slash command {} in guild {}: {}\n=== END ===",
+                name, server_id, e
+            );
+        }
         success!(ctx, msg);
     } else {
         failure!(ctx, msg);
@@ -440,7 +622,7 @@ async fn custom_command_display(ctx: &Context, msg: &Message, args: Args) -> Com
                     e.title("Custom commands");
                     e.description(
                         list.iter()
-                            .map(|(name, desc)| {
+                            .map(|(name, desc, _)| {
                                 if desc.is_empty() {
                                     newline += 1;
                                 }
@@ -470,3 +652,592 @@ async fn custom_command_display(ctx: &Context, msg: &Message, args: Args) -> Com
     }
     Ok(())
 }
+
+/// Number of revisions listed by `!command history`
+const HISTORY_LENGTH: u32 = 20;
+/// Unchanged lines kept around the changes of `!command diff`
+const DIFF_CONTEXT: usize = 2;
+
+#[command]
+#[aliases("history")]
+#[checks(is_admin)]
+#[only_in(guilds)]
+async fn custom_command_history(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let server_id = msg.guild_id.ok_or(NotInGuild)?;
+
+    let name = args.single::<String>()?.to_lowercase();
+
+    let revisions = get_command_revisions(ctx, server_id, &name, HISTORY_LENGTH).await?;
+    if revisions.is_empty() {
+        failure!(ctx, msg, "The custom command `{}` has no history!", name);
+        return Ok(());
+    }
+    let exists = check_command_exists(ctx, server_id, &name)
+        .await
+        .unwrap_or_default();
+
+    let prefix = get_prefix(ctx, server_id)
+        .await
+        .unwrap_or_else(|| "!".into());
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.title(format!("History of the custom command: {}", name));
+                e.description(
+                    revisions
+                        .iter()
+                        .enumerate()
+                        .map(|(i, revision)| {
+                            format!(
+                                "`#{}` {} by {}{}",
+                                revision.revision,
+                                revision.timestamp.format("<t:%s:f>"),
+                                match revision.author_id.0 {
+                                    0 => "an unknown author".to_string(),
+                                    id => format!("<@{}>", id),
+                                },
+                                if i == 0 && exists { " (current)" } else { "" }
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                );
+                e.footer(|f| {
+                    f.text(format!(
+                        "{0}command diff {1} <rev1> <rev2>  |  {0}command rollback {1} <rev>",
+                        prefix, name
+                    ))
+                })
+            })
+        })
+        .await?;
+    Ok(())
+}
+
+#[command]
+#[aliases("diff")]
+#[checks(is_admin)]
+#[only_in(guilds)]
+async fn custom_command_diff(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let server_id = msg.guild_id.ok_or(NotInGuild)?;
+
+    let name = args.single::<String>()?.to_lowercase();
+    let old_revision = args.single::<String>()?;
+    let new_revision = args.single::<String>()?;
+    let (old_revision, new_revision) = match (
+        old_revision.trim_start_matches('#').parse::<u32>(),
+        new_revision.trim_start_matches('#').parse::<u32>(),
+    ) {
+        (Ok(old_revision), Ok(new_revision)) => (old_revision, new_revision),
+        _ => {
+            failure!(ctx, msg, "The revisions must be numbers!");
+            return Ok(());
+        }
+    };
+
+    let (old, new) = join(
+        get_command_revision(ctx, server_id, &name, old_revision),
+        get_command_revision(ctx, server_id, &name, new_revision),
+    )
+    .await;
+    let (old, new) = match (old?, new?) {
+        (Some(old), Some(new)) => (old, new),
+        (None, _) => {
+            failure!(ctx, msg, "`{}` has no revision #{}", name, old_revision);
+            return Ok(());
+        }
+        (_, None) => {
+            failure!(ctx, msg, "`{}` has no revision #{}", name, new_revision);
+            return Ok(());
+        }
+    };
+
+    let mut diff = Vec::new();
+    if old.documentation != new.documentation {
+        diff.push("# documentation".to_string());
+        diff.extend(line_diff(
+            &old.documentation,
+            &new.documentation,
+            DIFF_CONTEXT,
+        ));
+        diff.push("# command".to_string());
+    }
+    diff.extend(line_diff(&old.body, &new.body, DIFF_CONTEXT));
+    let diff = if diff.iter().all(|line| line == "...") {
+        "No differences".to_string()
+    } else {
+        diff.join("\n")
+    };
+
+    let mut file_too_big = false;
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.title(format!(
+                    "Custom command: {}, revisions #{} → #{}",
+                    name, old.revision, new.revision
+                ));
+                if diff.len() < 4000 {
+                    e.description(format!(
+                        "```diff\n{}```",
+                        diff.replace("```", "`\u{200B}``")
+                    ));
+                } else {
+                    file_too_big = true;
+                    e.description("Diff in attachment.");
+                }
+                e
+            });
+            if file_too_big {
+                m.add_file((
+                    diff.as_bytes(),
+                    format!("{}-{}-{}.diff", name, old.revision, new.revision).as_str(),
+                ));
+            }
+            m
+        })
+        .await?;
+    Ok(())
+}
+
+#[command]
+#[aliases("rollback", "revert")]
+#[checks(is_admin)]
+#[only_in(guilds)]
+async fn custom_command_rollback(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let server_id = msg.guild_id.ok_or(NotInGuild)?;
+
+    let name = args.single::<String>()?.to_lowercase();
+    let revision = match args
+        .single::<String>()?
+        .trim_start_matches('#')
+        .parse::<u32>()
+    {
+        Ok(revision) => revision,
+        Err(_) => {
+            failure!(ctx, msg, "The revision must be a number!");
+            return Ok(());
+        }
+    };
+
+    let revision = match get_command_revision(ctx, server_id, &name, revision).await? {
+        Some(revision) => revision,
+        None => {
+            failure!(ctx, msg, "`{}` has no revision #{}", name, revision);
+            return Ok(());
+        }
+    };
+
+    // the rollback is stored as a new revision, so it can be undone too
+    let was_slash_command = is_stored_slash_command(ctx, server_id, &name).await;
+    add_custom_command(
+        ctx,
+        server_id,
+        &name,
+        &revision.body,
+        Some(revision.documentation.as_str()),
+        msg.author.id,
+    )
+    .await?;
+    if let Err(e) = sync_slash_command(ctx, server_id, &name, was_slash_command).await {
+        failure!(
+            ctx,
+            msg,
+            "The command was rolled back, but its slash command could not be updated: {}",
+            e
+        );
+        return Ok(());
+    }
+
+    success!(
+        ctx,
+        msg,
+        "Rolled back `{}` to revision #{}",
+        name,
+        revision.revision
+    );
+    Ok(())
+}
+
+/// Maximum number of options, subcommands or choices of a slash command
+const MAX_SLASH_OPTIONS: usize = 25;
+/// Maximum length of the descriptions of slash commands and their options
+const MAX_SLASH_DESCRIPTION_LENGTH: usize = 100;
+
+/// Whether a custom command opted in to be registered as a guild slash command
+fn is_slash_command(command: &Value) -> bool {
+    command["slash_command"].as_bool().unwrap_or_default()
+}
+
+/// Whether the stored version of a custom command is registered as a slash command
+async fn is_stored_slash_command(ctx: &Context, server_id: GuildId, name: &str) -> bool {
+    get_command_data(ctx, server_id, name, false)
+        .await
+        .and_then(|data| serde_json::from_str::<Value>(&data.body).ok())
+        .map_or(false, |command| is_slash_command(&command))
+}
+
+fn is_valid_slash_name(name: &str) -> bool {
+    (1..=32).contains(&name.chars().count())
+        && name
+            .chars()
+            .all(|c| c == '-' || c == '_' || (c.is_alphanumeric() && !c.is_uppercase()))
+}
+
+/// First line of a text, shortened to fit in a slash command description
+fn slash_description(text: Option<&str>, fallback: &str) -> String {
+    let line = text
+        .and_then(|text| text.lines().map(str::trim).find(|line| !line.is_empty()))
+        .unwrap_or(fallback);
+    if line.chars().count() > MAX_SLASH_DESCRIPTION_LENGTH {
+        let mut line = line
+            .chars()
+            .take(MAX_SLASH_DESCRIPTION_LENGTH - 1)
+            .collect::<String>();
+        line.push('…');
+        line
+    } else {
+        line.to_string()
+    }
+}
+
+/// Subcommands of a custom command that can be run as slash subcommands, with the
+/// body they run. The aliases of subcommands run the body of the aliased subcommand.
+fn slash_subcommands(command: &Value) -> impl Iterator<Item = (&str, &Value)> {
+    let subcommands = &command["subcommands"];
+    subcommands
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(move |(key, value)| {
+            let body = value.as_str().map_or(value, |alias| &subcommands[alias]);
+            (body.is_object() && !matches!(body["type"].as_str(), Some("alias" | "group")))
+                .then(|| (key.as_str(), body))
+        })
+}
+
+/// Checks that a custom command can be registered as a slash command
+fn check_slash_command(name: &str, command: &Value) -> Result<(), String> {
+    if !is_valid_slash_name(name) {
+        return Err(format!(
+            "`{}` is not a valid slash command name: use at most 32 lowercase letters, digits, \
+`-` and `_`",
+            name
+        ));
+    }
+    if matches!(command["type"].as_str(), Some("alias" | "group")) {
+        return Err("Alias and group commands cannot be slash commands.".into());
+    }
+
+    let subcommands = slash_subcommands(command).collect::<Vec<_>>();
+    if subcommands.len() > MAX_SLASH_OPTIONS {
+        return Err(format!(
+            "Slash commands can have at most {} subcommands.",
+            MAX_SLASH_OPTIONS
+        ));
+    }
+    if let Some((key, _)) = subcommands
+        .iter()
+        .find(|(key, _)| !is_valid_slash_name(key))
+    {
+        return Err(format!(
+            "The subcommand `{}` is not a valid slash subcommand name",
+            key
+        ));
+    }
+    for (key, body) in std::iter::once((name, command)).chain(subcommands) {
+        if let Some(schema) = ArgumentSchema::from_command(body).ok().flatten() {
+            if schema.0.len() > MAX_SLASH_OPTIONS {
+                return Err(format!(
+                    "`{}` has more than {} arguments.",
+                    key, MAX_SLASH_OPTIONS
+                ));
+            }
+            if let Some(argument) = schema.0.iter().find(|a| !is_valid_slash_name(&a.name)) {
+                return Err(format!(
+                    "The argument `{}` of `{}` must be lowercase to be a slash command option.",
+                    argument.name, key
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Options of a slash command running a custom command body: its declared
+/// arguments, or a single free text option if it has none
+fn slash_options(body: &Value) -> Vec<CreateApplicationCommandOption> {
+    let schema = match ArgumentSchema::from_command(body).ok().flatten() {
+        Some(schema) => schema,
+        None => {
+            let mut option = CreateApplicationCommandOption::default();
+            option
+                .name("arguments")
+                .description("Arguments of the command")
+                .kind(ApplicationCommandOptionType::String);
+            return vec![option];
+        }
+    };
+
+    schema
+        .0
+        .iter()
+        .map(|argument| {
+            let mut option = CreateApplicationCommandOption::default();
+            option
+                .name(&argument.name)
+                .description(slash_description(
+                    argument.description.as_deref(),
+                    argument.kind.as_str(),
+                ))
+                .kind(match argument.kind {
+                    ArgumentType::User => ApplicationCommandOptionType::User,
+                    ArgumentType::Channel => ApplicationCommandOptionType::Channel,
+                    ArgumentType::Role => ApplicationCommandOptionType::Role,
+                    ArgumentType::Integer => ApplicationCommandOptionType::Integer,
+                    ArgumentType::Choice | ArgumentType::Text => {
+                        ApplicationCommandOptionType::String
+                    }
+                })
+                .required(argument.required);
+            for choice in argument.choices.iter().take(MAX_SLASH_OPTIONS) {
+                option.add_string_choice(choice, choice);
+            }
+            option
+        })
+        .collect()
+}
+
+/// Builds the guild slash command of a custom command, with a slash subcommand for
+/// each of its subcommands
+fn create_custom_application_command<'a>(
+    c: &'a mut CreateApplicationCommand,
+    name: &str,
+    command: &Value,
+    description: Option<&str>,
+) -> &'a mut CreateApplicationCommand {
+    c.name(name)
+        .description(slash_description(description, "Custom command"));
+
+    let subcommands = slash_subcommands(command).collect::<Vec<_>>();
+    if subcommands.is_empty() {
+        for option in slash_options(command) {
+            c.add_option(option);
+        }
+    } else {
+        for (key, body) in subcommands.into_iter().take(MAX_SLASH_OPTIONS) {
+            let mut subcommand = CreateApplicationCommandOption::default();
+            subcommand
+                .name(key)
+                .description(format!("{} {}", name, key))
+                .kind(ApplicationCommandOptionType::SubCommand);
+            for option in slash_options(body) {
+                subcommand.add_sub_option(option);
+            }
+            c.add_option(subcommand);
+        }
+    }
+    c
+}
+
+/// Registers a custom command as a guild slash command if it opted in with the
+/// `"slash_command"` flag, or removes its slash command if it was registered before the
+/// change
+pub async fn sync_slash_command(
+    ctx: &Context,
+    server_id: GuildId,
+    name: &str,
+    was_slash_command: bool,
+) -> CommandResult {
+    let command_data = get_command_data(ctx, server_id, name, true).await;
+    let command = command_data
+        .as_ref()
+        .map(|data| serde_json::from_str::<Value>(&data.body))
+        .transpose()?;
+
+    match command.filter(is_slash_command) {
+        Some(command) => {
+            let description = command_data.and_then(|data| data.description);
+            server_id
+                .create_application_command(ctx, |c| {
+                    create_custom_application_command(c, name, &command, description.as_deref())
+                })
+                .await?;
+        }
+        None if was_slash_command => {
+            let application_commands = server_id.get_application_commands(ctx).await?;
+            for application_command in application_commands.iter().filter(|c| c.name == name) {
+                server_id
+                    .delete_application_command(ctx, application_command.id)
+                    .await?;
+            }
+        }
+        None => {}
+    }
+    Ok(())
+}
+
+/// Text of the value of a slash command option, like the id of a user option
+fn option_text(option: &ApplicationCommandInteractionDataOption) -> Option<String> {
+    match option.value.as_ref()? {
+        Value::String(text) => Some(text.clone()),
+        value => Some(value.to_string()),
+    }
+}
+
+/// Runs a custom command registered as a guild slash command, posting its message
+/// like the prefix command does.
+///
+/// The interaction is acknowledged first, as rendering and posting the message may take
+/// longer than the 3 seconds given to respond, and the outcome is then shown to the user.
+pub async fn custom_application_command(
+    ctx: &Context,
+    interaction: &ApplicationCommandInteraction,
+) -> CommandResult {
+    let server_id = match interaction.guild_id {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+    interaction
+        .create_interaction_response(ctx, |r| {
+            r.kind(InteractionResponseType::DeferredChannelMessageWithSource)
+                .interaction_response_data(|d| {
+                    d.flags(InteractionApplicationCommandCallbackDataFlags::EPHEMERAL)
+                })
+        })
+        .await?;
+
+    let (reply, result) = match run_custom_application_command(ctx, interaction, server_id).await {
+        Ok(reply) => (reply, Ok(())),
+        Err(e) => (
+            format!("This custom command could not be used: {}", e),
+            Err(e),
+        ),
+    };
+    interaction
+        .edit_original_interaction_response(ctx, |r| r.content(reply))
+        .await?;
+    result
+}
+
+/// Runs a custom slash command whose interaction was acknowledged, returning the
+/// reply to show to the user
+async fn run_custom_application_command(
+    ctx: &Context,
+    interaction: &ApplicationCommandInteraction,
+    server_id: GuildId,
+) -> Result<String, CommandError> {
+    let name = interaction.data.name.as_str();
+
+    let command_data = match get_command_data(ctx, server_id, name, false).await {
+        Some(command_data) => command_data,
+        None => return Ok("This custom command does not exist anymore!".into()),
+    };
+    let command: Value = serde_json::from_str(&command_data.body)?;
+    println!(
+        "Custom slash command execution: {} by {}",
+        name,
+        interaction.user.tag()
+    );
+
+    let mut options = interaction.data.options.as_slice();
+    let mut selected_subcommand = None;
+    if let Some(subcommand) = options
+        .first()
+        .filter(|option| option.kind == ApplicationCommandOptionType::SubCommand)
+    {
+        let key = command["subcommands"][&subcommand.name]
+            .as_str()
+            .unwrap_or(subcommand.name.as_str());
+        selected_subcommand = Some(key.to_string());
+        options = &subcommand.options;
+    }
+    let selected_body = match &selected_subcommand {
+        Some(key) => &command["subcommands"][key.as_str()],
+        None => &command,
+    };
+    if !selected_body.is_object() {
+        return Ok("This subcommand does not exist anymore!".into());
+    }
+
+    let command_type = selected_body["type"]
+        .as_str()
+        .or_else(|| command["type"].as_str());
+    if matches!(command_type, Some("alias" | "group")) {
+        return Ok("This command cannot be used as a slash command.".into());
+    }
+    if matches!(command_type, Some("meme" | "admin"))
+        && !is_custom_command_admin(ctx, server_id, interaction.user.id).await
+    {
+        if command_type == Some("admin") {
+            return Ok("You are not an admin on this server!".into());
+        }
+        if check_blacklist(ctx, server_id, interaction.user.id, interaction.channel_id)
+            .await
+            .unwrap_or(true)
+        {
+            return Ok("You are not allowed to use this command here.".into());
+        }
+    }
+
+    let option_value = |name: &str| {
+        options
+            .iter()
+            .find(|option| option.name == name)
+            .and_then(option_text)
+    };
+    let schema = ArgumentSchema::from_command(selected_body)?;
+    let values = match &schema {
+        Some(schema) => match schema.parse_named(option_value) {
+            Ok(values) => Some(values),
+            Err(e) => return Ok(e.to_string()),
+        },
+        None => None,
+    };
+    let arguments = match &values {
+        Some(values) => values
+            .iter()
+            .map(|value| Some(value.as_ref()?.text.clone()))
+            .collect(),
+        None => option_value("arguments")
+            .map(|text| {
+                Args::new(&text, &[Delimiter::Single(' ')])
+                    .iter::<String>()
+                    .filter_map(Result::ok)
+                    .map(|arg| Some(arg.trim_matches('"').to_string()))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default(),
+    };
+
+    let mut context = template_context(
+        ctx,
+        &interaction.user,
+        interaction.channel_id,
+        interaction.guild_id,
+        &arguments,
+        &selected_body["default_args"],
+    )
+    .await;
+    let mut mentions = command_mentions(&command_data.body, interaction.user.id);
+    if let (Some(schema), Some(values)) = (&schema, values) {
+        add_argument_variables(schema, values, &mut context, &mut mentions);
+    }
+
+    let message = match render_custom_command(
+        ctx,
+        server_id,
+        name,
+        &command,
+        selected_subcommand.as_deref(),
+        context,
+    )
+    .await
+    {
+        Ok(message) => message,
+        Err(e) => return Ok(format!("This custom command could not be rendered: {}", e)),
+    };
+
+    announce_with_mentions(ctx, interaction.channel_id, &message, &mentions).await?;
+    Ok("✅".into())
+}
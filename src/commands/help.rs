@@ -4,6 +4,7 @@ use serenity::model::channel::Message;
 use serenity::utils::Colour;
 
 use crate::check::*;
+use crate::command_arguments::command_usage;
 use crate::constants::{MANAGE_BOT_PERMS, OWNER_ID};
 use crate::database::{
     config::{get_minecraft_ip, get_prefix},
@@ -32,7 +33,7 @@ pub async fn help(ctx: &Context, msg: &Message) -> CommandResult {
     let mut newline: u32 = 0;
     let cctext = cclist
         .into_iter()
-        .filter_map(|(name, desc)| {
+        .filter_map(|(name, desc, body)| {
             if !is_admin && desc.is_empty() {
                 None
             } else {
@@ -40,9 +41,12 @@ pub async fn help(ctx: &Context, msg: &Message) -> CommandResult {
                     newline += 1;
                 }
                 Some(format!(
-                    "{newline}`{}{}`{}",
+                    "{newline}`{}{}{}`{}",
                     prefix,
                     name,
+                    command_usage(&body)
+                        .map(|usage| format!(" {}", usage))
+                        .unwrap_or_default(),
                     match newline {
                         0 => format!("  {}\n", desc),
                         _ => String::new(),
@@ -190,6 +194,10 @@ async fn json(ctx: &Context, msg: &Message) -> CommandResult {
 		"🍎", // unicode emojis
 		"<:name:0000000000000000>" // custom emojis
     ],
+	"bug_report_button": { // a button opening a bug report form
+		"label": "Report a bug",
+		"channel": "0000000000000000" // where reports are posted, defaults to this channel
+	},
 	"embed": {
 		"colour": "RRGGBB", // hexadecimal color code
 		"author": {
@@ -249,9 +257,14 @@ async fn custom_commands(ctx: &Context, msg: &Message) -> CommandResult {
 		// if the type is "meme", the command will be subject to the blacklist
 		// if the type is "admin", only admins will be able to use it.
 	"default_args": ["arg0", "arg1", ...]
-		// if $0, $1 are left in the json because there are not enough arguments
-		// to fill them, these values will be used.
+		// values of {{arg0}}, {{arg1}}... when there are not enough arguments
+	"arguments": [{"name": "page", "type": "text", "required": true, "default": ...}]
+		// checked when the command is used, and usable as {{page}}; types are
+		// "user", "channel", "role", "integer", "text" and "choice" with "choices": [...]
+	"variables": {"name": "{{arg0 | upper}}"} // usable as {{name}}
 	"self_delete": true // or false: wether the command message is deleted after execution.
+	"slash_command": true // also register the command as a slash command, whose options
+		// are the subcommands and the arguments (arguments can have a "description")
 	"subcommands" : {
 		"subcommand_name": {"content": "some content", ...},
 		"other_subcommand_name": {...}, // define subcommands. 
@@ -264,6 +277,14 @@ async fn custom_commands(ctx: &Context, msg: &Message) -> CommandResult {
 	}
 }
 ```
+**Templates**
+```
+{{user.name}} {{user.mention}} {{channel.name}} {{guild.name}} {{args}} {{arg0}}
+{{arg0 | lower | url-encode}}    filters: upper, lower, url-encode
+{{#if arg1}}...{{else}}...{{/if}}
+{{#each args}}{{index}}: {{this}}{{/each}}
+```
+The legacy `$me`, `$ping`, `$channel`, `$args` and `$0` still work.
 "#,
             )
         })
@@ -287,7 +308,7 @@ pub async fn display_bugtracker_help(ctx: &Context, msg: &Message) -> CommandRes
                 e.colour(Colour::DARK_GREEN);
                 e.author(|a| {
                     a.icon_url(crate::constants::TERMITE_IMAGE);
-                    a.name("The bugtracker is only available in the LOTR Mod Community Discord");
+                    a.name("Bugtracker");
                     a
                 });
                 e.title("Available bugtracker commands");
@@ -295,12 +316,15 @@ pub async fn display_bugtracker_help(ctx: &Context, msg: &Message) -> CommandRes
                     "**Creating a bug report**",
                     format!(
 "`{prefix}track [status] <bug title>`  Creates a new bug report with the optional specified \
-`status`: one of `low`, `medium`, `high`, `critical`, and `forge` or `vanilla`. \
-The command returns a unique bug id.
+`status`, one of the statuses of the bug tracker. The command returns a unique bug id.
  \t**Must be used with an inline reply to a message that will constitute the \
  initial bug report content.**
 \tYou can optionnally use  `{prefix}track legacy [status] <bug title>`  \
 to create a legacy bug report.
+ \tUse  `{prefix}track thread ...`  to open a discussion thread on the reported message, \
+ locked once the bug is resolved or closed.
+ \tIf similar bugs already exist, they are displayed as possible duplicates and you will \
+ be asked to confirm before the bug report is created.
 `{prefix}bug link <bug id> [link url] [link title]`  Adds additional information to the bug \
 report referenced by its `bug id`. Can also be used with an inline reply to a message, \
 in which case you don't need to specify a url.
@@ -312,23 +336,98 @@ in which case you don't need to specify a url.
                     false,
                 );
                 e.field(
-                    "**Displaying and editing bug reports**",
+                    "**Displaying bug reports**",
                     format!(
-"`{prefix}bugs [latest|oldest|highest|lowest] [status] [page] [limit n]`  Displays a list of \
-bugs. By default, it will display all bugs that are not `resolved`, `forge` or `closed`, in \
+"`{prefix}bugs [latest|oldest|highest|lowest|affected] [status] [page] [limit n]`  Displays a list of \
+bugs. By default, it will display all bugs with an open status, in \
 chronological order starting from the latest one, and with a default limit of 10 bugs.
  \tThe `limit` keyword is necessary to specify a custom limit. `highest` and `lowest` will \
- sort the bugs by priority.
- \tAvailable statuses are `low`, `medium`, `high`, `critical`, `resolved`, `forge` \
- (or `vanilla`) and `closed`.
+ sort the bugs by the weight of their status.
  \tYou can optionnally use  `{prefix}bugs [legacy|renewed] [latest|oldest] [status] [limit]`  \
  to display legacy only or renewed only bugs.
-`{prefix}bug <bug id>`  Displays a single bug.
-`{prefix}bug rename <bug id> <new title>`  Change a bug's title.
+ \t`{prefix}bugs mine`  and  `{prefix}bugs assignee:<user mention>`  only display the bugs \
+ assigned to you or to the mentioned user.  `{prefix}bugs label:<label>`  only displays the \
+ bugs with the given label.
+",
+                        prefix = prefix
+                    ),
+                    false,
+                );
+                e.field(
+                    "**Finding bug reports**",
+                    format!(
+                        "`{prefix}bug <bug id>`  Displays a single bug.
+`{prefix}bug <bug id> history`  Displays the timeline of all the changes made to a bug.
+`{prefix}bug search [limit n] <search terms>`  Searches bug titles, report contents and link \
+titles, and displays the matching bugs ordered by relevance.
+`{prefix}bug statistics [weeks]`  Show bugtracker statistics, with the number of bugs opened \
+and resolved during the last weeks (8 by default, up to 20), and the median time to resolve a bug.
+`{prefix}bug digest <channel mention> [days]`  Posts a weekly digest in the channel, with the new \
+and resolved bugs of the week, and the `high` and `critical` bugs open for more than `days` \
+(30 by default). Use  `{prefix}bug digest disable`  to stop it.
+The  `/bug track`,  `/bug view`,  `/bug status`,  `/bug link`  and  `/bug list`  slash commands \
+are also available, with autocompletion of bug ids and titles.
+",
+                        prefix = prefix
+                    ),
+                    false,
+                );
+                e.field(
+                    "**Crash logs**",
+                    format!(
+                        "`{prefix}crashlog`  Summarizes the crash report or log file attached to \
+your message, or to the message you reply to: exception, suspected mods, versions and LOTR mod \
+stack frames.
+The crash logs attached to tracked messages are summarized and stored automatically. When a \
+crash has the same exception and LOTR mod stack frames as an open bug, you can add the report to \
+that bug instead: its reporter is subscribed to it, and  `{prefix}bug <bug id>`  shows how many \
+times the crash was seen. Only the author of the crash log or a moderator can add it to a bug.
+",
+                        prefix = prefix
+                    ),
+                    false,
+                );
+                e.field(
+                    "**Editing bug reports**",
+                    format!(
+                        "`{prefix}bug rename <bug id> <new title>`  Change a bug's title.
 `{prefix}bug status <bug id> <new status>`  Change a bug's status.
-`{prefix}bug toggle <bug id>`  Switch a bug's edition between renewed and legacy.
-
-`{prefix}bug statistics` Show bugtracker statistics.
+`{prefix}bug toggle <bug id> <category>`  Change a bug's category.
+`{prefix}bug assign <bug id> <user mention>`  Assign a bug to a developer, who is automatically \
+subscribed to it.
+`{prefix}bug unassign <bug id>`  Remove the assignee of a bug.
+`{prefix}bug label add <bug id> <label>`  Add a label to a bug, like `worldgen` or `crash`. \
+Any label can be used.
+`{prefix}bug label remove <bug id> <label>`  Remove a label from a bug.
+`{prefix}bug labels`  Display all the labels in use.
+",
+                        prefix = prefix
+                    ),
+                    false,
+                );
+                e.field(
+                    "**Versions and releases**",
+                    format!(
+                        "`{prefix}bug version <bug id> <version>`  Set the mod version affected by \
+a bug. Use `none` to remove it.
+`{prefix}resolve <bug id> in <version>`  Marks a bug as resolved, and fixed in the given release.
+`{prefix}bugs version:<version>`  Only displays the bugs affecting or fixed in a version.
+`{prefix}bug changelog <version>`  Lists all the bugs resolved in a release, ready to paste in \
+a CurseForge changelog.
+",
+                        prefix = prefix
+                    ),
+                    false,
+                );
+                e.field(
+                    "**Exporting and importing bug reports**",
+                    format!(
+"`{prefix}bugs export [csv|json] [filters] [status] [category]`  Exports the bug list as a \
+CSV (default) or JSON file, with the same filters as  `{prefix}bugs`. All the bugs are \
+exported, without any limit.
+`{prefix}bug import`  Imports the bugs of an attached JSON or CSV file, in the format of \
+`{prefix}bugs export`. Timestamps, statuses, categories and links are kept, and the imported \
+bugs get new bug ids. Invalid rows are skipped and listed in the summary.
 ",
                         prefix = prefix
                     ),
@@ -341,6 +440,58 @@ chronological order starting from the latest one, and with a default limit of 10
 Equivalent to  `{prefix}bug status <bug id> resolved`.
 `{prefix}bug close <bug id>`  Marks a bug as closed. \
 Equivalent to  `{prefix}bug status <bug id> closed`.
+`{prefix}bug duplicate <bug id> <original bug id>`  Closes a bug as a duplicate of another. \
+Its subscribers are moved to the original bug, and both bugs get a link to each other.
+`{prefix}bug needinfo <bug id> [question]`  Marks a bug as `needs-info` and pings its reporter. \
+It is closed if left inactive.
+",
+                        prefix = prefix,
+                    ),
+                    false,
+                );
+                e
+            })
+        })
+        .await?;
+    // embeds of a single message are limited to 6000 characters in total
+    msg.author
+        .dm(ctx, |m| {
+            m.embed(|e| {
+                e.colour(Colour::DARK_GREEN);
+                e.author(|a| {
+                    a.icon_url(crate::constants::TERMITE_IMAGE);
+                    a.name("Bugtracker");
+                    a
+                });
+                e.field(
+                    "**Setting up the bug tracker**",
+                    format!(
+                        "`{prefix}bug tracker`  Displays the bug tracker setup of the server.
+`{prefix}bug tracker enable [prefix]`  Enables the bug tracker, with bug ids like `BUG-42` by \
+default. Use  `{prefix}bug tracker disable`  to disable it: its bugs are kept.
+`{prefix}bug tracker prefix <prefix>`  Changes the prefix of the bug ids.
+`{prefix}bug tracker channel <channel mention|none>`  Announces new bugs in a channel.
+`{prefix}bug tracker categories <categories>`  Sets the available bug categories, the first \
+one being the default.
+`{prefix}bug tracker status`  Lists the bug statuses, and how to add, edit or remove them.
+`{prefix}bug tracker needinfo <reminder days> <close days>`  Sets when inactive bugs \
+needing more information are reminded and closed.
+`{prefix}bug tracker moderator <user mention>`  Adds or removes a bug tracker moderator, who \
+can use all the commands above except the setup ones.
+",
+                        prefix = prefix,
+                    ),
+                    false,
+                );
+                e.field(
+                    "**Mirroring bugs as issues**",
+                    format!(
+                        "`{prefix}bug tracker mirror <owner/repo> [api url]`  Mirrors the bugs \
+in a GitHub repository, or in a compatible server like Gitea with an `api url`. Use  \
+`{prefix}bug tracker mirror none`  to stop. Only the bot owner can set the repository, as the bot writes in it with its own token.
+`{prefix}track mirror ...`  Opens an issue mirroring the new bug, linked in the bug report. The \
+bug is resolved, closed or reopened when its issue is, within 10 minutes. Also available with \
+the `mirror` option of  `/bug track`.
 ",
                         prefix = prefix,
                     ),
@@ -360,7 +511,8 @@ Equivalent to  `{prefix}bug status <bug id> closed`.
 }
 
 #[command]
-#[checks(is_admin)]
+#[checks(is_bugtracker_moderator)]
+#[only_in(guilds)]
 #[aliases("bug", "bugs")]
 pub async fn bugtracker(ctx: &Context, msg: &Message) -> CommandResult {
     display_bugtracker_help(ctx, msg).await
@@ -425,6 +577,9 @@ For the JSON argument documentation, type  `{prefix}help custom`
 `{prefix}command display [command name]`  Provide an argument to get info on a specific command, \
 or leave empty to get a list of commands
 `{prefix}command remove <command name>`  Remove a custom command
+`{prefix}command history <command name>`  List the revisions of a custom command
+`{prefix}command diff <command name> <rev1> <rev2>`  Compare two revisions
+`{prefix}command rollback <command name> <rev>`  Restore a previous revision
 
 *Only bot admins can use these commands*
 *For bugtracker help, use  `{prefix}help bugtracker`*",
@@ -6,6 +6,7 @@
 pub mod admin;
 pub mod announcements;
 pub mod bug_reports;
+pub mod bug_tracker_setup;
 pub mod custom_commands;
 pub mod general;
 pub mod help;
@@ -12,6 +12,8 @@ pub const EOA_DISCORD: GuildId = GuildId(325180225979809792);
 /// Maximum size, in bytes, of a JSON file for [announcements][crate::announcement]
 /// and [custom commands][crate::commands::custom_commands]
 pub const MAX_JSON_FILE_SIZE: u64 = 10240;
+/// Maximum size, in bytes, of a [crash log][crate::crash_logs] attached to a bug report
+pub const MAX_CRASH_LOG_SIZE: u64 = 2 * 1024 * 1024;
 
 /// Bit filter for colours
 pub const BIT_FILTER_24BITS: u32 = !(!0 << 24);
@@ -92,6 +94,9 @@ pub const CURSE_API: &str = "https://api.curseforge.com/v1/mods/";
 pub const MINECRAFT_API: &str = "https://api.mcsrvstat.us/2/";
 /// Google API for custom google search
 pub const GOOGLE_API: &str = "https://www.googleapis.com/customsearch/v1?";
+/// The [GitHub REST API](https://docs.github.com/en/rest), where bug reports can be
+/// [mirrored][crate::api::issues] as issues
+pub const GITHUB_API: &str = "https://api.github.com";
 
 /// Curseforge project ID for the [LOTR Mod Renewed](https://www.curseforge.com/minecraft/mc-mods/the-lord-of-the-rings-mod-renewed)
 pub const CURSEFORGE_ID_RENEWED: u64 = 406893;
@@ -112,12 +117,42 @@ pub const TABLE_CHANNEL_BLACKLIST: &str = "channel_blacklist";
 pub const TABLE_MC_SERVER_IP: &str = "mc_server_ip";
 /// SQL table name for [custom commands][crate::database::custom_commands]
 pub const TABLE_CUSTOM_COMMANDS: &str = "custom_commands";
+/// SQL table name for [custom command revisions][crate::database::custom_commands]
+pub const TABLE_CUSTOM_COMMANDS_REVISIONS: &str = "custom_commands__revisions";
 /// SQL table name for [bug reports][crate::database::bug_reports]
 pub const TABLE_BUG_REPORTS: &str = "bug_reports";
 /// SQL table name for [bug report links][crate::database::bug_reports]
 pub const TABLE_BUG_REPORTS_LINKS: &str = "bug_reports__links";
 /// SQL table name for [bug report notifications][crate::database::bug_reports]
 pub const TABLE_BUG_REPORTS_NOTIFICATIONS: &str = "bug_reports__notifications";
+/// SQL table name for [bug report notification settings][crate::database::bug_reports]
+pub const TABLE_BUG_REPORTS_NOTIFICATION_SETTINGS: &str = "bug_reports__notification_settings";
+/// SQL table name for [bug report notifications waiting for a digest][crate::database::bug_reports]
+pub const TABLE_BUG_REPORTS_NOTIFICATION_QUEUE: &str = "bug_reports__notification_queue";
+/// SQL table name for [bug report history][crate::database::bug_reports]
+pub const TABLE_BUG_REPORTS_HISTORY: &str = "bug_reports__history";
+/// SQL table name for [bug report labels][crate::database::bug_reports]
+pub const TABLE_BUG_REPORTS_LABELS: &str = "bug_reports__labels";
+/// SQL table name for [bug report digest channels][crate::database::bug_reports]
+pub const TABLE_BUG_REPORTS_DIGESTS: &str = "bug_reports__digests";
+/// SQL table name for [bug report crash logs][crate::database::bug_reports]
+pub const TABLE_BUG_REPORTS_CRASH_LOGS: &str = "bug_reports__crash_logs";
+/// SQL table name for [bug report crash sightings][crate::database::bug_reports]
+pub const TABLE_BUG_REPORTS_CRASH_SIGHTINGS: &str = "bug_reports__crash_sightings";
+/// SQL table name for [users affected by bug reports][crate::database::bug_reports]
+pub const TABLE_BUG_REPORTS_AFFECTED_USERS: &str = "bug_reports__affected_users";
+/// SQL table name for [issues mirroring bug reports][crate::database::bug_reports]
+pub const TABLE_BUG_REPORTS_ISSUES: &str = "bug_reports__issues";
+/// SQL table name for [bug report info requests][crate::database::bug_reports]
+pub const TABLE_BUG_REPORTS_INFO_REQUESTS: &str = "bug_reports__info_requests";
+/// SQL table name for [bug trackers][crate::database::bug_trackers]
+pub const TABLE_BUG_TRACKERS: &str = "bug_trackers";
+/// SQL table name for [bug tracker moderators][crate::database::bug_trackers]
+pub const TABLE_BUG_TRACKERS_MODERATORS: &str = "bug_trackers__moderators";
+/// SQL table name for [bug tracker statuses][crate::database::bug_trackers]
+pub const TABLE_BUG_TRACKERS_STATUSES: &str = "bug_trackers__statuses";
+/// SQL table name for [bug tracker issue mirroring][crate::database::bug_trackers]
+pub const TABLE_BUG_TRACKERS_ISSUE_SYNC: &str = "bug_trackers__issue_sync";
 /// SQL table name for [role handling][crate::database::roles]
 pub const TABLE_ROLES: &str = "roles";
 /// SQL table name for [role aliases handling][crate::database::roles]
@@ -126,7 +161,7 @@ pub const TABLE_ROLES_ALIASES: &str = "roles__aliases";
 pub const TABLE_LIST_GUILDS: &str = "list_guilds";
 
 /// Reserved command names that cannot be used as [custom commands][crate::commands::custom_commands]
-pub const RESERVED_NAMES: [&str; 52] = [
+pub const RESERVED_NAMES: [&str; 53] = [
     "legacy",
     "renewed",
     "download",
@@ -169,6 +204,7 @@ pub const RESERVED_NAMES: [&str; 52] = [
     "bugs",
     "buglist",
     "resolve",
+    "crashlog",
     "clean_database",
     "user",
     "user_info",
@@ -0,0 +1,360 @@
+//! Parsing of Minecraft crash reports and `latest.log` files attached to bug reports.
+
+use serenity::builder::CreateEmbed;
+use serenity::model::prelude::*;
+
+use crate::constants::MAX_CRASH_LOG_SIZE;
+
+/// Maximum number of LOTR mod stack frames kept from a crash log
+pub const MAX_MOD_FRAMES: usize = 5;
+/// Maximum length of the exception message kept from a crash log
+pub const MAX_EXCEPTION_LENGTH: usize = 500;
+/// Maximum length of the suspected mods kept from a crash log, one per line
+pub const MAX_SUSPECTED_MODS_LENGTH: usize = 500;
+
+/// Information extracted from a crash report or a log file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CrashLog {
+    pub exception: Option<String>,
+    pub suspected_mods: Vec<String>,
+    pub minecraft_version: Option<String>,
+    pub forge_version: Option<String>,
+    pub mod_version: Option<String>,
+    pub mod_frames: Vec<String>,
+}
+
+/// Returns the first attachment of a message that looks like a crash report or a log file
+pub fn crash_log_attachment(msg: &Message) -> Option<&Attachment> {
+    msg.attachments.iter().find(|a| {
+        let filename = a.filename.to_lowercase();
+        filename.ends_with(".txt")
+            || filename.ends_with(".log")
+            || a.content_type
+                .as_ref()
+                .map(|s| s.starts_with("text/plain"))
+                .unwrap_or_default()
+    })
+}
+
+/// Downloads and parses the first crash log attached to a message.
+///
+/// Returns `Ok(None)` if there is no text attachment, or if nothing could be extracted from it.
+pub async fn read_crash_log(msg: &Message) -> Result<Option<CrashLog>, String> {
+    let attachment = match crash_log_attachment(msg) {
+        Some(attachment) => attachment,
+        None => return Ok(None),
+    };
+
+    if attachment.size > MAX_CRASH_LOG_SIZE {
+        return Err(format!(
+            "Crash log is too big! Filesize must be under {}. Attached file size: {}",
+            bytesize::ByteSize(MAX_CRASH_LOG_SIZE),
+            bytesize::ByteSize(attachment.size)
+        ));
+    }
+
+    let data = attachment
+        .download()
+        .await
+        .map_err(|e| format!("Could not download attachment: {}", e))?;
+
+    Ok(CrashLog::parse(&String::from_utf8_lossy(&data)))
+}
+
+/// Returns the text following `prefix` on a line, if the line starts with it
+fn value_after<'a>(line: &'a str, prefix: &str) -> Option<&'a str> {
+    line.trim()
+        .strip_prefix(prefix)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+/// Returns the first whitespace-separated word following `pattern` in a line
+fn word_after<'a>(line: &'a str, pattern: &str) -> Option<&'a str> {
+    line.find(pattern)
+        .and_then(|i| line[i + pattern.len()..].split_whitespace().next())
+        .map(|s| s.trim_matches(|c| c == ',' || c == ']' || c == '}'))
+        .filter(|s| !s.is_empty())
+}
+
+/// Returns the text between `start` and the next `end` in a line
+fn between<'a>(line: &'a str, start: &str, end: char) -> Option<&'a str> {
+    let i = line.find(start)? + start.len();
+    let j = line[i..].find(end)?;
+    Some(line[i..i + j].trim()).filter(|s| !s.is_empty())
+}
+
+/// Strips the `at ` prefix and module information of a stack frame, returning
+/// the frame if it belongs to the LOTR mod
+fn mod_frame(line: &str) -> Option<String> {
+    let frame = line.trim().strip_prefix("at ")?;
+    // Renewed logs prefix frames with the module, like `TRANSFORMER/lotr@3.4.2/lotr.common...`
+    let frame = frame.split_whitespace().next()?;
+    let frame = frame.rsplit('/').next()?;
+    if frame.starts_with("lotr.") {
+        Some(frame.to_string())
+    } else {
+        None
+    }
+}
+
+/// Whether a line starts a Java stack trace, like `java.lang.NullPointerException: message`
+fn is_exception_line(line: &str) -> bool {
+    let class = line
+        .trim()
+        .split(|c: char| c == ':' || c.is_whitespace())
+        .next()
+        .unwrap_or_default();
+    class.contains('.')
+        && !class.ends_with('.')
+        && (class.ends_with("Exception")
+            || class.ends_with("Error")
+            || class.ends_with("Throwable"))
+}
+
+impl CrashLog {
+    /// Extracts the exception, suspected mods, versions and LOTR mod stack frames
+    /// from a crash report or a log file.
+    ///
+    /// Returns `None` if nothing could be found.
+    pub fn parse(content: &str) -> Option<Self> {
+        let mut log = Self::default();
+        let lines: Vec<&str> = content.lines().collect();
+
+        for (i, line) in lines.iter().enumerate() {
+            if log.exception.is_none()
+                && is_exception_line(line)
+                && lines
+                    .get(i + 1)
+                    .map_or(false, |next| next.trim_start().starts_with("at "))
+            {
+                log.exception = Some(line.trim().chars().take(MAX_EXCEPTION_LENGTH).collect());
+                // the stack trace goes on as long as there are frames or causes
+                let mut trace_end = i + 1;
+                while lines.get(trace_end).map_or(false, |l| {
+                    let l = l.trim_start();
+                    l.starts_with("at ") || l.starts_with("Caused by:") || l.starts_with("...")
+                }) {
+                    trace_end += 1;
+                }
+                log.mod_frames = lines[i + 1..trace_end]
+                    .iter()
+                    .filter_map(|l| mod_frame(l))
+                    .take(MAX_MOD_FRAMES)
+                    .collect();
+            }
+
+            if let Some(mods) =
+                value_after(line, "Suspected Mods:").or_else(|| value_after(line, "Suspected Mod:"))
+            {
+                if !mods.eq_ignore_ascii_case("none") && !mods.eq_ignore_ascii_case("unknown") {
+                    log.suspected_mods.push(mods.to_string());
+                }
+            } else if line.trim().starts_with("Suspected Mod") && log.suspected_mods.is_empty() {
+                // the suspected mods are listed on the following indented lines
+                log.suspected_mods = lines[i + 1..]
+                    .iter()
+                    .take_while(|l| l.starts_with(char::is_whitespace) && !l.trim().is_empty())
+                    .map(|l| l.trim())
+                    .filter(|l| !l.contains("URL:"))
+                    .map(str::to_string)
+                    .collect();
+            }
+
+            if log.minecraft_version.is_none() {
+                log.minecraft_version = value_after(line, "Minecraft Version:")
+                    .or_else(|| word_after(line, "--fml.mcVersion, "))
+                    .or_else(|| word_after(line, "for Minecraft "))
+                    .map(str::to_string);
+            }
+
+            if log.forge_version.is_none() {
+                log.forge_version = value_after(line, "Forge Version:")
+                    .or_else(|| word_after(line, "--fml.forgeVersion, "))
+                    .or_else(|| word_after(line, "Minecraft Forge "))
+                    .or_else(|| word_after(line, "MinecraftForge v"))
+                    .or_else(|| {
+                        value_after(line, "Launched Version:")?
+                            .split("-forge-")
+                            .nth(1)
+                    })
+                    .map(str::to_string);
+            }
+
+            if log.mod_version.is_none() {
+                log.mod_version = between(line, "lotr{", '}')
+                    .or_else(|| between(line, "{lotr} mods - versions {", '}'))
+                    .or_else(|| {
+                        // Renewed mod lists are tables like `lotr.jar |The Lord of the Rings Mod |lotr |3.4.2 |DONE`
+                        let mut cells = line.split('|').map(str::trim);
+                        cells.position(|cell| cell == "lotr")?;
+                        cells.next().filter(|s| !s.is_empty())
+                    })
+                    .map(str::to_string);
+            }
+        }
+
+        if !log.suspected_mods.is_empty() {
+            let suspected_mods: String = log
+                .suspected_mods
+                .join("\n")
+                .chars()
+                .take(MAX_SUSPECTED_MODS_LENGTH)
+                .collect();
+            log.suspected_mods = suspected_mods.lines().map(str::to_string).collect();
+        }
+
+        // fall back to any LOTR mod frame in the file if the main stack trace had none
+        if log.mod_frames.is_empty() {
+            log.mod_frames = lines
+                .iter()
+                .filter_map(|l| mod_frame(l))
+                .take(MAX_MOD_FRAMES)
+                .collect();
+        }
+
+        if log == Self::default() {
+            None
+        } else {
+            Some(log)
+        }
+    }
+
+    /// Normalized signature of the crash, used to group identical crashes: the exception type
+    /// followed by the top LOTR mod frames, without line numbers.
+    ///
+    /// Returns `None` if there is no exception or no LOTR mod frame.
+    pub fn signature(&self) -> Option<String> {
+        if self.mod_frames.is_empty() {
+            return None;
+        }
+        let exception_type = self
+            .exception
+            .as_deref()?
+            .split(|c: char| c == ':' || c.is_whitespace())
+            .next()?;
+
+        let mut signature = exception_type.to_string();
+        for frame in &self.mod_frames {
+            signature.push('\n');
+            signature.push_str(frame.split('(').next().unwrap_or(frame));
+        }
+        Some(signature)
+    }
+
+    /// Adds the crash log summary to an embed
+    pub fn add_embed_fields<'a>(&self, e: &'a mut CreateEmbed) -> &'a mut CreateEmbed {
+        if let Some(exception) = &self.exception {
+            e.field("Exception", format!("```\n{}\n```", exception), false);
+        }
+        let versions = [
+            ("Minecraft", &self.minecraft_version),
+            ("Forge", &self.forge_version),
+            ("LOTR mod", &self.mod_version),
+        ]
+        .iter()
+        .filter_map(|(name, version)| {
+            version
+                .as_ref()
+                .map(|version| format!("{}: `{}`", name, version))
+        })
+        .collect::<Vec<_>>();
+        if !versions.is_empty() {
+            e.field("Versions", versions.join("\n"), true);
+        }
+        if !self.suspected_mods.is_empty() {
+            e.field("Suspected mods", self.suspected_mods.join("\n"), true);
+        }
+        if !self.mod_frames.is_empty() {
+            e.field(
+                "LOTR mod stack frames",
+                format!("```\n{}\n```", self.mod_frames.join("\n")),
+                false,
+            );
+        }
+        e
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_crash_report() {
+        let report = "---- Minecraft Crash Report ----
+Description: Ticking entity
+
+java.lang.NullPointerException: Ticking entity
+\tat lotr.common.entity.npc.LOTREntityNPC.onLivingUpdate(LOTREntityNPC.java:512)
+\tat net.minecraft.entity.EntityLivingBase.onUpdate(EntityLivingBase.java:1816)
+\tat lotr.common.entity.npc.LOTREntityNPC.onUpdate(LOTREntityNPC.java:480)
+
+-- System Details --
+Details:
+\tMinecraft Version: 1.7.10
+\tFML: MCP v9.05 FML v7.10.99.99 Minecraft Forge 10.13.4.1614 4 mods loaded
+\tSuspected Mods: The Lord of the Rings Mod (lotr)
+\tUCHIJAAAA\tlotr{Update v36.15 for 1.7.10} [The Lord of the Rings Mod] (lotr.jar)
+";
+        let log = CrashLog::parse(report).unwrap();
+        assert_eq!(
+            log.exception.as_deref(),
+            Some("java.lang.NullPointerException: Ticking entity")
+        );
+        assert_eq!(log.minecraft_version.as_deref(), Some("1.7.10"));
+        assert_eq!(log.forge_version.as_deref(), Some("10.13.4.1614"));
+        assert_eq!(log.mod_version.as_deref(), Some("Update v36.15 for 1.7.10"));
+        assert_eq!(log.suspected_mods, ["The Lord of the Rings Mod (lotr)"]);
+        assert_eq!(
+            log.mod_frames,
+            [
+                "lotr.common.entity.npc.LOTREntityNPC.onLivingUpdate(LOTREntityNPC.java:512)",
+                "lotr.common.entity.npc.LOTREntityNPC.onUpdate(LOTREntityNPC.java:480)"
+            ]
+        );
+
+        assert_eq!(
+            log.signature().as_deref(),
+            Some(
+                "java.lang.NullPointerException
+lotr.common.entity.npc.LOTREntityNPC.onLivingUpdate
+lotr.common.entity.npc.LOTREntityNPC.onUpdate"
+            )
+        );
+
+        assert_eq!(CrashLog::parse("nothing to see here"), None);
+    }
+
+    #[test]
+    fn test_parse_suspected_mods_list() {
+        let report = "---- Minecraft Crash Report ----
+Description: Rendering entity in world
+
+-- Head --
+Thread: Render thread
+Suspected Mods: 
+\tThe Lord of the Rings Mod (lotr), Version: 1.5.3
+\t\tIssue tracker URL: https://github.com/LOTR-Minecraft-Mod/issues
+\tMinecraft (minecraft), Version: 1.16.5
+Stacktrace:
+\tat lotr.client.render.entity.HobbitRenderer.render(HobbitRenderer.java:42)
+";
+        let log = CrashLog::parse(report).unwrap();
+        assert_eq!(
+            log.suspected_mods,
+            [
+                "The Lord of the Rings Mod (lotr), Version: 1.5.3",
+                "Minecraft (minecraft), Version: 1.16.5"
+            ]
+        );
+
+        let many_mods = (0..100)
+            .map(|i| format!("\tSome Mod {} (mod{})", i, i))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let log = CrashLog::parse(&format!("Suspected Mods:\n{}\n", many_mods)).unwrap();
+        assert_eq!(log.suspected_mods[0], "Some Mod 0 (mod0)");
+        assert!(log.suspected_mods.join("\n").chars().count() <= MAX_SUSPECTED_MODS_LENGTH);
+    }
+}
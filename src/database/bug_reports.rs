@@ -1,20 +1,60 @@
 use chrono::{DateTime, NaiveDateTime, Utc};
 use const_format::formatcp;
-use mysql_async::prelude::*;
+use mysql_async::{prelude::*, Conn, TxOpts};
 use serenity::client::Context;
 use serenity::framework::standard::{CommandError, CommandResult};
 use serenity::model::prelude::*;
 use serenity::utils::Colour;
+use std::collections::HashMap;
 
+use crate::api::issues::IssueState;
 use crate::constants::{
-    TABLE_BUG_REPORTS, TABLE_BUG_REPORTS_LINKS, TABLE_BUG_REPORTS_NOTIFICATIONS,
+    TABLE_BUG_REPORTS, TABLE_BUG_REPORTS_AFFECTED_USERS, TABLE_BUG_REPORTS_CRASH_LOGS,
+    TABLE_BUG_REPORTS_CRASH_SIGHTINGS, TABLE_BUG_REPORTS_DIGESTS, TABLE_BUG_REPORTS_HISTORY,
+    TABLE_BUG_REPORTS_INFO_REQUESTS, TABLE_BUG_REPORTS_ISSUES, TABLE_BUG_REPORTS_LABELS,
+    TABLE_BUG_REPORTS_LINKS, TABLE_BUG_REPORTS_NOTIFICATIONS, TABLE_BUG_REPORTS_NOTIFICATION_QUEUE,
+    TABLE_BUG_REPORTS_NOTIFICATION_SETTINGS, TABLE_BUG_TRACKERS, TABLE_BUG_TRACKERS_STATUSES,
 };
+use crate::crash_logs::CrashLog;
+use crate::database::bug_trackers::{BugTracker, DEFAULT_BUG_PREFIX};
 use crate::get_database_conn;
 
+/// Columns of a [`PartialBugReport`], the id prefix coming from the tracker of the bug
+const PARTIAL_BUG_COLUMNS: &str = formatcp!(
+    "bug_id, title, status, timestamp, category, COALESCE((SELECT prefix FROM {0} \
+WHERE {0}.guild_id = {1}.guild_id), '{2}'), guild_id",
+    TABLE_BUG_TRACKERS,
+    TABLE_BUG_REPORTS,
+    DEFAULT_BUG_PREFIX
+);
+
+/// SQL condition selecting the bugs with an open status in their tracker
+const OPEN_STATUS: &str = formatcp!(
+    "status IN (SELECT name FROM {0} WHERE {0}.guild_id = {1}.guild_id AND {0}.open)",
+    TABLE_BUG_TRACKERS_STATUSES,
+    TABLE_BUG_REPORTS
+);
+
+/// SQL expression of the weight of the status of a bug in its tracker
+const STATUS_WEIGHT: &str = formatcp!(
+    "(SELECT weight FROM {0} WHERE {0}.guild_id = {1}.guild_id AND {0}.name = {1}.status)",
+    TABLE_BUG_TRACKERS_STATUSES,
+    TABLE_BUG_REPORTS
+);
+
+/// SQL expression of the number of users affected by a bug
+const AFFECTED_USERS: &str = formatcp!(
+    "(SELECT COUNT(*) FROM {0} WHERE {0}.bug_id = {1}.bug_id)",
+    TABLE_BUG_REPORTS_AFFECTED_USERS,
+    TABLE_BUG_REPORTS
+);
+
 #[derive(Debug, Clone, Copy)]
 pub enum BugOrder {
     Chronological(bool),
     Priority(bool),
+    /// By number of affected users
+    Affected(bool),
     None,
 }
 
@@ -57,6 +97,25 @@ impl std::str::FromStr for BugCategory {
 }
 
 impl BugCategory {
+    pub const ALL: [BugCategory; 4] = [
+        BugCategory::FaRenewed,
+        BugCategory::FaLegacy,
+        BugCategory::SaRenewed,
+        BugCategory::SaLegacy,
+    ];
+
+    /// Index of the category in [`BugCategory::ALL`]
+    pub fn index(self) -> usize {
+        use BugCategory::*;
+
+        match self {
+            FaRenewed => 0,
+            FaLegacy => 1,
+            SaRenewed => 2,
+            SaLegacy => 3,
+        }
+    }
+
     pub fn as_str(self) -> &'static str {
         use BugCategory::*;
 
@@ -75,106 +134,177 @@ impl Default for BugCategory {
     }
 }
 
-use BugStatus::*;
-
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub enum BugStatus {
-    Closed,
-    ForgeVanilla,
-    Resolved,
-    Low,
-    Medium,
-    High,
-    Critical,
+/// Status of resolved bugs, which every tracker has
+pub const STATUS_RESOLVED: &str = "resolved";
+/// Status of closed bugs, which every tracker has
+pub const STATUS_CLOSED: &str = "closed";
+/// Status of the bugs waiting for more information from their reporter, which every tracker has
+pub const STATUS_NEEDS_INFO: &str = "needs-info";
+/// Status of new bugs, if the tracker has it
+pub const DEFAULT_STATUS: &str = "medium";
+/// Open bugs with at least this weight are high priority, like the default `high` status
+pub const HIGH_PRIORITY_WEIGHT: i32 = 5;
+pub const MAX_STATUS_LENGTH: usize = 32;
+
+/// Workflow state of a bug, defined by its tracker
+#[derive(Debug, Clone)]
+pub struct BugStatus {
+    pub name: String,
+    pub colour: Colour,
+    pub marker: String,
+    /// Ordering of the statuses, the highest priority having the highest weight
+    pub weight: i32,
+    /// Open bugs are listed by default, and closed ones are locked
+    pub open: bool,
 }
 
-impl std::fmt::Display for BugStatus {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        match self {
-            ForgeVanilla => write!(f, "Forge or Vanilla"),
-            _ => write!(f, "{:?}", self),
-        }
+impl PartialEq for BugStatus {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-pub struct ParseStatusError;
-
-impl std::str::FromStr for BugStatus {
-    type Err = ParseStatusError;
+impl Eq for BugStatus {}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(match s.to_lowercase().as_str() {
-            "resolved" => Resolved,
-            "low" => Low,
-            "medium" => Medium,
-            "high" => High,
-            "critical" => Critical,
-            "closed" => Closed,
-            "forgevanilla" | "forge" | "vanilla" => ForgeVanilla,
-            _ => return Err(Self::Err {}),
-        })
+impl std::fmt::Display for BugStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.name)
     }
 }
 
-impl std::default::Default for BugStatus {
-    fn default() -> Self {
-        Medium
+impl BugStatus {
+    pub fn new(name: &str, colour: u32, marker: &str, weight: i32, open: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            colour: Colour(colour),
+            marker: marker.to_string(),
+            weight,
+            open,
+        }
     }
-}
 
-impl BugStatus {
-    #[inline]
-    pub const fn as_str(self) -> &'static str {
-        match self {
-            Resolved => "resolved",
-            Low => "low",
-            Medium => "medium",
-            High => "high",
-            Critical => "critical",
-            Closed => "closed",
-            ForgeVanilla => "forgevanilla",
+    /// Status of a bug that its tracker does not define anymore
+    pub fn unknown(name: String) -> Self {
+        Self {
+            name,
+            colour: Colour::LIGHT_GREY,
+            marker: ":white_circle:".into(),
+            weight: 0,
+            open: true,
         }
     }
 
+    /// Statuses of a new tracker
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::new("critical", 0xff0000, ":bangbang:", 6, true),
+            Self::new("high", 0xfd6104, ":red_circle:", 5, true),
+            Self::new(DEFAULT_STATUS, 0xfd9a01, ":orange_circle:", 4, true),
+            Self::new("low", 0xfef001, ":yellow_circle:", 3, true),
+            Self::new(STATUS_NEEDS_INFO, 0xaab8c2, ":grey_question:", 3, true),
+            Self::new(STATUS_RESOLVED, 0x2fd524, ":green_circle:", 2, false),
+            Self::new("forgevanilla", 0x9f00c5, ":regional_indicator_v:", 1, false),
+            Self::new(STATUS_CLOSED, 0x7694cb, ":blue_circle:", 0, false),
+        ]
+    }
+
     #[inline]
-    pub const fn colour(self) -> Colour {
-        match self {
-            Resolved => Colour(0x2fd524),
-            Low => Colour(0xfef001),
-            Medium => Colour(0xfd9a01),
-            High => Colour(0xfd6104),
-            Critical => Colour(0xff0000),
-            Closed => Colour(0x7694cb),
-            ForgeVanilla => Colour(0x9f00c5),
-        }
+    pub fn as_str(&self) -> &str {
+        &self.name
     }
 
-    pub const fn marker(self) -> &'static str {
-        match self {
-            Resolved => ":green_circle:",
-            Low => ":yellow_circle:",
-            Medium => ":orange_circle:",
-            High => ":red_circle:",
-            Critical => ":bangbang:",
-            Closed => ":blue_circle:",
-            ForgeVanilla => ":regional_indicator_v:",
-        }
+    /// Whether the status can be removed from its tracker
+    pub fn is_reserved(&self) -> bool {
+        matches!(
+            self.name.as_str(),
+            STATUS_RESOLVED | STATUS_CLOSED | STATUS_NEEDS_INFO
+        )
     }
 
-    pub fn reaction(self) -> ReactionType {
+    pub fn reaction(&self) -> ReactionType {
         ReactionType::Unicode(
-            match self {
-                Resolved => "✅",
-                Low | Medium | High | Critical => "⚠️",
-                Closed => "❌",
-                ForgeVanilla => "🇻", // not a V: the [V] emoji
+            match self.name.as_str() {
+                STATUS_RESOLVED => "✅",
+                "forgevanilla" => "🇻", // not a V: the [V] emoji
+                _ if self.open => "⚠️",
+                _ => "❌",
             }
             .to_string(),
         )
     }
 }
 
+/// Checks that a status name is only made of lowercase ASCII alphanumerics and dashes,
+/// and not too long
+pub fn normalize_status(name: &str) -> Option<String> {
+    let name = name.trim().to_lowercase();
+    if !name.is_empty()
+        && name.len() <= MAX_STATUS_LENGTH
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        Some(name)
+    } else {
+        None
+    }
+}
+
+/// Status definitions of the trackers, to resolve the status names stored with the bugs
+#[derive(Debug, Default)]
+struct StatusDefinitions(HashMap<(u64, String), BugStatus>);
+
+impl StatusDefinitions {
+    /// Loads the statuses of a guild, or of all the guilds
+    async fn load(conn: &mut Conn, guild_id: Option<GuildId>) -> Result<Self, mysql_async::Error> {
+        let statuses: Vec<(u64, BugStatus)> = conn
+            .exec_map(
+                formatcp!(
+                    "SELECT guild_id, name, colour, marker, weight, open FROM {} \
+WHERE :guild_id IS NULL OR guild_id = :guild_id",
+                    TABLE_BUG_TRACKERS_STATUSES
+                ),
+                params! {
+                    "guild_id" => guild_id.map(|g| g.0)
+                },
+                |(guild_id, name, colour, marker, weight, open): (
+                    u64,
+                    String,
+                    u32,
+                    String,
+                    i32,
+                    bool,
+                )| {
+                    (
+                        guild_id,
+                        BugStatus {
+                            name,
+                            colour: Colour(colour),
+                            marker,
+                            weight,
+                            open,
+                        },
+                    )
+                },
+            )
+            .await?;
+
+        Ok(Self(
+            statuses
+                .into_iter()
+                .map(|(guild_id, status)| ((guild_id, status.name.clone()), status))
+                .collect(),
+        ))
+    }
+
+    fn resolve(&self, guild_id: u64, name: String) -> BugStatus {
+        self.0
+            .get(&(guild_id, name.clone()))
+            .cloned()
+            .unwrap_or_else(|| BugStatus::unknown(name))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct BugLink {
     pub id: u64,
@@ -191,6 +321,9 @@ impl std::fmt::Display for BugLink {
 #[derive(Debug, Clone)]
 pub struct BugReport {
     pub bug_id: u64,
+    pub guild_id: GuildId,
+    /// Id prefix of the bug tracker of the guild
+    pub prefix: String,
     pub channel_id: ChannelId,
     pub message_id: MessageId,
     pub title: String,
@@ -198,11 +331,23 @@ pub struct BugReport {
     pub timestamp: DateTime<Utc>,
     pub category: BugCategory,
     pub links: Vec<BugLink>,
+    pub duplicate_of: Option<u64>,
+    pub assignee: Option<UserId>,
+    pub labels: Vec<String>,
+    pub thread_id: Option<ChannelId>,
+    pub affected_version: Option<String>,
+    pub fixed_in: Option<String>,
+    pub crash_log: Option<CrashLog>,
+    /// Number of times the crash of this bug was reported, including the original report
+    pub times_seen: u32,
+    /// Number of users who reported having this bug too
+    pub affected_users: u32,
 }
 
 #[derive(Debug, Clone)]
 pub struct PartialBugReport {
     pub bug_id: u64,
+    pub prefix: String,
     pub title: String,
     pub status: BugStatus,
     pub timestamp: DateTime<Utc>,
@@ -218,7 +363,8 @@ impl std::fmt::Display for PartialBugReport {
         };
         write!(
             f,
-            "EoA-{} — {}  ({})",
+            "{}-{} — {}  ({})",
+            self.prefix,
             self.bug_id,
             self.title,
             self.timestamp.format(format_str)
@@ -230,35 +376,351 @@ impl PartialBugReport {
     pub fn new(
         bug_id: u64,
         title: String,
-        status: String,
+        status: BugStatus,
         timestamp: NaiveDateTime,
         category: BugCategory,
+        prefix: String,
     ) -> Option<Self> {
         Some(Self {
             bug_id,
+            prefix,
             title,
-            status: status.parse().unwrap_or_default(),
+            status,
             timestamp: DateTime::from_utc(timestamp, Utc),
             category,
         })
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BugEvent {
+    Status,
+    Title,
+    Category,
+    Link,
+    Duplicate,
+    Assignee,
+    Label,
+    Version,
+    FixedIn,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ParseEventError;
+
+impl std::str::FromStr for BugEvent {
+    type Err = ParseEventError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use BugEvent::*;
+        Ok(match s {
+            "status" => Status,
+            "title" => Title,
+            "category" => Category,
+            "link" => Link,
+            "duplicate" => Duplicate,
+            "assignee" => Assignee,
+            "label" => Label,
+            "version" => Version,
+            "fixed_in" => FixedIn,
+            _ => return Err(ParseEventError),
+        })
+    }
+}
+
+impl BugEvent {
+    pub const fn as_str(self) -> &'static str {
+        use BugEvent::*;
+        match self {
+            Status => "status",
+            Title => "title",
+            Category => "category",
+            Link => "link",
+            Duplicate => "duplicate",
+            Assignee => "assignee",
+            Label => "label",
+            Version => "version",
+            FixedIn => "fixed_in",
+        }
+    }
+}
+
+/// How a user wants to be notified about the bugs they are subscribed to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationMode {
+    /// Every notification is sent right away
+    Instant,
+    /// Notifications are gathered in a daily DM
+    Digest,
+    /// Only status changes are sent
+    StatusOnly,
+    /// Only the bugs getting resolved or closed are sent
+    ResolveOnly,
+}
+
+impl Default for NotificationMode {
+    fn default() -> Self {
+        Self::Instant
+    }
+}
+
+impl std::str::FromStr for NotificationMode {
+    type Err = ParseEventError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use NotificationMode::*;
+        Ok(match s.to_lowercase().as_str() {
+            "instant" | "all" => Instant,
+            "digest" | "daily" => Digest,
+            "status" => StatusOnly,
+            "resolved" | "closed" | "resolve" | "close" => ResolveOnly,
+            _ => return Err(ParseEventError),
+        })
+    }
+}
+
+impl NotificationMode {
+    pub const ALL: [Self; 4] = [
+        Self::Instant,
+        Self::Digest,
+        Self::StatusOnly,
+        Self::ResolveOnly,
+    ];
+
+    pub const fn as_str(self) -> &'static str {
+        use NotificationMode::*;
+        match self {
+            Instant => "instant",
+            Digest => "digest",
+            StatusOnly => "status",
+            ResolveOnly => "resolved",
+        }
+    }
+
+    pub const fn description(self) -> &'static str {
+        use NotificationMode::*;
+        match self {
+            Instant => "every notification is sent right away",
+            Digest => "notifications are gathered in a daily DM",
+            StatusOnly => "only status changes are sent",
+            ResolveOnly => "only the bugs getting resolved or closed are sent",
+        }
+    }
+
+    /// Whether a user with this mode wants a kind of notification
+    pub fn accepts(self, kind: NotificationKind) -> bool {
+        match self {
+            Self::Instant | Self::Digest => true,
+            Self::StatusOnly => kind != NotificationKind::Update,
+            Self::ResolveOnly => kind == NotificationKind::Closing,
+        }
+    }
+}
+
+/// What a notification is about, to filter them with the [`NotificationMode`] of each user
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    /// Any other change to the bug: links, title, assignee...
+    Update,
+    /// A new status that is open
+    Status,
+    /// A new status that is not open, like `resolved` or `closed`
+    Closing,
+}
+
+impl NotificationKind {
+    /// Kind of the notification of a bug getting a new status
+    pub fn of_status(status: &BugStatus) -> Self {
+        if status.open {
+            Self::Status
+        } else {
+            Self::Closing
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BugHistoryEntry {
+    pub user_id: UserId,
+    pub event: BugEvent,
+    pub old_value: String,
+    pub new_value: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl std::fmt::Display for BugHistoryEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} <@{}> ",
+            self.timestamp.format("<t:%s:f>"),
+            self.user_id.0
+        )?;
+        match self.event {
+            BugEvent::Status => write!(
+                f,
+                "changed the status from `{}` to `{}`",
+                self.old_value, self.new_value
+            ),
+            BugEvent::Title => write!(
+                f,
+                "renamed the bug from \"{}\" to \"{}\"",
+                self.old_value, self.new_value
+            ),
+            BugEvent::Category => write!(
+                f,
+                "changed the edition from {} to {}",
+                self.old_value
+                    .parse::<BugCategory>()
+                    .map_or_else(|_| self.old_value.clone(), |c| c.to_string()),
+                self.new_value
+                    .parse::<BugCategory>()
+                    .map_or_else(|_| self.new_value.clone(), |c| c.to_string()),
+            ),
+            BugEvent::Link if self.old_value.is_empty() => {
+                write!(f, "added link {}", self.new_value)
+            }
+            BugEvent::Link => write!(f, "removed link {}", self.old_value),
+            BugEvent::Duplicate => {
+                write!(
+                    f,
+                    "marked the bug as a duplicate of bug #{}",
+                    self.new_value
+                )
+            }
+            BugEvent::Assignee if self.new_value.is_empty() => {
+                write!(f, "unassigned <@{}>", self.old_value)
+            }
+            BugEvent::Assignee => write!(f, "assigned the bug to <@{}>", self.new_value),
+            BugEvent::Label if self.old_value.is_empty() => {
+                write!(f, "added the label `{}`", self.new_value)
+            }
+            BugEvent::Label => write!(f, "removed the label `{}`", self.old_value),
+            BugEvent::Version if self.new_value.is_empty() => {
+                write!(f, "removed the affected version `{}`", self.old_value)
+            }
+            BugEvent::Version => write!(f, "set the affected version to `{}`", self.new_value),
+            BugEvent::FixedIn if self.new_value.is_empty() => {
+                write!(f, "removed the fix version `{}`", self.old_value)
+            }
+            BugEvent::FixedIn => write!(f, "marked the bug as fixed in `{}`", self.new_value),
+        }
+    }
+}
+
+async fn add_history_entry(
+    conn: &mut impl Queryable,
+    bug_id: u64,
+    user_id: UserId,
+    event: BugEvent,
+    old_value: &str,
+    new_value: &str,
+) -> Result<(), mysql_async::Error> {
+    conn.exec_drop(
+        formatcp!(
+            "INSERT INTO {} (bug_id, user_id, event, old_value, new_value) \
+VALUES (:bug_id, :user_id, :event, :old_value, :new_value)",
+            TABLE_BUG_REPORTS_HISTORY
+        ),
+        params! {
+            "bug_id" => bug_id,
+            "user_id" => user_id.0,
+            "event" => event.as_str(),
+            "old_value" => old_value,
+            "new_value" => new_value,
+        },
+    )
+    .await
+}
+
+pub async fn get_bug_history(
+    ctx: &Context,
+    bug_id: u64,
+    limit: u32,
+    page: u32,
+) -> Option<(Vec<BugHistoryEntry>, u32)> {
+    let mut conn = get_database_conn!(ctx);
+
+    let total: u32 = conn
+        .exec_first(
+            formatcp!(
+                "SELECT COUNT(history_id) FROM {} WHERE bug_id = :bug_id",
+                TABLE_BUG_REPORTS_HISTORY
+            ),
+            params! {
+                "bug_id" => bug_id
+            },
+        )
+        .await
+        .ok()??;
+
+    conn.exec_map(
+        formatcp!(
+            "SELECT user_id, event, old_value, new_value, timestamp FROM {} \
+WHERE bug_id = :bug_id ORDER BY timestamp ASC, history_id ASC LIMIT :limit OFFSET :offset",
+            TABLE_BUG_REPORTS_HISTORY
+        ),
+        params! {
+            "bug_id" => bug_id,
+            "limit" => limit,
+            "offset" => limit * page
+        },
+        |(user_id, event, old_value, new_value, timestamp): (
+            u64,
+            String,
+            String,
+            String,
+            NaiveDateTime,
+        )| {
+            Some(BugHistoryEntry {
+                user_id: UserId(user_id),
+                event: event.parse().ok()?,
+                old_value,
+                new_value,
+                timestamp: DateTime::from_utc(timestamp, Utc),
+            })
+        },
+    )
+    .await
+    .ok()
+    .map(|v| v.into_iter().flatten().collect())
+    .map(|v| (v, total))
+}
+
 pub async fn get_bug_from_id(ctx: &Context, bug_id: u64) -> Result<BugReport, CommandError> {
     let mut conn = get_database_conn!(ctx);
 
-    let (channel_id, message_id, title, status, timestamp, category): (
+    let (
+        guild_id,
+        channel_id,
+        message_id,
+        title,
+        status,
+        timestamp,
+        category,
+        duplicate_of,
+        assignee,
+        thread_id,
+        affected_version,
+        fixed_in,
+    ): (
+        u64,
         u64,
         u64,
         String,
         String,
         NaiveDateTime,
         String,
+        Option<u64>,
+        Option<u64>,
+        Option<u64>,
+        Option<String>,
+        Option<String>,
     ) = conn
         .exec_first(
             formatcp!(
-                "SELECT channel_id, message_id, title, status, timestamp, category \
-FROM {} WHERE bug_id = :bug_id",
+                "SELECT guild_id, channel_id, message_id, title, status, timestamp, category, \
+duplicate_of, assignee, thread_id, affected_version, fixed_in FROM {} WHERE bug_id = :bug_id",
                 TABLE_BUG_REPORTS
             ),
             params! {
@@ -281,82 +743,435 @@ FROM {} WHERE bug_id = :bug_id",
         )
         .await?;
 
+    let labels: Vec<String> = conn
+        .exec(
+            formatcp!(
+                "SELECT label FROM {} WHERE bug_id = :bug_id ORDER BY label ASC",
+                TABLE_BUG_REPORTS_LABELS
+            ),
+            params! {
+                "bug_id" => bug_id
+            },
+        )
+        .await?;
+
+    let crash_log = conn
+        .exec_first(
+            formatcp!(
+                "SELECT exception, suspected_mods, minecraft_version, forge_version, mod_version, \
+mod_frames FROM {} WHERE bug_id = :bug_id",
+                TABLE_BUG_REPORTS_CRASH_LOGS
+            ),
+            params! {
+                "bug_id" => bug_id
+            },
+        )
+        .await?
+        .map(crash_log_from_row);
+
+    let prefix = conn
+        .exec_first(
+            formatcp!(
+                "SELECT prefix FROM {} WHERE guild_id = :guild_id",
+                TABLE_BUG_TRACKERS
+            ),
+            params! {
+                "guild_id" => guild_id
+            },
+        )
+        .await?
+        .unwrap_or_else(|| DEFAULT_BUG_PREFIX.into());
+
+    let status = StatusDefinitions::load(&mut conn, Some(GuildId(guild_id)))
+        .await?
+        .resolve(guild_id, status);
+
     Ok(BugReport {
         bug_id,
+        guild_id: GuildId(guild_id),
+        prefix,
         channel_id: ChannelId(channel_id),
         message_id: MessageId(message_id),
         title,
-        status: status
-            .parse()
-            .expect("Expected a valid bug status from the database"),
+        status,
         timestamp: DateTime::from_utc(timestamp, Utc),
         category: category
             .parse()
             .expect("Expected a valid bug category from the database"),
         links,
+        duplicate_of,
+        assignee: assignee.map(UserId),
+        labels,
+        thread_id: thread_id.map(ChannelId),
+        affected_version,
+        fixed_in,
+        crash_log,
+        times_seen: get_times_seen(&mut conn, bug_id).await?,
+        affected_users: get_affected_users(&mut conn, bug_id).await?,
     })
 }
 
-pub async fn add_bug_report(
-    ctx: &Context,
-    msg: &Message,
-    title: String,
-    status: BugStatus,
-    category: BugCategory,
-) -> Result<u64, CommandError> {
+type CrashLogRow = (
+    Option<String>,
+    String,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    String,
+);
+
+/// Lists are stored as newline-separated text
+fn crash_log_from_row(
+    (exception, suspected_mods, minecraft_version, forge_version, mod_version, mod_frames): CrashLogRow,
+) -> CrashLog {
+    let split = |s: String| s.lines().map(str::to_string).collect();
+    CrashLog {
+        exception,
+        suspected_mods: split(suspected_mods),
+        minecraft_version,
+        forge_version,
+        mod_version,
+        mod_frames: split(mod_frames),
+    }
+}
+
+/// Stores the information extracted from the crash log of a bug report
+pub async fn set_crash_log(ctx: &Context, bug_id: u64, crash_log: &CrashLog) -> CommandResult {
     let mut conn = get_database_conn!(ctx);
 
     conn.exec_drop(
         formatcp!(
-            "INSERT INTO {} (channel_id, message_id, title, status, category) \
-VALUES (:channel_id, :message_id, :title, :status, :category)",
-            TABLE_BUG_REPORTS
+            "INSERT INTO {} (bug_id, exception, suspected_mods, minecraft_version, forge_version, \
+mod_version, mod_frames, signature) VALUES (:bug_id, :exception, :suspected_mods, \
+:minecraft_version, :forge_version, :mod_version, :mod_frames, :signature) \
+ON DUPLICATE KEY UPDATE exception = :exception, suspected_mods = :suspected_mods, \
+minecraft_version = :minecraft_version, forge_version = :forge_version, \
+mod_version = :mod_version, mod_frames = :mod_frames, signature = :signature",
+            TABLE_BUG_REPORTS_CRASH_LOGS
         ),
         params! {
-            "channel_id" => msg.channel_id.0,
-            "message_id" => msg.id.0,
-            "title" => title,
-            "status" => status.as_str(),
-            "category" => category.as_str(),
+            "bug_id" => bug_id,
+            "exception" => &crash_log.exception,
+            "suspected_mods" => crash_log.suspected_mods.join("\n"),
+            "minecraft_version" => &crash_log.minecraft_version,
+            "forge_version" => &crash_log.forge_version,
+            "mod_version" => &crash_log.mod_version,
+            "mod_frames" => crash_log.mod_frames.join("\n"),
+            "signature" => crash_log.signature(),
         },
     )
     .await?;
 
-    if let Err(e) = msg.react(ctx, status.reaction()).await {
-        println!("Could not add reaction to bug report: {}", e);
-    }
+    Ok(())
+}
 
-    conn.query_first(formatcp!("SELECT MAX(bug_id) FROM {}", TABLE_BUG_REPORTS))
-        .await?
-        .ok_or_else(|| CommandError::from("Could not get newest bug id!"))
+/// Number of users who reported having a bug too
+async fn get_affected_users(conn: &mut Conn, bug_id: u64) -> Result<u32, mysql_async::Error> {
+    let count: Option<u32> = conn
+        .exec_first(
+            formatcp!(
+                "SELECT COUNT(*) FROM {} WHERE bug_id = :bug_id",
+                TABLE_BUG_REPORTS_AFFECTED_USERS
+            ),
+            params! {
+                "bug_id" => bug_id
+            },
+        )
+        .await?;
+
+    Ok(count.unwrap_or_default())
 }
 
-pub async fn get_bug_list(
+/// Records that a user has a bug too, each user being counted once.
+///
+/// Returns the number of affected users, or `None` if the user was already counted.
+pub async fn add_affected_user(
     ctx: &Context,
-    status: Option<BugStatus>,
-    limit: u32,
-    display_order: BugOrder,
-    category: Option<BugCategory>,
-    page: u32,
-) -> Option<(Vec<PartialBugReport>, u32)> {
+    bug_id: u64,
+    user_id: UserId,
+) -> Result<Option<u32>, CommandError> {
     let mut conn = get_database_conn!(ctx);
 
-    println!("getting total...");
-
-    let total: u32 = conn
-        .query_first(format!(
-            "SELECT COUNT(bug_id) FROM {} WHERE {} {category}",
-            TABLE_BUG_REPORTS,
-            if let Some(status) = status {
-                format!("status = '{}'", status.as_str())
-            } else {
-                "status != 'resolved' AND status != 'closed' AND status != 'forgevanilla'".into()
-            },
-            category = if let Some(c) = category {
-                format!("AND category = '{}'", c.as_str())
-            } else {
-                "".into()
-            },
+    conn.exec_drop(
+        formatcp!(
+            "INSERT IGNORE INTO {} (bug_id, user_id) VALUES (:bug_id, :user_id)",
+            TABLE_BUG_REPORTS_AFFECTED_USERS
+        ),
+        params! {
+            "bug_id" => bug_id,
+            "user_id" => user_id.0
+        },
+    )
+    .await?;
+
+    if conn.affected_rows() == 0 {
+        return Ok(None);
+    }
+    Ok(Some(get_affected_users(&mut conn, bug_id).await?))
+}
+
+/// Number of times the crash of a bug was reported, including the original report
+async fn get_times_seen(conn: &mut Conn, bug_id: u64) -> Result<u32, mysql_async::Error> {
+    let sightings: Option<u32> = conn
+        .exec_first(
+            formatcp!(
+                "SELECT COUNT(*) FROM {} WHERE bug_id = :bug_id",
+                TABLE_BUG_REPORTS_CRASH_SIGHTINGS
+            ),
+            params! {
+                "bug_id" => bug_id
+            },
+        )
+        .await?;
+
+    Ok(sightings.unwrap_or_default() + 1)
+}
+
+/// Gets the oldest open bug with the given [crash signature][CrashLog::signature]
+pub async fn find_crash_duplicate(
+    ctx: &Context,
+    guild_id: GuildId,
+    signature: &str,
+) -> Result<Option<PartialBugReport>, CommandError> {
+    let mut conn = get_database_conn!(ctx);
+
+    let bugs = get_partial_bugs(
+        &mut conn,
+        formatcp!(
+            "SELECT {} FROM {} WHERE guild_id = :guild_id AND {} \
+AND bug_id IN (SELECT bug_id FROM {} WHERE signature = :signature) ORDER BY bug_id ASC LIMIT 1",
+            PARTIAL_BUG_COLUMNS,
+            TABLE_BUG_REPORTS,
+            OPEN_STATUS,
+            TABLE_BUG_REPORTS_CRASH_LOGS
+        ),
+        params! {
+            "guild_id" => guild_id.0,
+            "signature" => signature
+        },
+        Some(guild_id),
+    )
+    .await?;
+
+    Ok(bugs.into_iter().next())
+}
+
+/// Records another report of the crash of a bug, and returns the number of times
+/// it was reported. Reporting the same message twice only counts once.
+pub async fn add_crash_sighting(
+    ctx: &Context,
+    bug_id: u64,
+    msg: &Message,
+) -> Result<u32, CommandError> {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_drop(
+        formatcp!(
+            "INSERT IGNORE INTO {} (bug_id, channel_id, message_id, user_id) \
+VALUES (:bug_id, :channel_id, :message_id, :user_id)",
+            TABLE_BUG_REPORTS_CRASH_SIGHTINGS
+        ),
+        params! {
+            "bug_id" => bug_id,
+            "channel_id" => msg.channel_id.0,
+            "message_id" => msg.id.0,
+            "user_id" => msg.author.id.0,
+        },
+    )
+    .await?;
+
+    Ok(get_times_seen(&mut conn, bug_id).await?)
+}
+
+pub async fn add_bug_report(
+    ctx: &Context,
+    guild_id: GuildId,
+    msg: &Message,
+    title: String,
+    status: &BugStatus,
+    category: BugCategory,
+) -> Result<u64, CommandError> {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_drop(
+        formatcp!(
+            "INSERT INTO {} (guild_id, channel_id, message_id, title, status, category, \
+report_content) VALUES (:guild_id, :channel_id, :message_id, :title, :status, :category, \
+:report_content)",
+            TABLE_BUG_REPORTS
+        ),
+        params! {
+            "guild_id" => guild_id.0,
+            "channel_id" => msg.channel_id.0,
+            "message_id" => msg.id.0,
+            "title" => title,
+            "report_content" => &msg.content,
+            "status" => status.as_str(),
+            "category" => category.as_str(),
+        },
+    )
+    .await?;
+
+    if let Err(e) = msg.react(ctx, status.reaction()).await {
+        println!("Could not add reaction to bug report: {}", e);
+    }
+
+    conn.query_first(formatcp!("SELECT MAX(bug_id) FROM {}", TABLE_BUG_REPORTS))
+        .await?
+        .ok_or_else(|| CommandError::from("Could not get newest bug id!"))
+}
+
+/// Stores the discussion thread of a bug report
+pub async fn set_bug_thread(
+    ctx: &Context,
+    bug_id: u64,
+    thread_id: ChannelId,
+) -> Result<(), CommandError> {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_drop(
+        formatcp!(
+            "UPDATE {} SET thread_id = :thread_id WHERE bug_id = :bug_id",
+            TABLE_BUG_REPORTS
+        ),
+        params! {
+            "thread_id" => thread_id.0,
+            "bug_id" => bug_id,
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Inserts already existing bug reports, keeping their timestamp, status, category and links.
+///
+/// The bug ids of the reports are ignored, and the new bug ids are returned in order.
+/// The reports are all imported, or none of them if one fails.
+pub async fn import_bug_reports(
+    ctx: &Context,
+    bugs: &[BugReport],
+) -> Result<Vec<u64>, CommandError> {
+    let mut conn = get_database_conn!(ctx);
+    let mut transaction = conn.start_transaction(TxOpts::default()).await?;
+
+    let mut bug_ids = Vec::with_capacity(bugs.len());
+    for bug in bugs {
+        transaction
+            .exec_drop(
+                formatcp!(
+                    "INSERT INTO {} (guild_id, channel_id, message_id, title, status, timestamp, \
+category) VALUES (:guild_id, :channel_id, :message_id, :title, :status, :timestamp, :category)",
+                    TABLE_BUG_REPORTS
+                ),
+                params! {
+                    "guild_id" => bug.guild_id.0,
+                    "channel_id" => bug.channel_id.0,
+                    "message_id" => bug.message_id.0,
+                    "title" => &bug.title,
+                    "status" => bug.status.as_str(),
+                    "timestamp" => bug.timestamp.naive_utc(),
+                    "category" => bug.category.as_str(),
+                },
+            )
+            .await?;
+        let bug_id = transaction
+            .last_insert_id()
+            .ok_or_else(|| CommandError::from("Could not get imported bug id!"))?;
+
+        transaction
+            .exec_batch(
+                formatcp!(
+                    "INSERT INTO {} (bug_id, link_url, link_title) VALUES (:bug_id, :url, :title)",
+                    TABLE_BUG_REPORTS_LINKS
+                ),
+                bug.links.iter().map(|link| {
+                    params! {
+                        "bug_id" => bug_id,
+                        "url" => &link.url,
+                        "title" => &link.title,
+                    }
+                }),
+            )
+            .await?;
+
+        bug_ids.push(bug_id);
+    }
+
+    transaction.commit().await?;
+
+    Ok(bug_ids)
+}
+
+/// Filters for [`get_bug_list`]
+///
+/// With no status, only the bugs with an open status in their tracker are listed.
+#[derive(Debug, Clone, Default)]
+pub struct BugFilter {
+    /// With no guild, the bugs of all the guilds are listed
+    pub guild_id: Option<GuildId>,
+    pub status: Option<BugStatus>,
+    pub category: Option<BugCategory>,
+    pub assignee: Option<UserId>,
+    /// Labels must be [normalized][normalize_label]
+    pub labels: Vec<String>,
+    /// Version must be [normalized][normalize_version]
+    pub version: Option<String>,
+}
+
+impl BugFilter {
+    fn where_clause(&self) -> String {
+        // status names are normalized, and safe to use in the query
+        let mut clause = if let Some(status) = &self.status {
+            format!("status = '{}'", status.as_str())
+        } else {
+            OPEN_STATUS.into()
+        };
+        if let Some(guild_id) = self.guild_id {
+            clause.push_str(&format!(" AND guild_id = {}", guild_id.0));
+        }
+        if let Some(category) = self.category {
+            clause.push_str(&format!(" AND category = '{}'", category.as_str()));
+        }
+        if let Some(assignee) = self.assignee {
+            clause.push_str(&format!(" AND assignee = {}", assignee.0));
+        }
+        if let Some(version) = &self.version {
+            clause.push_str(&format!(
+                " AND (affected_version = '{0}' OR fixed_in = '{0}')",
+                version
+            ));
+        }
+        for label in &self.labels {
+            clause.push_str(&format!(
+                " AND bug_id IN (SELECT bug_id FROM {} WHERE label = '{}')",
+                TABLE_BUG_REPORTS_LABELS, label
+            ));
+        }
+        clause
+    }
+}
+
+pub async fn get_bug_list(
+    ctx: &Context,
+    filter: &BugFilter,
+    limit: u32,
+    display_order: BugOrder,
+    page: u32,
+) -> Option<(Vec<PartialBugReport>, u32)> {
+    let mut conn = get_database_conn!(ctx);
+
+    let where_clause = filter.where_clause();
+    let statuses = StatusDefinitions::load(&mut conn, filter.guild_id)
+        .await
+        .ok()?;
+
+    println!("getting total...");
+
+    let total: u32 = conn
+        .query_first(format!(
+            "SELECT COUNT(bug_id) FROM {} WHERE {}",
+            TABLE_BUG_REPORTS, where_clause
         ))
         .await
         .ok()??;
@@ -365,49 +1180,24 @@ pub async fn get_bug_list(
 
     conn.exec_map(
         format!(
-            "SELECT bug_id, title, status, timestamp, category FROM {} \
-WHERE {} {category} ORDER BY {ordering} LIMIT :limit OFFSET :offset",
+            "SELECT {} FROM {} WHERE {} ORDER BY {ordering} LIMIT :limit OFFSET :offset",
+            PARTIAL_BUG_COLUMNS,
             TABLE_BUG_REPORTS,
-            if let Some(status) = status {
-                format!("status = '{}'", status.as_str())
-            } else {
-                "status != 'resolved' AND status != 'closed' AND status != 'forgevanilla'".into()
-            },
-            category = if let Some(c) = category {
-                format!("AND category = '{}'", c.as_str())
-            } else {
-                "".into()
-            },
+            where_clause,
             ordering = match display_order {
                 BugOrder::Chronological(false) | BugOrder::None => "timestamp DESC",
                 BugOrder::Chronological(true) => "timestamp ASC",
-                BugOrder::Priority(false) => "status DESC, timestamp DESC",
-                BugOrder::Priority(true) => "status ASC, timestamp DESC",
+                BugOrder::Priority(false) => formatcp!("{} DESC, timestamp DESC", STATUS_WEIGHT),
+                BugOrder::Priority(true) => formatcp!("{} ASC, timestamp DESC", STATUS_WEIGHT),
+                BugOrder::Affected(false) => formatcp!("{} DESC, timestamp DESC", AFFECTED_USERS),
+                BugOrder::Affected(true) => formatcp!("{} ASC, timestamp DESC", AFFECTED_USERS),
             },
         ),
         params! {
             "limit" => limit,
             "offset" => limit * page
         },
-        |(bug_id, title, status, timestamp, category): (
-            u64,
-            String,
-            String,
-            NaiveDateTime,
-            String,
-        )| {
-            PartialBugReport::new(
-                bug_id,
-                title,
-                status
-                    .parse()
-                    .expect("Expected a valid bug status from the database"),
-                timestamp,
-                category
-                    .parse()
-                    .expect("Expected a valid bug category from the database"),
-            )
-        },
+        |row| partial_bug_from_row(&statuses, row),
     )
     .await
     .ok()
@@ -415,97 +1205,1586 @@ WHERE {} {category} ORDER BY {ordering} LIMIT :limit OFFSET :offset",
     .map(|v| (v, total))
 }
 
-pub async fn change_bug_status(
+/// Gets the full bug reports matching the filter, in chronological order.
+///
+/// Unlike [`get_bug_list`], the results are not paginated.
+pub async fn get_bug_reports(
+    ctx: &Context,
+    filter: &BugFilter,
+) -> Result<Vec<BugReport>, CommandError> {
+    let mut conn = get_database_conn!(ctx);
+
+    let where_clause = filter.where_clause();
+
+    let mut bugs: Vec<BugReport> = conn
+        .query_map(
+            format!(
+                "SELECT bug_id, channel_id, message_id, title, status, timestamp, category, \
+duplicate_of, assignee, thread_id, affected_version, fixed_in FROM {} WHERE {} \
+ORDER BY bug_id ASC",
+                TABLE_BUG_REPORTS, where_clause
+            ),
+            |(
+                bug_id,
+                channel_id,
+                message_id,
+                title,
+                status,
+                timestamp,
+                category,
+                duplicate_of,
+                assignee,
+                thread_id,
+                affected_version,
+                fixed_in,
+            ): (
+                u64,
+                u64,
+                u64,
+                String,
+                String,
+                NaiveDateTime,
+                String,
+                Option<u64>,
+                Option<u64>,
+                Option<u64>,
+                Option<String>,
+                Option<String>,
+            )| BugReport {
+                bug_id,
+                guild_id: GuildId(0),
+                prefix: DEFAULT_BUG_PREFIX.into(),
+                channel_id: ChannelId(channel_id),
+                message_id: MessageId(message_id),
+                title,
+                status: BugStatus::unknown(status),
+                timestamp: DateTime::from_utc(timestamp, Utc),
+                category: category
+                    .parse()
+                    .expect("Expected a valid bug category from the database"),
+                links: Vec::new(),
+                duplicate_of,
+                assignee: assignee.map(UserId),
+                labels: Vec::new(),
+                thread_id: thread_id.map(ChannelId),
+                affected_version,
+                fixed_in,
+                crash_log: None,
+                times_seen: 1,
+                affected_users: 0,
+            },
+        )
+        .await?;
+
+    // the guild and prefix do not fit in the tuple of the first query
+    let trackers: Vec<(u64, u64, String)> = conn
+        .query(format!(
+            "SELECT bug_id, guild_id, COALESCE((SELECT prefix FROM {0} \
+WHERE {0}.guild_id = {1}.guild_id), '{2}') FROM {1} WHERE {3}",
+            TABLE_BUG_TRACKERS, TABLE_BUG_REPORTS, DEFAULT_BUG_PREFIX, where_clause
+        ))
+        .await?;
+
+    let links: Vec<(u64, BugLink)> = conn
+        .query_map(
+            format!(
+                "SELECT bug_id, link_id, link_url, link_title FROM {} \
+WHERE bug_id IN (SELECT bug_id FROM {} WHERE {}) ORDER BY link_id ASC",
+                TABLE_BUG_REPORTS_LINKS, TABLE_BUG_REPORTS, where_clause
+            ),
+            |(bug_id, id, url, title)| (bug_id, BugLink { id, url, title }),
+        )
+        .await?;
+
+    let labels: Vec<(u64, String)> = conn
+        .query(format!(
+            "SELECT bug_id, label FROM {} \
+WHERE bug_id IN (SELECT bug_id FROM {} WHERE {}) ORDER BY label ASC",
+            TABLE_BUG_REPORTS_LABELS, TABLE_BUG_REPORTS, where_clause
+        ))
+        .await?;
+
+    let crash_logs: Vec<(u64, CrashLog)> = conn
+        .query_map(
+            format!(
+                "SELECT bug_id, exception, suspected_mods, minecraft_version, forge_version, \
+mod_version, mod_frames FROM {} WHERE bug_id IN (SELECT bug_id FROM {} WHERE {})",
+                TABLE_BUG_REPORTS_CRASH_LOGS, TABLE_BUG_REPORTS, where_clause
+            ),
+            |(
+                bug_id,
+                exception,
+                suspected_mods,
+                minecraft_version,
+                forge_version,
+                mod_version,
+                mod_frames,
+            )| {
+                (
+                    bug_id,
+                    crash_log_from_row((
+                        exception,
+                        suspected_mods,
+                        minecraft_version,
+                        forge_version,
+                        mod_version,
+                        mod_frames,
+                    )),
+                )
+            },
+        )
+        .await?;
+
+    let crash_sightings: Vec<(u64, u32)> = conn
+        .query(format!(
+            "SELECT bug_id, COUNT(*) FROM {} \
+WHERE bug_id IN (SELECT bug_id FROM {} WHERE {}) GROUP BY bug_id",
+            TABLE_BUG_REPORTS_CRASH_SIGHTINGS, TABLE_BUG_REPORTS, where_clause
+        ))
+        .await?;
+
+    let affected_users: Vec<(u64, u32)> = conn
+        .query(format!(
+            "SELECT bug_id, COUNT(*) FROM {} \
+WHERE bug_id IN (SELECT bug_id FROM {} WHERE {}) GROUP BY bug_id",
+            TABLE_BUG_REPORTS_AFFECTED_USERS, TABLE_BUG_REPORTS, where_clause
+        ))
+        .await?;
+
+    let statuses = StatusDefinitions::load(&mut conn, filter.guild_id).await?;
+
+    // bugs are sorted by id, so they can be found with a binary search
+    for (bug_id, guild_id, prefix) in trackers {
+        if let Ok(i) = bugs.binary_search_by_key(&bug_id, |bug| bug.bug_id) {
+            let bug = &mut bugs[i];
+            bug.guild_id = GuildId(guild_id);
+            bug.prefix = prefix;
+            bug.status = statuses.resolve(guild_id, std::mem::take(&mut bug.status.name));
+        }
+    }
+    for (bug_id, link) in links {
+        if let Ok(i) = bugs.binary_search_by_key(&bug_id, |bug| bug.bug_id) {
+            bugs[i].links.push(link);
+        }
+    }
+    for (bug_id, label) in labels {
+        if let Ok(i) = bugs.binary_search_by_key(&bug_id, |bug| bug.bug_id) {
+            bugs[i].labels.push(label);
+        }
+    }
+    for (bug_id, crash_log) in crash_logs {
+        if let Ok(i) = bugs.binary_search_by_key(&bug_id, |bug| bug.bug_id) {
+            bugs[i].crash_log = Some(crash_log);
+        }
+    }
+    for (bug_id, sightings) in crash_sightings {
+        if let Ok(i) = bugs.binary_search_by_key(&bug_id, |bug| bug.bug_id) {
+            bugs[i].times_seen += sightings;
+        }
+    }
+    for (bug_id, count) in affected_users {
+        if let Ok(i) = bugs.binary_search_by_key(&bug_id, |bug| bug.bug_id) {
+            bugs[i].affected_users = count;
+        }
+    }
+
+    Ok(bugs)
+}
+
+/// Bug ids and titles for the autocompletion of slash command options.
+///
+/// Bugs are matched by id prefix if `partial` is a bug id, or by title otherwise.
+pub async fn autocomplete_bugs(
+    ctx: &Context,
+    tracker: &BugTracker,
+    partial: &str,
+    limit: u32,
+) -> Vec<(u64, String)> {
+    let mut conn = get_database_conn!(ctx);
+
+    let partial = partial.trim();
+    let id_prefix = tracker.strip_prefix(partial);
+
+    let res = if !id_prefix.is_empty() && id_prefix.bytes().all(|b| b.is_ascii_digit()) {
+        conn.exec(
+            formatcp!(
+                "SELECT bug_id, title FROM {} WHERE guild_id = :guild_id \
+AND CAST(bug_id AS CHAR) LIKE :pattern ORDER BY bug_id DESC LIMIT :limit",
+                TABLE_BUG_REPORTS
+            ),
+            params! {
+                "guild_id" => tracker.guild_id.0,
+                "pattern" => format!("{}%", id_prefix),
+                "limit" => limit,
+            },
+        )
+        .await
+    } else {
+        let escaped = partial
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        conn.exec(
+            formatcp!(
+                "SELECT bug_id, title FROM {} WHERE guild_id = :guild_id AND title LIKE :pattern \
+ORDER BY {} DESC, timestamp DESC LIMIT :limit",
+                TABLE_BUG_REPORTS,
+                OPEN_STATUS
+            ),
+            params! {
+                "guild_id" => tracker.guild_id.0,
+                "pattern" => format!("%{}%", escaped),
+                "limit" => limit,
+            },
+        )
+        .await
+    };
+
+    res.unwrap_or_default()
+}
+
+/// Full-text search over bug titles, report contents and link titles.
+///
+/// Results are ranked by relevance, title matches weighing twice as much as
+/// the report content or the link titles.
+pub async fn search_bugs(
+    ctx: &Context,
+    guild_id: GuildId,
+    terms: &str,
+    limit: u32,
+    page: u32,
+) -> Option<(Vec<PartialBugReport>, u32)> {
+    let mut conn = get_database_conn!(ctx);
+
+    let statuses = StatusDefinitions::load(&mut conn, Some(guild_id))
+        .await
+        .ok()?;
+
+    let total: u32 = conn
+        .exec_first(
+            formatcp!(
+                "SELECT COUNT(t1.bug_id) FROM {TABLE_BUG_REPORTS} AS t1 \
+LEFT JOIN (SELECT bug_id, SUM(MATCH(link_title) AGAINST (:terms)) AS score \
+FROM {TABLE_BUG_REPORTS_LINKS} GROUP BY bug_id) AS t2 ON t1.bug_id = t2.bug_id \
+WHERE t1.guild_id = :guild_id \
+AND (MATCH(t1.title, t1.report_content) AGAINST (:terms) > 0 OR t2.score > 0)"
+            ),
+            params! {
+                "guild_id" => guild_id.0,
+                "terms" => terms
+            },
+        )
+        .await
+        .ok()??;
+
+    conn.exec_map(
+        formatcp!(
+            "SELECT t1.bug_id, t1.title, t1.status, t1.timestamp, t1.category, \
+COALESCE(t3.prefix, '{DEFAULT_BUG_PREFIX}'), t1.guild_id FROM {TABLE_BUG_REPORTS} AS t1 \
+LEFT JOIN (SELECT bug_id, SUM(MATCH(link_title) AGAINST (:terms)) AS score \
+FROM {TABLE_BUG_REPORTS_LINKS} GROUP BY bug_id) AS t2 ON t1.bug_id = t2.bug_id \
+LEFT JOIN {TABLE_BUG_TRACKERS} AS t3 ON t1.guild_id = t3.guild_id \
+WHERE t1.guild_id = :guild_id \
+AND (MATCH(t1.title, t1.report_content) AGAINST (:terms) > 0 OR t2.score > 0) \
+ORDER BY 2 * MATCH(t1.title) AGAINST (:terms) \
++ MATCH(t1.title, t1.report_content) AGAINST (:terms) \
++ COALESCE(t2.score, 0) DESC, t1.timestamp DESC \
+LIMIT :limit OFFSET :offset"
+        ),
+        params! {
+            "guild_id" => guild_id.0,
+            "terms" => terms,
+            "limit" => limit,
+            "offset" => limit * page
+        },
+        |row| partial_bug_from_row(&statuses, row),
+    )
+    .await
+    .ok()
+    .map(|v| v.into_iter().flatten().collect())
+    .map(|v| (v, total))
+}
+
+/// Changes the status of a bug to a status of its tracker, and returns the previous one
+pub async fn change_bug_status(
+    ctx: &Context,
+    bug_id: u64,
+    new_status: &BugStatus,
+    user_id: UserId,
+) -> Result<BugStatus, CommandError> {
+    let mut conn = get_database_conn!(ctx);
+
+    let (old_status_string, guild_id, channel_id, msg_id): (String, u64, u64, u64) = conn
+        .exec_first(
+            formatcp!(
+                "SELECT status, guild_id, channel_id, message_id FROM {} \
+WHERE bug_id = :bug_id LIMIT 1",
+                TABLE_BUG_REPORTS
+            ),
+            params! {
+                "bug_id" => bug_id
+            },
+        )
+        .await?
+        .ok_or_else(|| CommandError::from("Could not find bug in database"))?;
+
+    let old_status = StatusDefinitions::load(&mut conn, Some(GuildId(guild_id)))
+        .await?
+        .resolve(guild_id, old_status_string);
+
+    let mut transaction = conn.start_transaction(TxOpts::default()).await?;
+
+    transaction
+        .exec_drop(
+            formatcp!(
+                "UPDATE {} SET status = :status WHERE bug_id = :bug_id",
+                TABLE_BUG_REPORTS
+            ),
+            params! {
+                "status" => new_status.as_str(),
+                "bug_id" => bug_id
+            },
+        )
+        .await?;
+
+    if new_status.name != STATUS_NEEDS_INFO {
+        transaction
+            .exec_drop(
+                formatcp!(
+                    "DELETE FROM {} WHERE bug_id = :bug_id",
+                    TABLE_BUG_REPORTS_INFO_REQUESTS
+                ),
+                params! {
+                    "bug_id" => bug_id
+                },
+            )
+            .await?;
+    }
+
+    if &old_status != new_status {
+        add_history_entry(
+            &mut transaction,
+            bug_id,
+            user_id,
+            BugEvent::Status,
+            old_status.as_str(),
+            new_status.as_str(),
+        )
+        .await?;
+    }
+
+    transaction.commit().await?;
+
+    match ChannelId(channel_id).message(ctx, MessageId(msg_id)).await {
+        Ok(msg) => {
+            if let Err(e) = msg.delete_reaction_emoji(ctx, old_status.reaction()).await {
+                println!("Could not remove reaction from bug report: {}", e);
+            }
+            if let Err(e) = msg.react(ctx, new_status.reaction()).await {
+                println!("Could not add reaction to bug report: {}", e);
+            }
+        }
+        Err(e) => println!("Could not get message for bug report: {}", e),
+    }
+
+    Ok(old_status)
+}
+
+pub async fn add_link(
+    ctx: &Context,
+    bug_id: u64,
+    link_url: &str,
+    link_title: &str,
+    user_id: UserId,
+) -> Option<u64> {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_drop(
+        formatcp!(
+            "INSERT INTO {} (bug_id, link_url, link_title) VALUES (:bug_id, :link_url, :link_title)",
+            TABLE_BUG_REPORTS_LINKS
+        ),
+        params! {
+            "bug_id" => bug_id,
+            "link_url" => link_url,
+            "link_title" => link_title
+        },
+    )
+    .await.ok()?;
+
+    let link_id: u64 = conn
+        .exec_first(
+            formatcp!(
+                "SELECT MAX(link_id) FROM {} WHERE bug_id = :bug_id",
+                TABLE_BUG_REPORTS_LINKS
+            ),
+            params! {
+                "bug_id" => bug_id
+            },
+        )
+        .await
+        .ok()??;
+
+    // the link is added even if its history entry is not
+    if let Err(e) = add_history_entry(
+        &mut conn,
+        bug_id,
+        user_id,
+        BugEvent::Link,
+        "",
+        &format!("#{}: {}", link_id, link_title),
+    )
+    .await
+    {
+        println!(
+            "=== ERROR ===\nCould not record the link {} of bug {} in its history: {}\n=== END ===",
+            link_id, bug_id, e
+        );
+    }
+
+    Some(link_id)
+}
+
+pub async fn remove_link(
+    ctx: &Context,
+    bug_id: u64,
+    link_num: u64,
+    user_id: UserId,
+) -> CommandResult {
+    let mut conn = get_database_conn!(ctx);
+    let mut transaction = conn.start_transaction(TxOpts::default()).await?;
+
+    transaction
+        .exec_drop(
+            formatcp!(
+                "DELETE FROM {} WHERE bug_id = :bug_id AND link_id = :link_id",
+                TABLE_BUG_REPORTS_LINKS
+            ),
+            params! {
+                "bug_id" => bug_id,
+                "link_id" => link_num
+            },
+        )
+        .await?;
+
+    if transaction.affected_rows() != 0 {
+        add_history_entry(
+            &mut transaction,
+            bug_id,
+            user_id,
+            BugEvent::Link,
+            &format!("#{}", link_num),
+            "",
+        )
+        .await?;
+    }
+
+    transaction.commit().await?;
+
+    Ok(())
+}
+
+/// A bug mirrored as an issue of an external issue tracker
+#[derive(Debug, Clone)]
+pub struct MirroredIssue {
+    pub bug_id: u64,
+    pub issue_number: u64,
+    /// Last known state of the issue
+    pub state: IssueState,
+    /// Raw name of the current status of the bug
+    pub status: String,
+}
+
+pub async fn set_mirrored_issue(
+    ctx: &Context,
+    bug_id: u64,
+    issue_number: u64,
+    state: IssueState,
+) -> CommandResult {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_drop(
+        formatcp!(
+            "REPLACE INTO {} (bug_id, issue_number, state) VALUES (:bug_id, :issue_number, :state)",
+            TABLE_BUG_REPORTS_ISSUES
+        ),
+        params! {
+            "bug_id" => bug_id,
+            "issue_number" => issue_number,
+            "state" => state.as_str()
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Gets the bugs of a guild mirrored as issues
+pub async fn get_mirrored_issues(
+    ctx: &Context,
+    guild_id: GuildId,
+) -> Result<Vec<MirroredIssue>, CommandError> {
+    let mut conn = get_database_conn!(ctx);
+
+    Ok(conn
+        .exec_map(
+            formatcp!(
+                "SELECT i.bug_id, i.issue_number, i.state, b.status FROM {} i \
+JOIN {} b ON b.bug_id = i.bug_id WHERE b.guild_id = :guild_id",
+                TABLE_BUG_REPORTS_ISSUES,
+                TABLE_BUG_REPORTS
+            ),
+            params! {
+                "guild_id" => guild_id.0
+            },
+            |(bug_id, issue_number, state, status): (u64, u64, String, String)| MirroredIssue {
+                bug_id,
+                issue_number,
+                state: state.parse().unwrap_or(IssueState::Open),
+                status,
+            },
+        )
+        .await?)
+}
+
+pub async fn change_title(
+    ctx: &Context,
+    bug_id: u64,
+    new_title: &str,
+    user_id: UserId,
+) -> CommandResult {
+    let mut conn = get_database_conn!(ctx);
+
+    let old_title: String = conn
+        .exec_first(
+            formatcp!(
+                "SELECT title FROM {} WHERE bug_id = :bug_id",
+                TABLE_BUG_REPORTS
+            ),
+            params! {
+                "bug_id" => bug_id
+            },
+        )
+        .await?
+        .ok_or_else(|| CommandError::from("Bug report does not exist!"))?;
+
+    let mut transaction = conn.start_transaction(TxOpts::default()).await?;
+
+    transaction
+        .exec_drop(
+            formatcp!(
+                "UPDATE {} SET title = :new_title WHERE bug_id = :bug_id",
+                TABLE_BUG_REPORTS
+            ),
+            params! {
+                "new_title" => new_title,
+                "bug_id" => bug_id
+            },
+        )
+        .await?;
+
+    add_history_entry(
+        &mut transaction,
+        bug_id,
+        user_id,
+        BugEvent::Title,
+        &old_title,
+        new_title,
+    )
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(())
+}
+
+/// Maximum length of a bug label
+pub const MAX_LABEL_LENGTH: usize = 32;
+
+/// Normalizes a label to lowercase, with spaces replaced by dashes.
+///
+/// Returns `None` if the label is empty, too long, or contains characters
+/// other than ASCII alphanumerics, `-` and `_`.
+pub fn normalize_label(label: &str) -> Option<String> {
+    let label = label.trim().to_ascii_lowercase().replace(' ', "-");
+    (!label.is_empty()
+        && label.len() <= MAX_LABEL_LENGTH
+        && label
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+    .then(|| label)
+}
+
+/// Adds a [normalized][normalize_label] label to a bug.
+///
+/// Returns `false` if the bug already had this label, and an error if the bug does not exist.
+pub async fn add_label(
+    ctx: &Context,
+    bug_id: u64,
+    label: &str,
+    user_id: UserId,
+) -> Result<bool, CommandError> {
+    let mut conn = get_database_conn!(ctx);
+
+    let exists: bool = conn
+        .exec_first(
+            formatcp!(
+                "SELECT EXISTS(SELECT label_id FROM {} WHERE bug_id = :bug_id AND label = :label)",
+                TABLE_BUG_REPORTS_LABELS
+            ),
+            params! {
+                "bug_id" => bug_id,
+                "label" => label
+            },
+        )
+        .await?
+        .unwrap_or_default();
+    if exists {
+        return Ok(false);
+    }
+
+    let mut transaction = conn.start_transaction(TxOpts::default()).await?;
+
+    transaction
+        .exec_drop(
+            formatcp!(
+            "INSERT INTO {} (bug_id, label) SELECT bug_id, :label FROM {} WHERE bug_id = :bug_id",
+            TABLE_BUG_REPORTS_LABELS,
+            TABLE_BUG_REPORTS
+        ),
+            params! {
+                "bug_id" => bug_id,
+                "label" => label
+            },
+        )
+        .await?;
+    if transaction.affected_rows() == 0 {
+        return Err(CommandError::from("Could not find bug in database"));
+    }
+
+    add_history_entry(
+        &mut transaction,
+        bug_id,
+        user_id,
+        BugEvent::Label,
+        "",
+        label,
+    )
+    .await?;
+    transaction.commit().await?;
+
+    Ok(true)
+}
+
+/// Removes a label from a bug.
+///
+/// Returns `false` if the bug did not have this label.
+pub async fn remove_label(
+    ctx: &Context,
+    bug_id: u64,
+    label: &str,
+    user_id: UserId,
+) -> Result<bool, CommandError> {
+    let mut conn = get_database_conn!(ctx);
+    let mut transaction = conn.start_transaction(TxOpts::default()).await?;
+
+    transaction
+        .exec_drop(
+            formatcp!(
+                "DELETE FROM {} WHERE bug_id = :bug_id AND label = :label",
+                TABLE_BUG_REPORTS_LABELS
+            ),
+            params! {
+                "bug_id" => bug_id,
+                "label" => label
+            },
+        )
+        .await?;
+
+    if transaction.affected_rows() == 0 {
+        return Ok(false);
+    }
+
+    add_history_entry(
+        &mut transaction,
+        bug_id,
+        user_id,
+        BugEvent::Label,
+        label,
+        "",
+    )
+    .await?;
+    transaction.commit().await?;
+
+    Ok(true)
+}
+
+/// Returns all the labels in use, with the number of open bugs and the total
+/// number of bugs for each, most used first.
+pub async fn get_label_statistics(
+    ctx: &Context,
+    guild_id: GuildId,
+) -> Option<Vec<(String, u32, u32)>> {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec(
+        formatcp!(
+            "SELECT t1.label, \
+CAST(SUM(t2.status IN (SELECT name FROM {TABLE_BUG_TRACKERS_STATUSES} AS t3 \
+WHERE t3.guild_id = t2.guild_id AND t3.open)) AS UNSIGNED) AS open_bugs, \
+COUNT(t1.bug_id) AS total \
+FROM {TABLE_BUG_REPORTS_LABELS} AS t1 \
+JOIN {TABLE_BUG_REPORTS} AS t2 ON t1.bug_id = t2.bug_id WHERE t2.guild_id = :guild_id \
+GROUP BY t1.label ORDER BY total DESC, t1.label ASC"
+        ),
+        params! {
+            "guild_id" => guild_id.0
+        },
+    )
+    .await
+    .ok()
+}
+
+/// Changes the assignee of a bug, and returns the previous one.
+pub async fn set_assignee(
+    ctx: &Context,
+    bug_id: u64,
+    assignee: Option<UserId>,
+    user_id: UserId,
+) -> Result<Option<UserId>, CommandError> {
+    let mut conn = get_database_conn!(ctx);
+
+    let old_assignee: Option<u64> = conn
+        .exec_first(
+            formatcp!(
+                "SELECT assignee FROM {} WHERE bug_id = :bug_id",
+                TABLE_BUG_REPORTS
+            ),
+            params! {
+                "bug_id" => bug_id
+            },
+        )
+        .await?
+        .ok_or_else(|| CommandError::from("Bug report does not exist!"))?;
+    let old_assignee = old_assignee.map(UserId);
+
+    let mut transaction = conn.start_transaction(TxOpts::default()).await?;
+
+    transaction
+        .exec_drop(
+            formatcp!(
+                "UPDATE {} SET assignee = :assignee WHERE bug_id = :bug_id",
+                TABLE_BUG_REPORTS
+            ),
+            params! {
+                "assignee" => assignee.map(|a| a.0),
+                "bug_id" => bug_id
+            },
+        )
+        .await?;
+
+    if old_assignee != assignee {
+        add_history_entry(
+            &mut transaction,
+            bug_id,
+            user_id,
+            BugEvent::Assignee,
+            &old_assignee.map(|a| a.0.to_string()).unwrap_or_default(),
+            &assignee.map(|a| a.0.to_string()).unwrap_or_default(),
+        )
+        .await?;
+    }
+
+    transaction.commit().await?;
+
+    Ok(old_assignee)
+}
+
+pub const MAX_VERSION_LENGTH: usize = 32;
+
+/// Normalizes a mod version, removing a leading `v`.
+///
+/// Returns `None` if the version is empty, too long, or contains characters
+/// other than ASCII alphanumerics, `.`, `-`, `_` and `+`.
+pub fn normalize_version(version: &str) -> Option<String> {
+    let version = version.trim();
+    let version = version
+        .strip_prefix(|c| c == 'v' || c == 'V')
+        .filter(|v| v.starts_with(|c: char| c.is_ascii_digit()))
+        .unwrap_or(version);
+    (!version.is_empty()
+        && version.len() <= MAX_VERSION_LENGTH
+        && version
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '+')))
+    .then(|| version.to_string())
+}
+
+/// Changes a version column of a bug, and returns the previous version.
+async fn set_version(
+    ctx: &Context,
+    bug_id: u64,
+    column: &str,
+    event: BugEvent,
+    version: Option<&str>,
+    user_id: UserId,
+) -> Result<Option<String>, CommandError> {
+    let mut conn = get_database_conn!(ctx);
+
+    let old_version: Option<String> = conn
+        .exec_first(
+            format!(
+                "SELECT {} FROM {} WHERE bug_id = :bug_id",
+                column, TABLE_BUG_REPORTS
+            ),
+            params! {
+                "bug_id" => bug_id
+            },
+        )
+        .await?
+        .ok_or_else(|| CommandError::from("Bug report does not exist!"))?;
+
+    let mut transaction = conn.start_transaction(TxOpts::default()).await?;
+
+    transaction
+        .exec_drop(
+            format!(
+                "UPDATE {} SET {} = :version WHERE bug_id = :bug_id",
+                TABLE_BUG_REPORTS, column
+            ),
+            params! {
+                "version" => version,
+                "bug_id" => bug_id
+            },
+        )
+        .await?;
+
+    if old_version.as_deref() != version {
+        add_history_entry(
+            &mut transaction,
+            bug_id,
+            user_id,
+            event,
+            old_version.as_deref().unwrap_or_default(),
+            version.unwrap_or_default(),
+        )
+        .await?;
+    }
+
+    transaction.commit().await?;
+
+    Ok(old_version)
+}
+
+/// Changes the [normalized][normalize_version] mod version affected by a bug,
+/// and returns the previous one.
+pub async fn set_affected_version(
+    ctx: &Context,
+    bug_id: u64,
+    version: Option<&str>,
+    user_id: UserId,
+) -> Result<Option<String>, CommandError> {
+    set_version(
+        ctx,
+        bug_id,
+        "affected_version",
+        BugEvent::Version,
+        version,
+        user_id,
+    )
+    .await
+}
+
+/// Changes the [normalized][normalize_version] release that fixed a bug,
+/// and returns the previous one.
+pub async fn set_fixed_in(
+    ctx: &Context,
+    bug_id: u64,
+    version: Option<&str>,
+    user_id: UserId,
+) -> Result<Option<String>, CommandError> {
+    set_version(ctx, bug_id, "fixed_in", BugEvent::FixedIn, version, user_id).await
+}
+
+/// Gets the resolved bugs fixed in a [normalized][normalize_version] release,
+/// ordered by edition then by id
+pub async fn get_fixed_bugs(
+    ctx: &Context,
+    guild_id: GuildId,
+    version: &str,
+) -> Result<Vec<PartialBugReport>, CommandError> {
+    let mut conn = get_database_conn!(ctx);
+
+    Ok(get_partial_bugs(
+        &mut conn,
+        formatcp!(
+            "SELECT {} FROM {} WHERE guild_id = :guild_id AND status = '{}' \
+AND fixed_in = :version ORDER BY category ASC, bug_id ASC",
+            PARTIAL_BUG_COLUMNS,
+            TABLE_BUG_REPORTS,
+            STATUS_RESOLVED
+        ),
+        params! {
+            "guild_id" => guild_id.0,
+            "version" => version
+        },
+        Some(guild_id),
+    )
+    .await?)
+}
+
+/// Marks `bug_id` as a duplicate of `original_id`, and moves all its
+/// subscribers over to the original bug.
+///
+/// The status of the duplicate is not changed: use [`change_bug_status`] to close it.
+pub async fn mark_duplicate(
+    ctx: &Context,
+    bug_id: u64,
+    original_id: u64,
+    user_id: UserId,
+) -> CommandResult {
+    let mut conn = get_database_conn!(ctx);
+    let mut transaction = conn.start_transaction(TxOpts::default()).await?;
+
+    transaction
+        .exec_drop(
+            formatcp!(
+                "UPDATE {} SET duplicate_of = :original_id WHERE bug_id = :bug_id",
+                TABLE_BUG_REPORTS
+            ),
+            params! {
+                "original_id" => original_id,
+                "bug_id" => bug_id
+            },
+        )
+        .await?;
+
+    add_history_entry(
+        &mut transaction,
+        bug_id,
+        user_id,
+        BugEvent::Duplicate,
+        "",
+        &original_id.to_string(),
+    )
+    .await?;
+
+    transaction
+        .exec_drop(
+            formatcp!(
+                "INSERT INTO {TABLE_BUG_REPORTS_NOTIFICATIONS} (bug_id, user_id) \
+SELECT :original_id, user_id FROM {TABLE_BUG_REPORTS_NOTIFICATIONS} \
+WHERE bug_id = :bug_id AND user_id NOT IN \
+(SELECT user_id FROM {TABLE_BUG_REPORTS_NOTIFICATIONS} WHERE bug_id = :original_id)"
+            ),
+            params! {
+                "original_id" => original_id,
+                "bug_id" => bug_id
+            },
+        )
+        .await?;
+
+    transaction
+        .exec_drop(
+            formatcp!(
+                "DELETE FROM {} WHERE bug_id = :bug_id",
+                TABLE_BUG_REPORTS_NOTIFICATIONS
+            ),
+            params! {
+                "bug_id" => bug_id
+            },
+        )
+        .await?;
+
+    transaction.commit().await?;
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Counts {
+    /// Number of bugs for each status name
+    pub statuses: HashMap<String, u32>,
+    pub open: u32,
+    pub total: u32,
+}
+
+impl Counts {
+    fn add(&mut self, status: &BugStatus, count: u32) {
+        *self.statuses.entry(status.name.clone()).or_default() += count;
+        if status.open {
+            self.open += count;
+        }
+        self.total += count;
+    }
+
+    pub fn get(&self, status: &str) -> u32 {
+        self.statuses.get(status).copied().unwrap_or_default()
+    }
+}
+
+/// Number of bugs opened and resolved during a week
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WeeklyActivity {
+    pub opened: u32,
+    pub resolved: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct BugStatistics {
+    pub counts: Counts,
+    /// Counts for each category, in the order of [`BugCategory::ALL`]
+    pub categories: [Counts; 4],
+    /// Activity of the last weeks, starting from the current week
+    pub weeks: Vec<WeeklyActivity>,
+    /// Median time to resolve in seconds, for each category
+    pub median_by_category: [Option<i64>; 4],
+    /// Median time to resolve in seconds, for each open status the bug had before being
+    /// resolved, from the lowest weight
+    pub median_by_priority: Vec<(BugStatus, Option<i64>)>,
+}
+
+fn median(mut values: Vec<i64>) -> Option<i64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    let mid = values.len() / 2;
+    Some(if values.len() % 2 == 0 {
+        (values[mid - 1] + values[mid]) / 2
+    } else {
+        values[mid]
+    })
+}
+
+pub async fn get_bug_statistics(
+    ctx: &Context,
+    guild_id: GuildId,
+    weeks: u32,
+) -> Option<BugStatistics> {
+    let mut conn = get_database_conn!(ctx);
+
+    let statuses = StatusDefinitions::load(&mut conn, Some(guild_id))
+        .await
+        .ok()?;
+
+    let mut counts = Counts::default();
+    let mut categories = [(); 4].map(|_| Counts::default());
+    let grouped_counts: Vec<(String, String, u32)> = conn
+        .exec(
+            formatcp!(
+                "SELECT category, status, COUNT(bug_id) FROM {} WHERE guild_id = :guild_id \
+GROUP BY category, status",
+                TABLE_BUG_REPORTS
+            ),
+            params! {
+                "guild_id" => guild_id.0
+            },
+        )
+        .await
+        .ok()?;
+    for (category, status, count) in grouped_counts {
+        let category: BugCategory = category
+            .parse()
+            .expect("Expected a valid bug category from the database");
+        let status = statuses.resolve(guild_id.0, status);
+        counts.add(&status, count);
+        categories[category.index()].add(&status, count);
+    }
+
+    let mut activity = vec![WeeklyActivity::default(); weeks as usize];
+    let opened: Vec<(u32, u32)> = conn
+        .exec(
+            formatcp!(
+                "SELECT TIMESTAMPDIFF(WEEK, timestamp, UTC_TIMESTAMP()) AS weeks_ago, COUNT(bug_id) \
+FROM {} WHERE guild_id = :guild_id AND timestamp > UTC_TIMESTAMP() - INTERVAL :weeks WEEK \
+GROUP BY weeks_ago",
+                TABLE_BUG_REPORTS
+            ),
+            params! {
+                "guild_id" => guild_id.0,
+                "weeks" => weeks
+            },
+        )
+        .await
+        .ok()?;
+    // the resolution date is taken from the bug history
+    let resolved: Vec<(u32, u32)> = conn
+        .exec(
+            formatcp!(
+                "SELECT TIMESTAMPDIFF(WEEK, timestamp, UTC_TIMESTAMP()) AS weeks_ago, \
+COUNT(DISTINCT bug_id) FROM {} WHERE event = 'status' AND new_value = '{}' \
+AND timestamp > UTC_TIMESTAMP() - INTERVAL :weeks WEEK \
+AND bug_id IN (SELECT bug_id FROM {} WHERE guild_id = :guild_id) GROUP BY weeks_ago",
+                TABLE_BUG_REPORTS_HISTORY,
+                STATUS_RESOLVED,
+                TABLE_BUG_REPORTS
+            ),
+            params! {
+                "guild_id" => guild_id.0,
+                "weeks" => weeks
+            },
+        )
+        .await
+        .ok()?;
+    for (weeks_ago, count) in opened {
+        if let Some(week) = activity.get_mut(weeks_ago as usize) {
+            week.opened = count;
+        }
+    }
+    for (weeks_ago, count) in resolved {
+        if let Some(week) = activity.get_mut(weeks_ago as usize) {
+            week.resolved = count;
+        }
+    }
+
+    // time between the report and its first resolution, for the bugs that are still resolved
+    let resolution_times: Vec<(String, String, i64)> = conn
+        .exec(
+            formatcp!(
+                "SELECT b.category, h.old_value, TIMESTAMPDIFF(SECOND, b.timestamp, h.timestamp) \
+FROM {0} AS h INNER JOIN {1} AS b ON b.bug_id = h.bug_id \
+WHERE b.guild_id = :guild_id AND b.status = '{2}' AND h.history_id IN (\
+SELECT MIN(history_id) FROM {0} WHERE event = 'status' AND new_value = '{2}' GROUP BY bug_id)",
+                TABLE_BUG_REPORTS_HISTORY,
+                TABLE_BUG_REPORTS,
+                STATUS_RESOLVED
+            ),
+            params! {
+                "guild_id" => guild_id.0
+            },
+        )
+        .await
+        .ok()?;
+
+    let mut priorities: Vec<&BugStatus> = statuses.0.values().filter(|s| s.open).collect();
+    priorities.sort_by_key(|status| status.weight);
+    let mut by_category = [(); 4].map(|_| Vec::new());
+    let mut by_priority = vec![Vec::new(); priorities.len()];
+    for (category, old_status, seconds) in resolution_times {
+        if let Ok(category) = category.parse::<BugCategory>() {
+            by_category[category.index()].push(seconds);
+        }
+        if let Some(i) = priorities.iter().position(|p| p.name == old_status) {
+            by_priority[i].push(seconds);
+        }
+    }
+
+    Some(BugStatistics {
+        counts,
+        categories,
+        weeks: activity,
+        median_by_category: by_category.map(median),
+        median_by_priority: priorities
+            .into_iter()
+            .cloned()
+            .zip(by_priority.into_iter().map(median))
+            .collect(),
+    })
+}
+
+pub async fn change_category(
+    ctx: &Context,
+    bug_id: u64,
+    category: BugCategory,
+    user_id: UserId,
+) -> Option<BugCategory> {
+    let mut conn = get_database_conn!(ctx);
+
+    let old_category: BugCategory = conn
+        .exec_first::<String, _, _>(
+            formatcp!(
+                "SELECT category FROM {} WHERE bug_id = :bug_id",
+                TABLE_BUG_REPORTS
+            ),
+            params! {
+                "bug_id" => bug_id,
+            },
+        )
+        .await
+        .ok()??
+        .parse()
+        .expect("Expected a valid bug category from the database");
+
+    let mut transaction = conn.start_transaction(TxOpts::default()).await.ok()?;
+
+    transaction
+        .exec_drop(
+            formatcp!(
+                "UPDATE {} SET category = :category WHERE bug_id = :bug_id",
+                TABLE_BUG_REPORTS
+            ),
+            params! {
+                "category" => category.as_str(),
+                "bug_id" => bug_id
+            },
+        )
+        .await
+        .ok()?;
+
+    if old_category != category {
+        add_history_entry(
+            &mut transaction,
+            bug_id,
+            user_id,
+            BugEvent::Category,
+            old_category.as_str(),
+            category.as_str(),
+        )
+        .await
+        .ok()?;
+    }
+
+    transaction.commit().await.ok()?;
+
+    Some(old_category)
+}
+
+pub async fn is_notified_user(ctx: &Context, bug_id: u64, user_id: UserId) -> Option<bool> {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_first(
+        formatcp!(
+            "SELECT EXISTS(SELECT notification_id \
+FROM {} WHERE bug_id = :bug_id AND user_id = :user_id)",
+            TABLE_BUG_REPORTS_NOTIFICATIONS
+        ),
+        params! {
+                "bug_id" => bug_id,
+                "user_id" => user_id.0
+        },
+    )
+    .await
+    .ok()?
+}
+
+/// Gets the bugs a user is subscribed to, with the id prefix of their tracker
+pub async fn get_notifications_for_user(
+    ctx: &Context,
+    user_id: UserId,
+    closed: bool,
+) -> CommandResult<Vec<(u64, String)>> {
+    let mut conn = get_database_conn!(ctx);
+
+    Ok(if closed {
+        conn.exec(
+            formatcp!(
+                "SELECT t1.bug_id, COALESCE(t3.prefix, '{DEFAULT_BUG_PREFIX}') \
+FROM {TABLE_BUG_REPORTS_NOTIFICATIONS} AS t1 \
+JOIN {TABLE_BUG_REPORTS} AS t2 ON t1.bug_id = t2.bug_id \
+LEFT JOIN {TABLE_BUG_TRACKERS} AS t3 ON t2.guild_id = t3.guild_id \
+WHERE t1.user_id = :user_id"
+            ),
+            params! {
+                "user_id" => user_id.0
+            },
+        )
+        .await?
+    } else {
+        conn.exec(
+            formatcp!(
+                "SELECT t1.bug_id, COALESCE(t3.prefix, '{DEFAULT_BUG_PREFIX}') \
+FROM {TABLE_BUG_REPORTS_NOTIFICATIONS} AS t1 \
+JOIN {TABLE_BUG_REPORTS} AS t2 \
+ON t1.bug_id = t2.bug_id \
+AND t2.status IN (SELECT name FROM {TABLE_BUG_TRACKERS_STATUSES} AS t4 \
+WHERE t4.guild_id = t2.guild_id AND t4.open) \
+LEFT JOIN {TABLE_BUG_TRACKERS} AS t3 ON t2.guild_id = t3.guild_id \
+WHERE t1.user_id = :user_id"
+            ),
+            params! {"user_id" => user_id.0},
+        )
+        .await?
+    })
+}
+
+/// Gets the users subscribed to a bug, with their notification mode
+pub async fn get_notified_users(
+    ctx: &Context,
+    bug_id: u64,
+) -> CommandResult<Vec<(UserId, NotificationMode)>> {
+    let mut conn = get_database_conn!(ctx);
+
+    Ok(conn
+        .exec_map(
+            formatcp!(
+                "SELECT n.user_id, s.mode FROM {} AS n LEFT JOIN {} AS s ON s.user_id = n.user_id \
+WHERE n.bug_id = :bug_id",
+                TABLE_BUG_REPORTS_NOTIFICATIONS,
+                TABLE_BUG_REPORTS_NOTIFICATION_SETTINGS
+            ),
+            params! {
+                "bug_id" => bug_id
+            },
+            |(user_id, mode): (u64, Option<String>)| {
+                (
+                    UserId(user_id),
+                    mode.and_then(|mode| mode.parse().ok()).unwrap_or_default(),
+                )
+            },
+        )
+        .await?)
+}
+
+pub async fn add_notified_user(ctx: &Context, bug_id: u64, user_id: UserId) -> CommandResult {
+    let mut conn = get_database_conn!(ctx);
+
+    Ok(conn
+        .exec_drop(
+            formatcp!(
+                "INSERT INTO {} (bug_id, user_id) VALUES (:bug_id, :user_id)",
+                TABLE_BUG_REPORTS_NOTIFICATIONS
+            ),
+            params! {
+                "bug_id" => bug_id,
+                "user_id" => user_id.0
+            },
+        )
+        .await?)
+}
+
+pub async fn remove_notified_user(ctx: &Context, bug_id: u64, user_id: UserId) -> CommandResult {
+    let mut conn = get_database_conn!(ctx);
+
+    Ok(conn
+        .exec_drop(
+            formatcp!(
+                "DELETE FROM {} WHERE bug_id = :bug_id AND user_id = :user_id LIMIT 1",
+                TABLE_BUG_REPORTS_NOTIFICATIONS
+            ),
+            params! {
+                "bug_id" => bug_id,
+                "user_id" => user_id.0
+            },
+        )
+        .await?)
+}
+
+pub async fn get_notification_mode(ctx: &Context, user_id: UserId) -> Option<NotificationMode> {
+    let mut conn = get_database_conn!(ctx);
+
+    let mode: Option<String> = conn
+        .exec_first(
+            formatcp!(
+                "SELECT mode FROM {} WHERE user_id = :user_id",
+                TABLE_BUG_REPORTS_NOTIFICATION_SETTINGS
+            ),
+            params! {
+                "user_id" => user_id.0
+            },
+        )
+        .await
+        .ok()?;
+
+    Some(mode.and_then(|mode| mode.parse().ok()).unwrap_or_default())
+}
+
+pub async fn set_notification_mode(
+    ctx: &Context,
+    user_id: UserId,
+    mode: NotificationMode,
+) -> CommandResult {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_drop(
+        formatcp!(
+            "INSERT INTO {} (user_id, mode) VALUES (:user_id, :mode) \
+ON DUPLICATE KEY UPDATE mode = :mode",
+            TABLE_BUG_REPORTS_NOTIFICATION_SETTINGS
+        ),
+        params! {
+            "user_id" => user_id.0,
+            "mode" => mode.as_str()
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Queues a notification for the daily digest of some users
+pub async fn queue_notification(
+    ctx: &Context,
+    bug_id: u64,
+    users: &[UserId],
+    message: &str,
+) -> CommandResult {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_batch(
+        formatcp!(
+            "INSERT INTO {} (user_id, bug_id, message) VALUES (:user_id, :bug_id, :message)",
+            TABLE_BUG_REPORTS_NOTIFICATION_QUEUE
+        ),
+        users.iter().map(|user_id| {
+            params! {
+                "user_id" => user_id.0,
+                "bug_id" => bug_id,
+                "message" => message
+            }
+        }),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Gets the users with queued notifications whose daily digest is due, or who are no longer
+/// in digest mode
+pub async fn get_due_notification_digests(ctx: &Context) -> Result<Vec<UserId>, CommandError> {
+    let mut conn = get_database_conn!(ctx);
+
+    Ok(conn
+        .query_map(
+            formatcp!(
+                "SELECT DISTINCT q.user_id FROM {} AS q LEFT JOIN {} AS s ON s.user_id = q.user_id \
+WHERE s.mode IS NULL OR s.mode != 'digest' OR s.last_digest IS NULL \
+OR s.last_digest < UTC_TIMESTAMP() - INTERVAL 1 DAY",
+                TABLE_BUG_REPORTS_NOTIFICATION_QUEUE,
+                TABLE_BUG_REPORTS_NOTIFICATION_SETTINGS
+            ),
+            UserId,
+        )
+        .await?)
+}
+
+/// Notification waiting for the daily digest of a user
+#[derive(Debug, Clone)]
+pub struct QueuedNotification {
+    pub queue_id: u64,
+    pub bug_id: u64,
+    pub prefix: String,
+    pub title: String,
+    pub message: String,
+}
+
+/// Gets the queued notifications of a user, oldest first
+pub async fn get_queued_notifications(
     ctx: &Context,
-    bug_id: u64,
-    new_status: BugStatus,
-) -> Result<BugStatus, CommandError> {
+    user_id: UserId,
+) -> Result<Vec<QueuedNotification>, CommandError> {
     let mut conn = get_database_conn!(ctx);
 
-    let (old_status_string, channel_id, msg_id): (String, u64, u64) = conn
-        .exec_first(
+    Ok(conn
+        .exec_map(
             formatcp!(
-                "SELECT status, channel_id, message_id FROM {} WHERE bug_id = :bug_id LIMIT 1",
-                TABLE_BUG_REPORTS
+                "SELECT q.queue_id, q.bug_id, COALESCE(t.prefix, '{}'), b.title, q.message \
+FROM {} AS q JOIN {} AS b ON b.bug_id = q.bug_id LEFT JOIN {} AS t ON t.guild_id = b.guild_id \
+WHERE q.user_id = :user_id ORDER BY q.queue_id ASC",
+                DEFAULT_BUG_PREFIX,
+                TABLE_BUG_REPORTS_NOTIFICATION_QUEUE,
+                TABLE_BUG_REPORTS,
+                TABLE_BUG_TRACKERS
             ),
             params! {
-                "bug_id" => bug_id
+                "user_id" => user_id.0
+            },
+            |(queue_id, bug_id, prefix, title, message): (u64, u64, String, String, String)| {
+                QueuedNotification {
+                    queue_id,
+                    bug_id,
+                    prefix,
+                    title,
+                    message,
+                }
             },
         )
-        .await?
-        .ok_or_else(|| CommandError::from("Could not find bug in database"))?;
+        .await?)
+}
 
-    let old_status: BugStatus = old_status_string
-        .parse()
-        .expect("Expected a valid bug status from database!");
+/// Removes the queued notifications of a user once they have been sent
+pub async fn remove_queued_notifications(
+    ctx: &Context,
+    user_id: UserId,
+    queue_ids: &[u64],
+) -> CommandResult {
+    let mut conn = get_database_conn!(ctx);
 
-    conn.exec_drop(
+    conn.exec_batch(
         formatcp!(
-            "UPDATE {} SET status = :status WHERE bug_id = :bug_id",
-            TABLE_BUG_REPORTS
+            "DELETE FROM {} WHERE user_id = :user_id AND queue_id = :queue_id",
+            TABLE_BUG_REPORTS_NOTIFICATION_QUEUE
         ),
-        params! {
-            "status" => new_status.as_str(),
-            "bug_id" => bug_id
-        },
+        queue_ids.iter().map(|queue_id| {
+            params! {
+                "user_id" => user_id.0,
+                "queue_id" => queue_id
+            }
+        }),
     )
     .await?;
 
-    match ChannelId(channel_id).message(ctx, MessageId(msg_id)).await {
-        Ok(msg) => {
-            if let Err(e) = msg.delete_reaction_emoji(ctx, old_status.reaction()).await {
-                println!("Could not remove reaction from bug report: {}", e);
-            }
-            if let Err(e) = msg.react(ctx, new_status.reaction()).await {
-                println!("Could not add reaction to bug report: {}", e);
-            }
-        }
-        Err(e) => println!("Could not get message for bug report: {}", e),
-    }
-
-    Ok(old_status)
+    Ok(())
 }
 
-pub async fn add_link(ctx: &Context, bug_id: u64, link_url: &str, link_title: &str) -> Option<u64> {
+/// Marks the daily digest of a user as sent
+pub async fn set_notification_digest_sent(ctx: &Context, user_id: UserId) -> CommandResult {
     let mut conn = get_database_conn!(ctx);
 
     conn.exec_drop(
         formatcp!(
-            "INSERT INTO {} (bug_id, link_url, link_title) VALUES (:bug_id, :link_url, :link_title)",
-            TABLE_BUG_REPORTS_LINKS
+            "UPDATE {} SET last_digest = UTC_TIMESTAMP() WHERE user_id = :user_id",
+            TABLE_BUG_REPORTS_NOTIFICATION_SETTINGS
         ),
         params! {
-            "bug_id" => bug_id,
-            "link_url" => link_url,
-            "link_title" => link_title
+            "user_id" => user_id.0
         },
     )
-    .await.ok()?;
+    .await?;
+
+    Ok(())
+}
+
+/// Channel where the weekly bug digest of a guild is posted
+#[derive(Debug, Clone, Copy)]
+pub struct DigestConfig {
+    pub guild_id: GuildId,
+    pub channel_id: ChannelId,
+    /// Open [high priority][HIGH_PRIORITY_WEIGHT] bugs older than this are listed in the digest
+    pub stale_days: u32,
+}
+
+pub struct BugDigest {
+    pub new: Vec<PartialBugReport>,
+    pub resolved: Vec<PartialBugReport>,
+    pub stale: Vec<PartialBugReport>,
+}
+
+pub async fn get_digest_config(ctx: &Context, guild_id: GuildId) -> Option<DigestConfig> {
+    let mut conn = get_database_conn!(ctx);
 
     conn.exec_first(
         formatcp!(
-            "SELECT MAX(link_id) FROM {} WHERE bug_id = :bug_id",
-            TABLE_BUG_REPORTS_LINKS
+            "SELECT channel_id, stale_days FROM {} WHERE guild_id = :guild_id",
+            TABLE_BUG_REPORTS_DIGESTS
         ),
         params! {
-            "bug_id" => bug_id
+            "guild_id" => guild_id.0
         },
     )
     .await
     .ok()?
+    .map(|(channel_id, stale_days)| DigestConfig {
+        guild_id,
+        channel_id: ChannelId(channel_id),
+        stale_days,
+    })
 }
 
-pub async fn remove_link(ctx: &Context, bug_id: u64, link_num: u64) -> CommandResult {
+pub async fn set_digest_config(ctx: &Context, config: DigestConfig) -> CommandResult {
     let mut conn = get_database_conn!(ctx);
 
     conn.exec_drop(
         formatcp!(
-            "DELETE FROM {} WHERE bug_id = :bug_id AND link_id = :link_id",
-            TABLE_BUG_REPORTS_LINKS
+            "INSERT INTO {} (guild_id, channel_id, stale_days) \
+VALUES (:guild_id, :channel_id, :stale_days) \
+ON DUPLICATE KEY UPDATE channel_id = :channel_id, stale_days = :stale_days",
+            TABLE_BUG_REPORTS_DIGESTS
         ),
         params! {
-            "bug_id" => bug_id,
-            "link_id" => link_num
+            "guild_id" => config.guild_id.0,
+            "channel_id" => config.channel_id.0,
+            "stale_days" => config.stale_days,
         },
     )
     .await?;
@@ -513,229 +2792,286 @@ pub async fn remove_link(ctx: &Context, bug_id: u64, link_num: u64) -> CommandRe
     Ok(())
 }
 
-pub async fn change_title(ctx: &Context, bug_id: u64, new_title: &str) -> CommandResult {
+pub async fn remove_digest_config(ctx: &Context, guild_id: GuildId) -> Result<bool, CommandError> {
     let mut conn = get_database_conn!(ctx);
 
     conn.exec_drop(
         formatcp!(
-            "UPDATE {} SET title = :new_title WHERE bug_id = :bug_id",
-            TABLE_BUG_REPORTS
+            "DELETE FROM {} WHERE guild_id = :guild_id",
+            TABLE_BUG_REPORTS_DIGESTS
         ),
         params! {
-            "new_title" => new_title,
-            "bug_id" => bug_id
+            "guild_id" => guild_id.0
         },
     )
     .await?;
 
-    Ok(())
-}
-
-#[derive(Debug, Clone, Copy)]
-pub struct Counts {
-    pub resolved: u32,
-    pub low: u32,
-    pub medium: u32,
-    pub high: u32,
-    pub critical: u32,
-    pub closed: u32,
-    pub forgevanilla: u32,
-    pub total: u32,
-    pub second_age: u32,
-}
-
-pub async fn get_bug_statistics(ctx: &Context) -> Option<Counts> {
-    let mut conn = get_database_conn!(ctx);
-
-    let statuses = [
-        "resolved",
-        "low",
-        "medium",
-        "high",
-        "critical",
-        "closed",
-        "forgevanilla",
-    ];
-
-    let mut counts = [0; 9];
-
-    for (i, s) in statuses.iter().enumerate() {
-        let x = conn
-            .exec_first(
-                formatcp!(
-                    "SELECT COUNT(bug_id) FROM {} WHERE status = :status",
-                    TABLE_BUG_REPORTS
-                ),
-                params! {
-                    "status" => s
-                },
-            )
-            .await
-            .ok()??;
-        counts[i] = x;
-    }
-
-    counts[7] = counts.iter().sum();
-
-    counts[8] = conn
-        .query_first(formatcp!(
-            "SELECT COUNT(bug_id) FROM {} WHERE category = 'sa_legacy' OR category = 'sa_renewed'",
-            TABLE_BUG_REPORTS
-        ))
-        .await
-        .ok()??;
-
-    Some(Counts {
-        resolved: counts[0],
-        low: counts[1],
-        medium: counts[2],
-        high: counts[3],
-        critical: counts[4],
-        closed: counts[5],
-        forgevanilla: counts[6],
-        total: counts[7],
-        second_age: counts[8],
-    })
+    Ok(conn.affected_rows() > 0)
 }
 
-pub async fn change_category(
-    ctx: &Context,
-    bug_id: u64,
-    category: BugCategory,
-) -> Option<BugCategory> {
+/// Gets the digest channels that have not received a digest for a week
+pub async fn get_due_digests(ctx: &Context) -> Result<Vec<DigestConfig>, CommandError> {
     let mut conn = get_database_conn!(ctx);
 
-    let old_category = conn
-        .exec_first::<String, _, _>(
+    Ok(conn
+        .query_map(
             formatcp!(
-                "SELECT category FROM {} WHERE bug_id = :bug_id",
-                TABLE_BUG_REPORTS
+                "SELECT guild_id, channel_id, stale_days FROM {} \
+WHERE last_digest IS NULL OR last_digest < UTC_TIMESTAMP() - INTERVAL 1 WEEK",
+                TABLE_BUG_REPORTS_DIGESTS
             ),
-            params! {
-                "bug_id" => bug_id,
+            |(guild_id, channel_id, stale_days)| DigestConfig {
+                guild_id: GuildId(guild_id),
+                channel_id: ChannelId(channel_id),
+                stale_days,
             },
         )
-        .await
-        .ok()??
-        .parse()
-        .expect("Expected a valid bug category from the database");
+        .await?)
+}
+
+pub async fn set_digest_sent(ctx: &Context, guild_id: GuildId) -> CommandResult {
+    let mut conn = get_database_conn!(ctx);
 
     conn.exec_drop(
         formatcp!(
-            "UPDATE {} SET category = :category WHERE bug_id = :bug_id",
-            TABLE_BUG_REPORTS
+            "UPDATE {} SET last_digest = UTC_TIMESTAMP() WHERE guild_id = :guild_id",
+            TABLE_BUG_REPORTS_DIGESTS
         ),
         params! {
-            "category" => category.as_str(),
-            "bug_id" => bug_id
+            "guild_id" => guild_id.0
         },
     )
-    .await
-    .ok()?;
+    .await?;
 
-    Some(old_category)
+    Ok(())
 }
 
-pub async fn is_notified_user(ctx: &Context, bug_id: u64, user_id: UserId) -> Option<bool> {
+/// Pending request for more information about a bug, waiting for a reply of its reporter
+#[derive(Debug, Clone, Copy)]
+pub struct InfoRequest {
+    pub bug_id: u64,
+    pub guild_id: GuildId,
+    pub reporter: UserId,
+    /// Whether the bug has been inactive for long enough to be closed
+    pub stale: bool,
+}
+
+/// Asks the reporter of a bug for more information, restarting the reminder delay
+pub async fn add_info_request(ctx: &Context, bug_id: u64, reporter: UserId) -> CommandResult {
     let mut conn = get_database_conn!(ctx);
 
-    conn.exec_first(
+    conn.exec_drop(
         formatcp!(
-            "SELECT EXISTS(SELECT notification_id \
-FROM {} WHERE bug_id = :bug_id AND user_id = :user_id)",
-            TABLE_BUG_REPORTS_NOTIFICATIONS
+            "REPLACE INTO {} (bug_id, user_id, timestamp, reminded) \
+VALUES (:bug_id, :user_id, UTC_TIMESTAMP(), FALSE)",
+            TABLE_BUG_REPORTS_INFO_REQUESTS
         ),
         params! {
-                "bug_id" => bug_id,
-                "user_id" => user_id.0
+            "bug_id" => bug_id,
+            "user_id" => reporter.0
         },
     )
-    .await
-    .ok()?
+    .await?;
+
+    Ok(())
 }
 
-pub async fn get_notifications_for_user(
+/// Records a reply of a reporter in the thread of a bug waiting for more information,
+/// or to its reported message, restarting the reminder delay.
+///
+/// Returns the id of the bug, if the message is such a reply.
+pub async fn record_info_reply(
     ctx: &Context,
-    user_id: UserId,
-    closed: bool,
-) -> CommandResult<Vec<u64>> {
+    message: &Message,
+) -> Result<Option<u64>, CommandError> {
     let mut conn = get_database_conn!(ctx);
 
-    Ok(if closed {
-        conn.exec(
+    let bug_id: Option<u64> = conn
+        .exec_first(
             formatcp!(
-                "SELECT bug_id FROM {} WHERE user_id = :user_id",
-                TABLE_BUG_REPORTS_NOTIFICATIONS,
+                "SELECT r.bug_id FROM {} AS r JOIN {} AS b ON b.bug_id = r.bug_id \
+WHERE r.user_id = :user_id AND (b.thread_id = :channel_id OR b.message_id = :message_id) LIMIT 1",
+                TABLE_BUG_REPORTS_INFO_REQUESTS,
+                TABLE_BUG_REPORTS
             ),
             params! {
-                "user_id" => user_id.0
+                "user_id" => message.author.id.0,
+                "channel_id" => message.channel_id.0,
+                "message_id" => message.referenced_message.as_ref().map(|m| m.id.0)
             },
         )
-        .await?
-    } else {
-        conn.exec(
-            formatcp!(
-                "SELECT t1.bug_id FROM {TABLE_BUG_REPORTS_NOTIFICATIONS} AS t1 \
-JOIN {TABLE_BUG_REPORTS} AS t2 \
-ON t1.bug_id = t2.bug_id \
-AND t2.status != 'closed' \
-AND t2.status != 'resolved' \
-AND t2.status != 'forgevanilla' \
-WHERE t1.user_id = :user_id"
-            ),
-            params! {"user_id" => user_id.0},
-        )
-        .await?
-    })
-}
-
-pub async fn get_notified_users(ctx: &Context, bug_id: u64) -> CommandResult<Vec<UserId>> {
-    let mut conn = get_database_conn!(ctx);
+        .await?;
 
-    Ok(conn
-        .exec_map(
+    if let Some(bug_id) = bug_id {
+        conn.exec_drop(
             formatcp!(
-                "SELECT user_id FROM {} WHERE bug_id = :bug_id",
-                TABLE_BUG_REPORTS_NOTIFICATIONS
+                "UPDATE {} SET timestamp = UTC_TIMESTAMP(), reminded = FALSE \
+WHERE bug_id = :bug_id",
+                TABLE_BUG_REPORTS_INFO_REQUESTS
             ),
             params! {
                 "bug_id" => bug_id
             },
-            UserId,
         )
-        .await?)
+        .await?;
+    }
+
+    Ok(bug_id)
 }
 
-pub async fn add_notified_user(ctx: &Context, bug_id: u64, user_id: UserId) -> CommandResult {
+/// Gets the info requests without activity for longer than the delays of their tracker:
+/// the ones to remind their reporter of, and the stale ones to close.
+///
+/// Both replies of the reporter and changes to the bug count as activity.
+pub async fn get_due_info_requests(ctx: &Context) -> Result<Vec<InfoRequest>, CommandError> {
     let mut conn = get_database_conn!(ctx);
 
     Ok(conn
-        .exec_drop(
+        .query_map(
             formatcp!(
-                "INSERT INTO {} (bug_id, user_id) VALUES (:bug_id, :user_id)",
-                TABLE_BUG_REPORTS_NOTIFICATIONS
+                "SELECT bug_id, guild_id, user_id, \
+info_close_days > 0 AND activity < UTC_TIMESTAMP() - INTERVAL info_close_days DAY AS stale \
+FROM (SELECT r.bug_id, b.guild_id, r.user_id, r.reminded, \
+t.info_reminder_days, t.info_close_days, GREATEST(r.timestamp, \
+COALESCE((SELECT MAX(h.timestamp) FROM {} AS h WHERE h.bug_id = r.bug_id), r.timestamp)) \
+AS activity FROM {} AS r JOIN {} AS b ON b.bug_id = r.bug_id \
+JOIN {} AS t ON t.guild_id = b.guild_id) AS requests \
+WHERE (info_close_days > 0 AND activity < UTC_TIMESTAMP() - INTERVAL info_close_days DAY) \
+OR (NOT reminded AND info_reminder_days > 0 \
+AND activity < UTC_TIMESTAMP() - INTERVAL info_reminder_days DAY)",
+                TABLE_BUG_REPORTS_HISTORY,
+                TABLE_BUG_REPORTS_INFO_REQUESTS,
+                TABLE_BUG_REPORTS,
+                TABLE_BUG_TRACKERS
             ),
-            params! {
-                "bug_id" => bug_id,
-                "user_id" => user_id.0
+            |(bug_id, guild_id, user_id, stale): (u64, u64, u64, bool)| InfoRequest {
+                bug_id,
+                guild_id: GuildId(guild_id),
+                reporter: UserId(user_id),
+                stale,
             },
         )
         .await?)
 }
 
-pub async fn remove_notified_user(ctx: &Context, bug_id: u64, user_id: UserId) -> CommandResult {
+pub async fn set_info_reminded(ctx: &Context, bug_id: u64) -> CommandResult {
     let mut conn = get_database_conn!(ctx);
 
+    conn.exec_drop(
+        formatcp!(
+            "UPDATE {} SET reminded = TRUE WHERE bug_id = :bug_id",
+            TABLE_BUG_REPORTS_INFO_REQUESTS
+        ),
+        params! {
+            "bug_id" => bug_id
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+type PartialBugRow = (u64, String, String, NaiveDateTime, String, String, u64);
+
+/// Reads a row of [`PARTIAL_BUG_COLUMNS`]
+fn partial_bug_from_row(
+    statuses: &StatusDefinitions,
+    (bug_id, title, status, timestamp, category, prefix, guild_id): PartialBugRow,
+) -> Option<PartialBugReport> {
+    PartialBugReport::new(
+        bug_id,
+        title,
+        statuses.resolve(guild_id, status),
+        timestamp,
+        category
+            .parse()
+            .expect("Expected a valid bug category from the database"),
+        prefix,
+    )
+}
+
+async fn get_partial_bugs(
+    conn: &mut Conn,
+    query: &str,
+    params: mysql_async::Params,
+    guild_id: Option<GuildId>,
+) -> Result<Vec<PartialBugReport>, mysql_async::Error> {
+    let statuses = StatusDefinitions::load(conn, guild_id).await?;
     Ok(conn
-        .exec_drop(
-            formatcp!(
-                "DELETE FROM {} WHERE bug_id = :bug_id AND user_id = :user_id LIMIT 1",
-                TABLE_BUG_REPORTS_NOTIFICATIONS
-            ),
-            params! {
-                "bug_id" => bug_id,
-                "user_id" => user_id.0
-            },
-        )
-        .await?)
+        .exec_map(query, params, |row| partial_bug_from_row(&statuses, row))
+        .await?
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+/// Gets the bugs opened and resolved during the last week, and the open
+/// [high priority][HIGH_PRIORITY_WEIGHT] bugs older than `stale_days`
+pub async fn get_bug_digest(
+    ctx: &Context,
+    guild_id: GuildId,
+    stale_days: u32,
+) -> Result<BugDigest, CommandError> {
+    let mut conn = get_database_conn!(ctx);
+
+    let new = get_partial_bugs(
+        &mut conn,
+        formatcp!(
+            "SELECT {} FROM {} WHERE guild_id = :guild_id \
+AND timestamp > UTC_TIMESTAMP() - INTERVAL 1 WEEK ORDER BY timestamp ASC",
+            PARTIAL_BUG_COLUMNS,
+            TABLE_BUG_REPORTS
+        ),
+        params! {
+            "guild_id" => guild_id.0
+        },
+        Some(guild_id),
+    )
+    .await?;
+
+    let resolved = get_partial_bugs(
+        &mut conn,
+        formatcp!(
+            "SELECT {} FROM {} WHERE guild_id = :guild_id \
+AND status = '{2}' AND bug_id IN (SELECT bug_id FROM {3} WHERE event = 'status' \
+AND new_value = '{2}' AND timestamp > UTC_TIMESTAMP() - INTERVAL 1 WEEK) \
+ORDER BY timestamp ASC",
+            PARTIAL_BUG_COLUMNS,
+            TABLE_BUG_REPORTS,
+            STATUS_RESOLVED,
+            TABLE_BUG_REPORTS_HISTORY
+        ),
+        params! {
+            "guild_id" => guild_id.0
+        },
+        Some(guild_id),
+    )
+    .await?;
+
+    let stale = get_partial_bugs(
+        &mut conn,
+        formatcp!(
+            "SELECT {} FROM {} WHERE guild_id = :guild_id \
+AND {} AND {} >= :weight AND timestamp < UTC_TIMESTAMP() - INTERVAL :days DAY \
+ORDER BY {} DESC, timestamp ASC",
+            PARTIAL_BUG_COLUMNS,
+            TABLE_BUG_REPORTS,
+            OPEN_STATUS,
+            STATUS_WEIGHT,
+            STATUS_WEIGHT
+        ),
+        params! {
+            "guild_id" => guild_id.0,
+            "weight" => HIGH_PRIORITY_WEIGHT,
+            "days" => stale_days
+        },
+        Some(guild_id),
+    )
+    .await?;
+
+    Ok(BugDigest {
+        new,
+        resolved,
+        stale,
+    })
 }
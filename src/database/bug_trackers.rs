@@ -0,0 +1,647 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use const_format::formatcp;
+use mysql_async::prelude::*;
+use serenity::client::Context;
+use serenity::framework::standard::{CommandError, CommandResult};
+use serenity::model::prelude::*;
+
+use crate::api::issues::{GitHubTracker, IssueTracker};
+use crate::constants::{
+    TABLE_BUG_REPORTS, TABLE_BUG_TRACKERS, TABLE_BUG_TRACKERS_ISSUE_SYNC,
+    TABLE_BUG_TRACKERS_MODERATORS, TABLE_BUG_TRACKERS_STATUSES,
+};
+use crate::database::bug_reports::{BugCategory, BugStatus, DEFAULT_STATUS};
+use crate::get_database_conn;
+
+/// Prefix of the bug ids of a guild that has no bug tracker anymore
+pub const DEFAULT_BUG_PREFIX: &str = "BUG";
+pub const MAX_PREFIX_LENGTH: usize = 8;
+pub const DEFAULT_INFO_REMINDER_DAYS: u32 = 7;
+pub const DEFAULT_INFO_CLOSE_DAYS: u32 = 30;
+
+/// Bug tracker of a guild. Bug ids are shared by all the guilds, but each guild
+/// only sees its own bugs, with its own id prefix.
+#[derive(Debug, Clone)]
+pub struct BugTracker {
+    pub guild_id: GuildId,
+    /// Prefix of the bug ids, like `EoA` in `EoA-42`
+    pub prefix: String,
+    /// Channel where new bug reports are announced
+    pub channel_id: Option<ChannelId>,
+    /// Categories available for bug reports, the first one being the default
+    pub categories: Vec<BugCategory>,
+    /// Statuses of the bug reports, from the highest weight
+    pub statuses: Vec<BugStatus>,
+    /// Days without activity before reminding the reporter of a bug that needs more
+    /// information, or 0 to never remind them
+    pub info_reminder_days: u32,
+    /// Days without activity before closing a bug that needs more information,
+    /// or 0 to never close it
+    pub info_close_days: u32,
+}
+
+impl BugTracker {
+    pub fn new(guild_id: GuildId, prefix: String) -> Self {
+        Self {
+            guild_id,
+            prefix,
+            channel_id: None,
+            categories: BugCategory::ALL.to_vec(),
+            statuses: BugStatus::defaults(),
+            info_reminder_days: DEFAULT_INFO_REMINDER_DAYS,
+            info_close_days: DEFAULT_INFO_CLOSE_DAYS,
+        }
+    }
+
+    /// Formats a bug id with the tracker prefix, like `EoA-42`
+    pub fn display_id(&self, bug_id: u64) -> String {
+        format!("{}-{}", self.prefix, bug_id)
+    }
+
+    /// Removes the tracker prefix from a bug id, ignoring case
+    pub fn strip_prefix<'a>(&self, bug_id: &'a str) -> &'a str {
+        let prefix_len = self.prefix.len() + 1;
+        // compared as bytes, as the input may have a multi-byte character across the prefix
+        bug_id
+            .as_bytes()
+            .get(..prefix_len)
+            .filter(|prefix| {
+                prefix[..prefix_len - 1].eq_ignore_ascii_case(self.prefix.as_bytes())
+                    && prefix[prefix_len - 1] == b'-'
+            })
+            .map_or(bug_id, |_| &bug_id[prefix_len..])
+    }
+
+    /// Parses a bug id, with or without the tracker prefix
+    pub fn parse_bug_id(&self, bug_id: &str) -> Option<u64> {
+        self.strip_prefix(bug_id.trim()).parse().ok()
+    }
+
+    pub fn default_category(&self) -> BugCategory {
+        self.categories.first().copied().unwrap_or_default()
+    }
+
+    pub fn has_category(&self, category: BugCategory) -> bool {
+        self.categories.contains(&category)
+    }
+
+    /// Finds a status of the tracker, ignoring case
+    pub fn status(&self, name: &str) -> Option<&BugStatus> {
+        let name = name.trim();
+        self.statuses
+            .iter()
+            .find(|status| status.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn parse_status(&self, name: &str) -> Option<BugStatus> {
+        self.status(name).cloned()
+    }
+
+    /// Gets a status of the tracker, even if it was removed
+    pub fn status_or_unknown(&self, name: &str) -> BugStatus {
+        self.parse_status(name)
+            .unwrap_or_else(|| BugStatus::unknown(name.to_string()))
+    }
+
+    /// Status of new bug reports: `medium` if it is an open status of the tracker,
+    /// or else its open status of lowest weight
+    pub fn default_status(&self) -> BugStatus {
+        self.status(DEFAULT_STATUS)
+            .filter(|status| status.open)
+            .or_else(|| self.open_statuses().last())
+            .cloned()
+            .unwrap_or_else(|| BugStatus::unknown(DEFAULT_STATUS.to_string()))
+    }
+
+    /// Statuses of the bugs that still need work, from the highest weight
+    pub fn open_statuses(&self) -> impl DoubleEndedIterator<Item = &BugStatus> {
+        self.statuses.iter().filter(|status| status.open)
+    }
+}
+
+/// Checks that a prefix is only made of ASCII alphanumerics, and not too long
+pub fn is_valid_prefix(prefix: &str) -> bool {
+    !prefix.is_empty()
+        && prefix.len() <= MAX_PREFIX_LENGTH
+        && prefix.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+type TrackerRow = (u64, String, Option<u64>, String, u32, u32);
+
+fn tracker_from_row(
+    (guild_id, prefix, channel_id, categories, info_reminder_days, info_close_days): TrackerRow,
+) -> BugTracker {
+    BugTracker {
+        guild_id: GuildId(guild_id),
+        prefix,
+        channel_id: channel_id.map(ChannelId),
+        categories: categories
+            .split(',')
+            .filter_map(|category| category.parse().ok())
+            .collect(),
+        statuses: Vec::new(),
+        info_reminder_days,
+        info_close_days,
+    }
+}
+
+/// Gets the statuses of a guild, or of all the guilds, from the highest weight
+async fn get_statuses(
+    conn: &mut mysql_async::Conn,
+    guild_id: Option<GuildId>,
+) -> Result<Vec<(GuildId, BugStatus)>, mysql_async::Error> {
+    conn.exec_map(
+        formatcp!(
+            "SELECT guild_id, name, colour, marker, weight, open FROM {} \
+WHERE :guild_id IS NULL OR guild_id = :guild_id ORDER BY weight DESC, name ASC",
+            TABLE_BUG_TRACKERS_STATUSES
+        ),
+        params! {
+            "guild_id" => guild_id.map(|g| g.0)
+        },
+        |(guild_id, name, colour, marker, weight, open): (u64, String, u32, String, i32, bool)| {
+            (
+                GuildId(guild_id),
+                BugStatus::new(&name, colour, &marker, weight, open),
+            )
+        },
+    )
+    .await
+}
+
+pub async fn get_bug_tracker(ctx: &Context, guild_id: GuildId) -> Option<BugTracker> {
+    let mut conn = get_database_conn!(ctx);
+
+    let mut tracker = conn
+        .exec_first(
+            formatcp!(
+                "SELECT guild_id, prefix, channel_id, categories, info_reminder_days, \
+info_close_days FROM {} WHERE guild_id = :guild_id",
+                TABLE_BUG_TRACKERS
+            ),
+            params! {
+                "guild_id" => guild_id.0
+            },
+        )
+        .await
+        .ok()?
+        .map(tracker_from_row)?;
+
+    tracker.statuses = get_statuses(&mut conn, Some(guild_id))
+        .await
+        .ok()?
+        .into_iter()
+        .map(|(_, status)| status)
+        .collect();
+
+    Some(tracker)
+}
+
+pub async fn get_bug_trackers(ctx: &Context) -> Result<Vec<BugTracker>, CommandError> {
+    let mut conn = get_database_conn!(ctx);
+
+    let mut trackers: Vec<BugTracker> = conn
+        .query_map(
+            formatcp!(
+                "SELECT guild_id, prefix, channel_id, categories, info_reminder_days, \
+info_close_days FROM {} ORDER BY guild_id ASC",
+                TABLE_BUG_TRACKERS
+            ),
+            tracker_from_row,
+        )
+        .await?;
+
+    // trackers are sorted by guild, so they can be found with a binary search
+    for (guild_id, status) in get_statuses(&mut conn, None).await? {
+        if let Ok(i) = trackers.binary_search_by_key(&guild_id, |tracker| tracker.guild_id) {
+            trackers[i].statuses.push(status);
+        }
+    }
+
+    Ok(trackers)
+}
+
+/// Creates or updates the bug tracker of a guild
+pub async fn set_bug_tracker(ctx: &Context, tracker: &BugTracker) -> CommandResult {
+    let mut conn = get_database_conn!(ctx);
+
+    let categories = tracker
+        .categories
+        .iter()
+        .map(|category| category.as_str())
+        .collect::<Vec<_>>()
+        .join(",");
+
+    conn.exec_drop(
+        formatcp!(
+            "INSERT INTO {} (guild_id, prefix, channel_id, categories, info_reminder_days, \
+info_close_days) VALUES (:guild_id, :prefix, :channel_id, :categories, :info_reminder_days, \
+:info_close_days) ON DUPLICATE KEY UPDATE prefix = :prefix, channel_id = :channel_id, \
+categories = :categories, info_reminder_days = :info_reminder_days, \
+info_close_days = :info_close_days",
+            TABLE_BUG_TRACKERS
+        ),
+        params! {
+            "guild_id" => tracker.guild_id.0,
+            "prefix" => &tracker.prefix,
+            "channel_id" => tracker.channel_id.map(|c| c.0),
+            "categories" => categories,
+            "info_reminder_days" => tracker.info_reminder_days,
+            "info_close_days" => tracker.info_close_days,
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Adds statuses to the tracker of a guild, keeping the existing statuses with the same name
+pub async fn add_bug_statuses(
+    ctx: &Context,
+    guild_id: GuildId,
+    statuses: &[BugStatus],
+) -> CommandResult {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_batch(
+        formatcp!(
+            "INSERT IGNORE INTO {} (guild_id, name, colour, marker, weight, open) \
+VALUES (:guild_id, :name, :colour, :marker, :weight, :open)",
+            TABLE_BUG_TRACKERS_STATUSES
+        ),
+        statuses.iter().map(|status| {
+            params! {
+                "guild_id" => guild_id.0,
+                "name" => &status.name,
+                "colour" => status.colour.0,
+                "marker" => &status.marker,
+                "weight" => status.weight,
+                "open" => status.open,
+            }
+        }),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Creates or updates a status of the tracker of a guild
+pub async fn set_bug_status(ctx: &Context, guild_id: GuildId, status: &BugStatus) -> CommandResult {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_drop(
+        formatcp!(
+            "INSERT INTO {} (guild_id, name, colour, marker, weight, open) \
+VALUES (:guild_id, :name, :colour, :marker, :weight, :open) \
+ON DUPLICATE KEY UPDATE colour = :colour, marker = :marker, weight = :weight, open = :open",
+            TABLE_BUG_TRACKERS_STATUSES
+        ),
+        params! {
+            "guild_id" => guild_id.0,
+            "name" => &status.name,
+            "colour" => status.colour.0,
+            "marker" => &status.marker,
+            "weight" => status.weight,
+            "open" => status.open,
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Counts the bugs of a guild with a status
+pub async fn count_bugs_with_status(
+    ctx: &Context,
+    guild_id: GuildId,
+    status: &str,
+) -> Result<u32, CommandError> {
+    let mut conn = get_database_conn!(ctx);
+
+    Ok(conn
+        .exec_first(
+            formatcp!(
+                "SELECT COUNT(bug_id) FROM {} WHERE guild_id = :guild_id AND status = :status",
+                TABLE_BUG_REPORTS
+            ),
+            params! {
+                "guild_id" => guild_id.0,
+                "status" => status
+            },
+        )
+        .await?
+        .unwrap_or_default())
+}
+
+/// Removes a status from the tracker of a guild.
+///
+/// Returns `false` if the tracker had no such status.
+pub async fn remove_bug_status(
+    ctx: &Context,
+    guild_id: GuildId,
+    status: &str,
+) -> Result<bool, CommandError> {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_drop(
+        formatcp!(
+            "DELETE FROM {} WHERE guild_id = :guild_id AND name = :name",
+            TABLE_BUG_TRACKERS_STATUSES
+        ),
+        params! {
+            "guild_id" => guild_id.0,
+            "name" => status
+        },
+    )
+    .await?;
+
+    Ok(conn.affected_rows() != 0)
+}
+
+/// Repository of an external issue tracker where the bugs of a guild are mirrored
+#[derive(Debug, Clone)]
+pub struct IssueSync {
+    pub guild_id: GuildId,
+    /// Repository path, like `owner/repo`
+    pub repository: String,
+    /// Url of the API of a self-hosted issue tracker, instead of GitHub
+    pub api_url: Option<String>,
+    /// Date of the last issue status synchronisation
+    pub last_sync: DateTime<Utc>,
+}
+
+impl IssueSync {
+    pub fn issue_tracker(&self) -> Result<Box<dyn IssueTracker>, CommandError> {
+        Ok(Box::new(GitHubTracker::new(
+            self.api_url.as_deref(),
+            &self.repository,
+        )?))
+    }
+}
+
+type IssueSyncRow = (u64, String, Option<String>, NaiveDateTime);
+
+fn issue_sync_from_row((guild_id, repository, api_url, last_sync): IssueSyncRow) -> IssueSync {
+    IssueSync {
+        guild_id: GuildId(guild_id),
+        repository,
+        api_url,
+        last_sync: DateTime::from_utc(last_sync, Utc),
+    }
+}
+
+pub async fn get_issue_sync(ctx: &Context, guild_id: GuildId) -> Option<IssueSync> {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_first(
+        formatcp!(
+            "SELECT guild_id, repository, api_url, last_sync FROM {} WHERE guild_id = :guild_id",
+            TABLE_BUG_TRACKERS_ISSUE_SYNC
+        ),
+        params! {
+            "guild_id" => guild_id.0
+        },
+    )
+    .await
+    .ok()?
+    .map(issue_sync_from_row)
+}
+
+pub async fn get_issue_syncs(ctx: &Context) -> Result<Vec<IssueSync>, CommandError> {
+    let mut conn = get_database_conn!(ctx);
+
+    Ok(conn
+        .query_map(
+            formatcp!(
+                "SELECT s.guild_id, s.repository, s.api_url, s.last_sync FROM {} s \
+JOIN {} t ON t.guild_id = s.guild_id",
+                TABLE_BUG_TRACKERS_ISSUE_SYNC,
+                TABLE_BUG_TRACKERS
+            ),
+            issue_sync_from_row,
+        )
+        .await?)
+}
+
+/// Mirrors the bugs of a guild to a repository. Only the issues updated from now
+/// on will be synchronised.
+pub async fn set_issue_sync(
+    ctx: &Context,
+    guild_id: GuildId,
+    repository: &str,
+    api_url: Option<&str>,
+) -> CommandResult {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_drop(
+        formatcp!(
+            "REPLACE INTO {} (guild_id, repository, api_url, last_sync) \
+VALUES (:guild_id, :repository, :api_url, CURRENT_TIMESTAMP)",
+            TABLE_BUG_TRACKERS_ISSUE_SYNC
+        ),
+        params! {
+            "guild_id" => guild_id.0,
+            "repository" => repository,
+            "api_url" => api_url
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn set_issue_sync_time(
+    ctx: &Context,
+    guild_id: GuildId,
+    last_sync: DateTime<Utc>,
+) -> CommandResult {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_drop(
+        formatcp!(
+            "UPDATE {} SET last_sync = :last_sync WHERE guild_id = :guild_id",
+            TABLE_BUG_TRACKERS_ISSUE_SYNC
+        ),
+        params! {
+            "guild_id" => guild_id.0,
+            "last_sync" => last_sync.naive_utc()
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Stops mirroring the bugs of a guild.
+///
+/// Returns `false` if they were not mirrored.
+pub async fn remove_issue_sync(ctx: &Context, guild_id: GuildId) -> Result<bool, CommandError> {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_drop(
+        formatcp!(
+            "DELETE FROM {} WHERE guild_id = :guild_id",
+            TABLE_BUG_TRACKERS_ISSUE_SYNC
+        ),
+        params! {
+            "guild_id" => guild_id.0
+        },
+    )
+    .await?;
+
+    Ok(conn.affected_rows() != 0)
+}
+
+/// Removes the bug tracker of a guild. Its bug reports are kept, and
+/// are available again if the bug tracker is enabled again.
+///
+/// Returns `false` if the guild had no bug tracker.
+pub async fn remove_bug_tracker(ctx: &Context, guild_id: GuildId) -> Result<bool, CommandError> {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_drop(
+        formatcp!(
+            "DELETE FROM {} WHERE guild_id = :guild_id",
+            TABLE_BUG_TRACKERS
+        ),
+        params! {
+            "guild_id" => guild_id.0
+        },
+    )
+    .await?;
+
+    Ok(conn.affected_rows() != 0)
+}
+
+/// Gets the id prefix of the tracker a bug belongs to
+pub async fn get_bug_prefix(ctx: &Context, bug_id: u64) -> String {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_first(
+        formatcp!(
+            "SELECT t.prefix FROM {} b JOIN {} t ON t.guild_id = b.guild_id \
+WHERE b.bug_id = :bug_id",
+            TABLE_BUG_REPORTS,
+            TABLE_BUG_TRACKERS
+        ),
+        params! {
+            "bug_id" => bug_id
+        },
+    )
+    .await
+    .ok()
+    .flatten()
+    .unwrap_or_else(|| DEFAULT_BUG_PREFIX.into())
+}
+
+/// Checks that a bug belongs to the tracker of a guild
+pub async fn is_tracker_bug(ctx: &Context, guild_id: GuildId, bug_id: u64) -> Option<bool> {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_first(
+        formatcp!(
+            "SELECT EXISTS(SELECT bug_id FROM {} WHERE bug_id = :bug_id AND guild_id = :guild_id)",
+            TABLE_BUG_REPORTS
+        ),
+        params! {
+            "bug_id" => bug_id,
+            "guild_id" => guild_id.0
+        },
+    )
+    .await
+    .ok()?
+}
+
+pub async fn is_bug_moderator(ctx: &Context, guild_id: GuildId, user_id: UserId) -> Option<bool> {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_first(
+        formatcp!(
+            "SELECT EXISTS(SELECT user_id FROM {} WHERE guild_id = :guild_id AND user_id = :user_id)",
+            TABLE_BUG_TRACKERS_MODERATORS
+        ),
+        params! {
+            "guild_id" => guild_id.0,
+            "user_id" => user_id.0
+        },
+    )
+    .await
+    .ok()?
+}
+
+pub async fn get_bug_moderators(ctx: &Context, guild_id: GuildId) -> Option<Vec<UserId>> {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_map(
+        formatcp!(
+            "SELECT user_id FROM {} WHERE guild_id = :guild_id",
+            TABLE_BUG_TRACKERS_MODERATORS
+        ),
+        params! {
+            "guild_id" => guild_id.0
+        },
+        UserId,
+    )
+    .await
+    .ok()
+}
+
+pub async fn add_bug_moderator(ctx: &Context, guild_id: GuildId, user_id: UserId) -> CommandResult {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_drop(
+        formatcp!(
+            "INSERT IGNORE INTO {} (guild_id, user_id) VALUES (:guild_id, :user_id)",
+            TABLE_BUG_TRACKERS_MODERATORS
+        ),
+        params! {
+            "guild_id" => guild_id.0,
+            "user_id" => user_id.0
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn remove_bug_moderator(
+    ctx: &Context,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> CommandResult {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_drop(
+        formatcp!(
+            "DELETE FROM {} WHERE guild_id = :guild_id AND user_id = :user_id",
+            TABLE_BUG_TRACKERS_MODERATORS
+        ),
+        params! {
+            "guild_id" => guild_id.0,
+            "user_id" => user_id.0
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BugTracker;
+    use serenity::model::id::GuildId;
+
+    #[test]
+    fn test_parse_bug_id() {
+        let tracker = BugTracker::new(GuildId(1), "EoA".into());
+
+        assert_eq!(tracker.parse_bug_id("EoA-42"), Some(42));
+        assert_eq!(tracker.parse_bug_id("eoa-42"), Some(42));
+        assert_eq!(tracker.parse_bug_id(" 42 "), Some(42));
+        assert_eq!(tracker.parse_bug_id("BUG-42"), None);
+        assert_eq!(tracker.parse_bug_id("Eoé"), None);
+        assert_eq!(tracker.parse_bug_id("Eoé-42"), None);
+        assert_eq!(tracker.parse_bug_id("EoA-é"), None);
+        assert_eq!(tracker.strip_prefix("éé"), "éé");
+    }
+}
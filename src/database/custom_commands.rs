@@ -1,10 +1,104 @@
-use mysql_async::prelude::*;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use const_format::formatcp;
+use dashmap::DashMap;
+use mysql_async::{prelude::*, TxOpts};
+use serde_json::Value;
 use serenity::client::Context;
-use serenity::framework::standard::CommandResult;
-use serenity::model::id::GuildId;
+use serenity::framework::standard::{CommandError, CommandResult};
+use serenity::model::id::{GuildId, UserId};
+use serenity::prelude::TypeMapKey;
+use std::sync::Arc;
 
-use crate::constants::TABLE_CUSTOM_COMMANDS;
+use crate::constants::{TABLE_CUSTOM_COMMANDS, TABLE_CUSTOM_COMMANDS_REVISIONS};
 use crate::get_database_conn;
+use crate::template::JsonTemplate;
+
+/// Parsed templates of the custom commands, by guild and command name
+#[derive(Debug, Clone)]
+pub struct CommandTemplateCache(Arc<DashMap<(GuildId, String), Arc<JsonTemplate>>>);
+
+impl TypeMapKey for CommandTemplateCache {
+    type Value = Self;
+}
+
+impl std::ops::Deref for CommandTemplateCache {
+    type Target = DashMap<(GuildId, String), Arc<JsonTemplate>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Default for CommandTemplateCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CommandTemplateCache {
+    pub fn new() -> Self {
+        Self(Arc::new(DashMap::new()))
+    }
+}
+
+/// Gets the parsed template of a custom command, parsing its body if it is not cached.
+///
+/// The strings that are not valid templates, in the commands stored before templates
+/// existed, are kept as plain text.
+pub async fn get_command_template(
+    ctx: &Context,
+    server_id: GuildId,
+    name: &str,
+    body: &Value,
+) -> Arc<JsonTemplate> {
+    let cache = ctx
+        .data
+        .read()
+        .await
+        .get::<CommandTemplateCache>()
+        .expect("Expected a command template cache in the type map")
+        .clone();
+
+    let key = (server_id, name.to_lowercase());
+    if let Some(template) = cache.get(&key) {
+        return template.value().clone();
+    }
+
+    let (template, errors) = JsonTemplate::parse_lenient(body);
+    if !errors.is_empty() {
+        println!(
+            "The custom command {} of guild {} has invalid templates, kept as text: {}",
+            name,
+            server_id,
+            errors
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    let template = Arc::new(template);
+    cache.insert(key, template.clone());
+    template
+}
+
+/// Caches the template of a custom command parsed when it was defined
+pub async fn set_command_template(
+    ctx: &Context,
+    server_id: GuildId,
+    name: &str,
+    template: JsonTemplate,
+) {
+    if let Some(cache) = ctx.data.read().await.get::<CommandTemplateCache>() {
+        cache.insert((server_id, name.to_lowercase()), Arc::new(template));
+    }
+}
+
+async fn forget_command_template(ctx: &Context, server_id: GuildId, name: &str) {
+    if let Some(cache) = ctx.data.read().await.get::<CommandTemplateCache>() {
+        cache.remove(&(server_id, name.to_lowercase()));
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct CustomCommand {
@@ -24,12 +118,15 @@ pub async fn check_command_exists(ctx: &Context, server_id: GuildId, name: &str)
     .ok()?
 }
 
+/// Adds or updates a custom command, and stores the result as a new revision in the
+/// same transaction
 pub async fn add_custom_command(
     ctx: &Context,
     server_id: GuildId,
     name: &str,
     body: &str,
     description: Option<&str>,
+    author_id: UserId,
 ) -> CommandResult {
     let mut conn = get_database_conn!(ctx);
     let query = if check_command_exists(ctx, server_id, name)
@@ -52,16 +149,54 @@ pub async fn add_custom_command(
         )
     };
 
-    conn.exec_drop(
-        query,
-        params! {
-            "server_id" => server_id.0,
-            "name" => name,
-            "body" => body,
-            "description" => description.unwrap_or_default()
-        },
-    )
-    .await?;
+    let mut transaction = conn.start_transaction(TxOpts::default()).await?;
+    transaction
+        .exec_drop(
+            query,
+            params! {
+                "server_id" => server_id.0,
+                "name" => name,
+                "body" => body,
+                "description" => description.unwrap_or_default()
+            },
+        )
+        .await?;
+
+    // the revisions are locked, so that concurrent updates cannot take the same number
+    let revision: u32 = transaction
+        .exec_first(
+            formatcp!(
+                "SELECT COALESCE(MAX(revision), 0) + 1 FROM {} \
+WHERE server_id = :server_id AND name = :name FOR UPDATE",
+                TABLE_CUSTOM_COMMANDS_REVISIONS
+            ),
+            params! {
+                "server_id" => server_id.0,
+                "name" => name
+            },
+        )
+        .await?
+        .unwrap_or(1);
+    // the documentation is kept when a command is updated without one
+    transaction
+        .exec_drop(
+            formatcp!(
+                "INSERT INTO {} (server_id, name, revision, command_json, documentation, \
+author_id) SELECT server_id, name, :revision, command_json, documentation, :author_id FROM {} \
+WHERE server_id = :server_id AND name = :name",
+                TABLE_CUSTOM_COMMANDS_REVISIONS,
+                TABLE_CUSTOM_COMMANDS
+            ),
+            params! {
+                "server_id" => server_id.0,
+                "name" => name,
+                "revision" => revision,
+                "author_id" => author_id.0
+            },
+        )
+        .await?;
+    transaction.commit().await?;
+    forget_command_template(ctx, server_id, name).await;
 
     Ok(())
 }
@@ -82,6 +217,7 @@ pub async fn remove_custom_command(ctx: &Context, server_id: GuildId, name: &str
         },
     )
     .await?;
+    forget_command_template(ctx, server_id, name).await;
 
     Ok(())
 }
@@ -132,15 +268,16 @@ pub async fn get_command_data(
     })
 }
 
+/// Names, documentations and JSON bodies of the custom commands of a server
 pub async fn get_custom_commands_list(
     ctx: &Context,
     server_id: GuildId,
-) -> Option<Vec<(String, String)>> {
+) -> Option<Vec<(String, String, String)>> {
     let mut conn = get_database_conn!(ctx);
 
     conn.exec(
         format!(
-            "SELECT name, documentation FROM {} WHERE server_id = :server_id ORDER BY documentation DESC",
+            "SELECT name, documentation, command_json FROM {} WHERE server_id = :server_id ORDER BY documentation DESC",
             TABLE_CUSTOM_COMMANDS
         )
         .as_str(),
@@ -151,3 +288,79 @@ pub async fn get_custom_commands_list(
     .await
     .ok()
 }
+
+/// A stored version of a custom command
+#[derive(Debug, Clone)]
+pub struct CommandRevision {
+    pub revision: u32,
+    pub body: String,
+    pub documentation: String,
+    /// `UserId(0)` for the revisions from before the history was kept
+    pub author_id: UserId,
+    pub timestamp: DateTime<Utc>,
+}
+
+type RevisionRow = (u32, String, String, u64, NaiveDateTime);
+
+impl From<RevisionRow> for CommandRevision {
+    fn from((revision, body, documentation, author_id, timestamp): RevisionRow) -> Self {
+        Self {
+            revision,
+            body,
+            documentation,
+            author_id: UserId(author_id),
+            timestamp: DateTime::from_utc(timestamp, Utc),
+        }
+    }
+}
+
+/// Gets the latest revisions of a custom command, newest first
+pub async fn get_command_revisions(
+    ctx: &Context,
+    server_id: GuildId,
+    name: &str,
+    limit: u32,
+) -> Result<Vec<CommandRevision>, CommandError> {
+    let mut conn = get_database_conn!(ctx);
+
+    Ok(conn
+        .exec_map(
+            formatcp!(
+                "SELECT revision, command_json, documentation, author_id, timestamp FROM {} \
+WHERE server_id = :server_id AND name = :name ORDER BY revision DESC LIMIT :limit",
+                TABLE_CUSTOM_COMMANDS_REVISIONS
+            ),
+            params! {
+                "server_id" => server_id.0,
+                "name" => name,
+                "limit" => limit
+            },
+            |row: RevisionRow| CommandRevision::from(row),
+        )
+        .await?)
+}
+
+pub async fn get_command_revision(
+    ctx: &Context,
+    server_id: GuildId,
+    name: &str,
+    revision: u32,
+) -> Result<Option<CommandRevision>, CommandError> {
+    let mut conn = get_database_conn!(ctx);
+
+    Ok(conn
+        .exec_first::<RevisionRow, _, _>(
+            formatcp!(
+                "SELECT revision, command_json, documentation, author_id, timestamp FROM {} \
+WHERE server_id = :server_id AND name = :name AND revision = :revision",
+                TABLE_CUSTOM_COMMANDS_REVISIONS
+            ),
+            params! {
+                "server_id" => server_id.0,
+                "name" => name,
+                "revision" => revision
+            },
+        )
+        .await?
+        .map(CommandRevision::from))
+}
@@ -3,6 +3,7 @@
 pub mod admin_data;
 pub mod blacklist;
 pub mod bug_reports;
+pub mod bug_trackers;
 pub mod config;
 pub mod custom_commands;
 pub mod floppa;
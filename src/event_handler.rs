@@ -9,6 +9,13 @@ use serenity::model::interactions::message_component::{
 use serenity::model::prelude::*;
 use serenity::utils::colours;
 
+use crate::commands::bug_reports::{
+    bug_application_command, bug_autocomplete, create_bug_application_command, handle_info_reply,
+    open_bug_report_form, submit_bug_report_form,
+};
+use crate::commands::custom_commands::custom_application_command;
+use crate::database::bug_trackers::{get_bug_prefix, get_bug_trackers};
+use crate::scheduler::SchedulerContext;
 use crate::utils::InteractionEasyResponse;
 
 pub struct Handler;
@@ -16,6 +23,34 @@ pub struct Handler;
 #[async_trait]
 impl EventHandler for Handler {
     async fn ready(&self, ctx: Context, ready: Ready) {
+        if let Some(scheduler) = ctx.data.read().await.get::<SchedulerContext>() {
+            scheduler.update(&ctx);
+        }
+
+        match get_bug_trackers(&ctx).await {
+            Ok(trackers) => {
+                for tracker in trackers {
+                    if let Err(e) = tracker
+                        .guild_id
+                        .create_application_command(&ctx, |c| {
+                            create_bug_application_command(c, &tracker)
+                        })
+                        .await
+                    {
+                        println!(
+                            "=== ERROR ===\nCould not register the bug slash command in guild {}: \
+{}\n=== END ===",
+                            tracker.guild_id, e
+                        );
+                    }
+                }
+            }
+            Err(e) => println!(
+                "=== ERROR ===\nCould not get the bug trackers: {}\n=== END ===",
+                e
+            ),
+        }
+
         ctx.set_activity(Activity::playing(
             "The Eras of Arda Community: creating addons and submods for the Lord of the Rings Mod.",
         ))
@@ -146,14 +181,20 @@ impl EventHandler for Handler {
     async fn message(&self, ctx: Context, message: Message) {
         let guild_id = match message.guild_id {
             None => return,
-            Some(guild_id) => {
-                if guild_id != EOA_DISCORD {
-                    return;
-                }
-                guild_id
-            }
+            Some(guild_id) => guild_id,
         };
 
+        if let Err(e) = handle_info_reply(&ctx, &message).await {
+            println!(
+                "=== ERROR ===\nCould not record a reply to a bug info request: {}\n=== END ===",
+                e
+            );
+        }
+
+        if guild_id != EOA_DISCORD {
+            return;
+        }
+
         if message.referenced_message.is_none() {
             return;
         }
@@ -162,6 +203,54 @@ impl EventHandler for Handler {
     }
 
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        match &interaction {
+            Interaction::ApplicationCommand(command) if command.data.name == "bug" => {
+                if let Err(e) = bug_application_command(&ctx, command).await {
+                    println!(
+                        "=== ERROR ===\nError in the bug slash command: {}\n=== END ===",
+                        e
+                    );
+                }
+                return;
+            }
+            Interaction::ApplicationCommand(command) => {
+                if let Err(e) = custom_application_command(&ctx, command).await {
+                    println!(
+                        "=== ERROR ===\nError in the custom slash command {}: {}\n=== END ===",
+                        command.data.name, e
+                    );
+                }
+                return;
+            }
+            Interaction::Autocomplete(autocomplete) if autocomplete.data.name == "bug" => {
+                if let Err(e) = bug_autocomplete(&ctx, autocomplete).await {
+                    println!(
+                        "=== ERROR ===\nCould not autocomplete bugs: {}\n=== END ===",
+                        e
+                    );
+                }
+                return;
+            }
+            Interaction::ModalSubmit(modal) => {
+                if let Some(channel_id) = modal
+                    .data
+                    .custom_id
+                    .strip_prefix("bug_report_modal__")
+                    .and_then(|s| s.parse::<u64>().ok())
+                {
+                    if let Err(e) = submit_bug_report_form(&ctx, modal, ChannelId(channel_id)).await
+                    {
+                        println!(
+                            "=== ERROR ===\nCould not submit bug report form: {}\n=== END ===",
+                            e
+                        );
+                    }
+                }
+                return;
+            }
+            _ => (),
+        }
+
         if let Interaction::MessageComponent(
             component_interaction @ MessageComponentInteraction {
                 user,
@@ -175,11 +264,24 @@ impl EventHandler for Handler {
             },
         ) = &interaction
         {
-            if let Some(bug_id) = custom_id
+            if let Some(channel_id) = custom_id
+                .strip_prefix("bug_report_form__")
+                .and_then(|s| s.parse::<u64>().ok())
+            {
+                if let Err(e) =
+                    open_bug_report_form(&ctx, component_interaction, ChannelId(channel_id)).await
+                {
+                    println!(
+                        "=== ERROR ===\nCould not open bug report form: {}\n=== END ===",
+                        e
+                    );
+                }
+            } else if let Some(bug_id) = custom_id
                 .strip_prefix("bug_unsubscribe__")
                 .map(|s| s.parse::<u64>().ok())
                 .flatten()
             {
+                let prefix = get_bug_prefix(&ctx, bug_id).await;
                 if crate::database::bug_reports::is_notified_user(&ctx, bug_id, user.id).await
                     != Some(true)
                 {
@@ -187,10 +289,10 @@ impl EventHandler for Handler {
                         .say_ephemeral(
                             &ctx,
                             format!(
-                                ":x: You are not subscribed to bug EoA-{}.
+                                ":x: You are not subscribed to bug {}-{}.
 
 To see all your active notifications type  `!bug notifications`",
-                                bug_id
+                                prefix, bug_id
                             ),
                         )
                         .await
@@ -199,7 +301,7 @@ To see all your active notifications type  `!bug notifications`",
                 {
                     println!(
                         "=== ERROR ===\nCould not remove {} {:?} \
-from EoA-{} notifications\nError: {}\n=== END ===",
+from bug #{} notifications\nError: {}\n=== END ===",
                         user.tag(),
                         user.id,
                         bug_id,
@@ -210,10 +312,10 @@ from EoA-{} notifications\nError: {}\n=== END ===",
                         .say_ephemeral(
                             &ctx,
                             format!(
-                                "You have successfully been unsubscribed from bug EoA-{}.
+                                "You have successfully been unsubscribed from bug {}-{}.
 
 To see all your active notifications type  `!bug notifications`",
-                                bug_id
+                                prefix, bug_id
                             ),
                         )
                         .await;
@@ -223,6 +325,7 @@ To see all your active notifications type  `!bug notifications`",
                 .map(|s| s.parse::<u64>().ok())
                 .flatten()
             {
+                let prefix = get_bug_prefix(&ctx, bug_id).await;
                 if crate::database::bug_reports::is_notified_user(&ctx, bug_id, user.id).await
                     != Some(false)
                 {
@@ -230,10 +333,10 @@ To see all your active notifications type  `!bug notifications`",
                         .say_ephemeral(
                             &ctx,
                             format!(
-                                ":x: You are already subscribed to bug EoA-{}.
+                                ":x: You are already subscribed to bug {}-{}.
 
 To see all your active notifications type  `!bug notifications`",
-                                bug_id
+                                prefix, bug_id
                             ),
                         )
                         .await
@@ -242,7 +345,7 @@ To see all your active notifications type  `!bug notifications`",
                 {
                     println!(
                         "=== ERROR ===\nCould not add {} {:?} \
-to EoA-{} notifications\nError: {}\n=== END ===",
+to bug #{} notifications\nError: {}\n=== END ===",
                         user.tag(),
                         user.id,
                         bug_id,
@@ -253,14 +356,55 @@ to EoA-{} notifications\nError: {}\n=== END ===",
                         .say_ephemeral(
                             &ctx,
                             format!(
-                                "You have successfully been subscribed to bug EoA-{}.
+                                "You have successfully been subscribed to bug {}-{}.
 
 To see all your active notifications type  `!bug notifications`",
-                                bug_id
+                                prefix, bug_id
                             ),
                         )
                         .await;
                 }
+            } else if let Some(bug_id) = custom_id
+                .strip_prefix("bug_affected__")
+                .map(|s| s.parse::<u64>().ok())
+                .flatten()
+            {
+                let prefix = get_bug_prefix(&ctx, bug_id).await;
+                match crate::database::bug_reports::add_affected_user(&ctx, bug_id, user.id).await {
+                    Ok(Some(count)) => {
+                        component_interaction
+                            .say_ephemeral(
+                                &ctx,
+                                format!(
+                                    "Thanks, bug {}-{} now affects {} user{}.",
+                                    prefix,
+                                    bug_id,
+                                    count,
+                                    if count == 1 { "" } else { "s" }
+                                ),
+                            )
+                            .await
+                    }
+                    Ok(None) => {
+                        component_interaction
+                            .say_ephemeral(
+                                &ctx,
+                                format!(
+                                    ":x: You already reported having bug {}-{}.",
+                                    prefix, bug_id
+                                ),
+                            )
+                            .await
+                    }
+                    Err(e) => println!(
+                        "=== ERROR ===\nCould not add {} {:?} \
+to the users affected by bug #{}\nError: {}\n=== END ===",
+                        user.tag(),
+                        user.id,
+                        bug_id,
+                        e
+                    ),
+                }
             }
         }
     }
@@ -10,12 +10,16 @@
 pub mod announcement;
 pub mod api;
 pub mod check;
+pub mod command_arguments;
 pub mod commands;
 pub mod constants;
+pub mod crash_logs;
 pub mod database;
 pub mod event_handler;
 pub mod qa_answers;
 pub mod role_cache;
+pub mod scheduler;
+pub mod template;
 pub mod utils;
 
 use mysql_async::OptsBuilder;
@@ -36,11 +40,13 @@ use commands::{
 use constants::{BOT_ID, OWNER_ID};
 use database::{
     config::{get_prefix, PrefixCache},
+    custom_commands::CommandTemplateCache,
     qa_data::QaChannelsCache,
     DatabasePool,
 };
 use event_handler::Handler;
 use role_cache::RoleCache;
+use scheduler::{run_scheduler, SchedulerContext};
 
 #[group]
 #[commands(
@@ -71,7 +77,7 @@ struct Meme;
 struct Wiki;
 
 #[group]
-#[commands(track, buglist, bug, resolve)]
+#[commands(track, buglist, bug, resolve, crashlog)]
 struct BugReports;
 
 #[group]
@@ -147,6 +153,8 @@ async fn main() {
     let role_cache = RoleCache::new();
     let prefix_cache = PrefixCache::new();
     let qa_channels_cache = QaChannelsCache::new();
+    let command_template_cache = CommandTemplateCache::new();
+    let (scheduler_context, scheduler_receiver) = SchedulerContext::channel();
 
     // initialize bot framework
     let framework = StandardFramework::new()
@@ -196,7 +204,9 @@ async fn main() {
         .type_map_insert::<RoleCache>(role_cache)
         .type_map_insert::<PrefixCache>(prefix_cache)
         .type_map_insert::<QaChannelsCache>(qa_channels_cache)
+        .type_map_insert::<CommandTemplateCache>(command_template_cache)
         .type_map_insert::<FrameworkKey>(framework)
+        .type_map_insert::<SchedulerContext>(scheduler_context)
         .await
         .expect("Error creating client");
 
@@ -211,6 +221,9 @@ async fn main() {
         });
     }
 
+    // background tasks, like the weekly bug digest
+    tokio::spawn(run_scheduler(scheduler_receiver));
+
     #[cfg(unix)]
     {
         // Sigterm listener
@@ -0,0 +1,85 @@
+//! Background tasks running at a regular interval, like the weekly
+//! [bug digest][crate::commands::bug_reports::post_bug_digests], the
+//! [bug info reminders][crate::commands::bug_reports::process_info_requests] and the
+//! daily [notification digests][crate::commands::bug_reports::send_notification_digests],
+//! and the more frequent [issue synchronisation][crate::commands::bug_reports::sync_mirrored_issues].
+
+use serenity::client::Context;
+use serenity::prelude::TypeMapKey;
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Interval between two runs of the scheduled tasks
+pub const SCHEDULER_INTERVAL: Duration = Duration::from_secs(3600);
+/// Interval between two synchronisations of the issues mirroring bug reports
+pub const ISSUE_SYNC_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Sends the latest context to the scheduler every time the bot is ready.
+pub struct SchedulerContext(watch::Sender<Option<Context>>);
+
+impl TypeMapKey for SchedulerContext {
+    type Value = Self;
+}
+
+impl SchedulerContext {
+    /// Creates the context sender, and the receiving end to give to [`run_scheduler`]
+    pub fn channel() -> (Self, watch::Receiver<Option<Context>>) {
+        let (sender, receiver) = watch::channel(None);
+        (Self(sender), receiver)
+    }
+
+    pub fn update(&self, ctx: &Context) {
+        // only fails if the scheduler has stopped
+        let _ = self.0.send(Some(ctx.clone()));
+    }
+}
+
+/// Runs the scheduled tasks forever, starting as soon as the bot is ready.
+pub async fn run_scheduler(mut receiver: watch::Receiver<Option<Context>>) {
+    if receiver.changed().await.is_err() {
+        return;
+    }
+
+    let mut interval = tokio::time::interval(SCHEDULER_INTERVAL);
+    let mut issue_sync_interval = tokio::time::interval(ISSUE_SYNC_INTERVAL);
+    loop {
+        let issue_sync = tokio::select! {
+            _ = interval.tick() => false,
+            _ = issue_sync_interval.tick() => true,
+        };
+
+        let ctx = match receiver.borrow().clone() {
+            Some(ctx) => ctx,
+            None => continue,
+        };
+
+        if issue_sync {
+            if let Err(e) = crate::commands::bug_reports::sync_mirrored_issues(&ctx).await {
+                println!(
+                    "=== ERROR ===\nCould not synchronise the mirrored issues: {}\n=== END ===",
+                    e
+                );
+            }
+            continue;
+        }
+
+        if let Err(e) = crate::commands::bug_reports::post_bug_digests(&ctx).await {
+            println!(
+                "=== ERROR ===\nCould not post the bug digests: {}\n=== END ===",
+                e
+            );
+        }
+        if let Err(e) = crate::commands::bug_reports::process_info_requests(&ctx).await {
+            println!(
+                "=== ERROR ===\nCould not process the bug info requests: {}\n=== END ===",
+                e
+            );
+        }
+        if let Err(e) = crate::commands::bug_reports::send_notification_digests(&ctx).await {
+            println!(
+                "=== ERROR ===\nCould not send the notification digests: {}\n=== END ===",
+                e
+            );
+        }
+    }
+}
@@ -0,0 +1,698 @@
+//! Template language of the [custom commands][crate::commands::custom_commands],
+//! parsed once when a command is defined.
+//!
+//! ```text
+//! {{user.name}} {{arg0}} {{args}}              variables
+//! {{arg0 | lower | url-encode}}                filters: upper, lower, url-encode
+//! {{#if arg1}} ... {{else}} ... {{/if}}        conditionals, true for non-empty values
+//! {{#each args}}{{index}}: {{this}}{{/each}}   loops over a list
+//! $me $ping $channel $args $0                  legacy variables
+//! ```
+//!
+//! `\{{` and `\$` are rendered as `{{` and `$`.
+//!
+//! The commands stored before templates existed may have strings that are not valid
+//! templates: these strings are kept as plain text when the commands are loaded.
+
+use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    /// A tag opened with `{{` is never closed
+    UnclosedTag,
+    EmptyTag,
+    InvalidVariable(String),
+    UnknownFilter(String),
+    UnknownBlock(String),
+    /// A block is never closed, like `{{#if arg0}}` without `{{/if}}`
+    UnclosedBlock(String),
+    /// A closing tag does not match the open block
+    UnexpectedClose(String),
+    UnexpectedElse,
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use TemplateError::*;
+
+        match self {
+            UnclosedTag => write!(f, "a `{{{{` tag is not closed with `}}}}`"),
+            EmptyTag => write!(f, "a `{{{{}}}}` tag is empty"),
+            InvalidVariable(name) => write!(f, "`{}` is not a valid variable name", name),
+            UnknownFilter(name) => write!(
+                f,
+                "unknown filter `{}`, expected `upper`, `lower` or `url-encode`",
+                name
+            ),
+            UnknownBlock(name) => {
+                write!(f, "unknown block `#{}`, expected `#if` or `#each`", name)
+            }
+            UnclosedBlock(name) => write!(
+                f,
+                "the `#{0}` block is not closed with `{{{{/{0}}}}}`",
+                name
+            ),
+            UnexpectedClose(name) => write!(f, "unexpected `{{{{/{}}}}}`", name),
+            UnexpectedElse => write!(f, "`{{{{else}}}}` is only allowed in an `#if` block"),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateValue {
+    Text(String),
+    List(Vec<String>),
+}
+
+impl TemplateValue {
+    /// Empty texts and lists are false in conditionals
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Self::Text(text) => !text.is_empty(),
+            Self::List(list) => !list.is_empty(),
+        }
+    }
+
+    /// Lists are displayed separated with spaces
+    pub fn as_text(&self) -> Cow<'_, str> {
+        match self {
+            Self::Text(text) => Cow::Borrowed(text),
+            Self::List(list) => Cow::Owned(list.join(" ")),
+        }
+    }
+
+    fn items(&self) -> &[String] {
+        match self {
+            Self::Text(text) => std::slice::from_ref(text),
+            Self::List(list) => list,
+        }
+    }
+}
+
+/// Variables available to a template
+#[derive(Debug, Clone, Default)]
+pub struct TemplateContext {
+    variables: HashMap<String, TemplateValue>,
+}
+
+impl TemplateContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<TemplateValue>) -> &mut Self {
+        self.variables.insert(name.into(), value.into());
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&TemplateValue> {
+        self.variables.get(name)
+    }
+}
+
+impl From<String> for TemplateValue {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for TemplateValue {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+impl From<Vec<String>> for TemplateValue {
+    fn from(list: Vec<String>) -> Self {
+        Self::List(list)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Filter {
+    Upper,
+    Lower,
+    UrlEncode,
+}
+
+impl Filter {
+    fn parse(name: &str) -> Result<Self, TemplateError> {
+        match name {
+            "upper" => Ok(Self::Upper),
+            "lower" => Ok(Self::Lower),
+            "url-encode" | "urlencode" => Ok(Self::UrlEncode),
+            _ => Err(TemplateError::UnknownFilter(name.to_string())),
+        }
+    }
+
+    fn apply(&self, text: &str) -> String {
+        match self {
+            Self::Upper => text.to_uppercase(),
+            Self::Lower => text.to_lowercase(),
+            Self::UrlEncode => url_encode(text),
+        }
+    }
+}
+
+/// Percent-encodes everything except the unreserved characters of RFC 3986
+fn url_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+fn is_valid_variable(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+fn parse_variable(name: &str) -> Result<String, TemplateError> {
+    let name = name.trim();
+    if is_valid_variable(name) {
+        Ok(name.to_string())
+    } else {
+        Err(TemplateError::InvalidVariable(name.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Expression {
+    variable: String,
+    filters: Vec<Filter>,
+}
+
+impl Expression {
+    fn parse(expression: &str) -> Result<Self, TemplateError> {
+        let mut parts = expression.split('|');
+        let variable = parse_variable(parts.next().unwrap_or_default())?;
+        let filters = parts
+            .map(|filter| Filter::parse(filter.trim()))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { variable, filters })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Text(String),
+    Expression(Expression),
+    If {
+        condition: String,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    Each {
+        list: String,
+        body: Vec<Node>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Text(String),
+    /// Content of a `{{ }}` tag, or the equivalent of a legacy variable
+    Tag(String),
+}
+
+/// Name of a legacy `$` variable at the start of a string, and its length
+fn legacy_variable(s: &str) -> Option<(String, usize)> {
+    for (legacy, variable) in [
+        ("$me", "user.name"),
+        ("$ping", "user.mention"),
+        ("$channel", "channel.mention"),
+        ("$args", "args"),
+    ] {
+        if s.starts_with(legacy) {
+            return Some((variable.to_string(), legacy.len()));
+        }
+    }
+    let digits = s[1..].bytes().take_while(u8::is_ascii_digit).count();
+    (digits > 0).then(|| (format!("arg{}", &s[1..1 + digits]), 1 + digits))
+}
+
+fn tokenize(mut source: &str) -> Result<Vec<Token>, TemplateError> {
+    let mut tokens = Vec::new();
+    let mut text = String::new();
+
+    while let Some(i) = source.find(['{', '$', '\\']) {
+        text.push_str(&source[..i]);
+        source = &source[i..];
+
+        if let Some(rest) = source.strip_prefix("\\{{") {
+            text.push_str("{{");
+            source = rest;
+        } else if let Some(rest) = source.strip_prefix("\\$") {
+            text.push('$');
+            source = rest;
+        } else if let Some(rest) = source.strip_prefix("{{") {
+            let end = rest.find("}}").ok_or(TemplateError::UnclosedTag)?;
+            let tag = rest[..end].trim();
+            if tag.is_empty() {
+                return Err(TemplateError::EmptyTag);
+            }
+            tokens.push(Token::Text(std::mem::take(&mut text)));
+            tokens.push(Token::Tag(tag.to_string()));
+            source = &rest[end + 2..];
+        } else if let Some((variable, len)) = source
+            .starts_with('$')
+            .then(|| legacy_variable(source))
+            .flatten()
+        {
+            tokens.push(Token::Text(std::mem::take(&mut text)));
+            tokens.push(Token::Tag(variable));
+            source = &source[len..];
+        } else {
+            text.push_str(&source[..1]);
+            source = &source[1..];
+        }
+    }
+    text.push_str(source);
+    tokens.push(Token::Text(text));
+
+    tokens.retain(|token| token != &Token::Text(String::new()));
+    Ok(tokens)
+}
+
+/// How a sequence of nodes ended
+enum End {
+    Eof,
+    Else,
+    Close(String),
+}
+
+fn parse_nodes(tokens: &mut std::vec::IntoIter<Token>) -> Result<(Vec<Node>, End), TemplateError> {
+    let mut nodes = Vec::new();
+
+    while let Some(token) = tokens.next() {
+        let tag = match token {
+            Token::Text(text) => {
+                nodes.push(Node::Text(text));
+                continue;
+            }
+            Token::Tag(tag) => tag,
+        };
+
+        if let Some(block) = tag.strip_prefix('#') {
+            let (name, argument) = block.split_once(char::is_whitespace).unwrap_or((block, ""));
+            match name {
+                "if" => {
+                    let condition = parse_variable(argument)?;
+                    let (then, end) = parse_nodes(tokens)?;
+                    let otherwise = match end {
+                        End::Else => match parse_nodes(tokens)? {
+                            (otherwise, End::Close(close)) if close == "if" => otherwise,
+                            (_, End::Else) => return Err(TemplateError::UnexpectedElse),
+                            (_, End::Close(close)) => {
+                                return Err(TemplateError::UnexpectedClose(close))
+                            }
+                            (_, End::Eof) => return Err(TemplateError::UnclosedBlock("if".into())),
+                        },
+                        End::Close(close) if close == "if" => Vec::new(),
+                        End::Close(close) => return Err(TemplateError::UnexpectedClose(close)),
+                        End::Eof => return Err(TemplateError::UnclosedBlock("if".into())),
+                    };
+                    nodes.push(Node::If {
+                        condition,
+                        then,
+                        otherwise,
+                    });
+                }
+                "each" => {
+                    let list = parse_variable(argument)?;
+                    let body = match parse_nodes(tokens)? {
+                        (body, End::Close(close)) if close == "each" => body,
+                        (_, End::Else) => return Err(TemplateError::UnexpectedElse),
+                        (_, End::Close(close)) => {
+                            return Err(TemplateError::UnexpectedClose(close))
+                        }
+                        (_, End::Eof) => return Err(TemplateError::UnclosedBlock("each".into())),
+                    };
+                    nodes.push(Node::Each { list, body });
+                }
+                _ => return Err(TemplateError::UnknownBlock(name.to_string())),
+            }
+        } else if let Some(close) = tag.strip_prefix('/') {
+            return Ok((nodes, End::Close(close.trim().to_string())));
+        } else if tag == "else" {
+            return Ok((nodes, End::Else));
+        } else {
+            nodes.push(Node::Expression(Expression::parse(&tag)?));
+        }
+    }
+
+    Ok((nodes, End::Eof))
+}
+
+/// Variables of a template, and of the loops it is rendered in
+struct Scope<'a> {
+    context: &'a TemplateContext,
+    /// Index and value of the current loop item
+    item: Option<(usize, &'a str)>,
+}
+
+impl<'a> Scope<'a> {
+    fn get(&self, name: &str) -> Option<Cow<'a, TemplateValue>> {
+        match (name, self.item) {
+            ("this", Some((_, item))) => Some(Cow::Owned(item.into())),
+            ("index", Some((index, _))) => Some(Cow::Owned(index.to_string().into())),
+            _ => self.context.get(name).map(Cow::Borrowed),
+        }
+    }
+}
+
+fn render_nodes(nodes: &[Node], scope: &Scope, output: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => output.push_str(text),
+            Node::Expression(expression) => {
+                if let Some(value) = scope.get(&expression.variable) {
+                    let text = expression
+                        .filters
+                        .iter()
+                        .fold(value.as_text().into_owned(), |text, filter| {
+                            filter.apply(&text)
+                        });
+                    output.push_str(&text);
+                }
+            }
+            Node::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                let is_true = scope
+                    .get(condition)
+                    .map_or(false, |value| value.is_truthy());
+                render_nodes(if is_true { then } else { otherwise }, scope, output);
+            }
+            Node::Each { list, body } => {
+                if let Some(value) = scope.get(list) {
+                    for (index, item) in value.items().iter().enumerate() {
+                        let scope = Scope {
+                            context: scope.context,
+                            item: Some((index, item)),
+                        };
+                        render_nodes(body, &scope, output);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Self, TemplateError> {
+        let mut tokens = tokenize(source)?.into_iter();
+        match parse_nodes(&mut tokens)? {
+            (nodes, End::Eof) => Ok(Self { nodes }),
+            (_, End::Else) => Err(TemplateError::UnexpectedElse),
+            (_, End::Close(close)) => Err(TemplateError::UnexpectedClose(close)),
+        }
+    }
+
+    /// Template rendered as the given text, without any variable
+    pub fn text(text: &str) -> Self {
+        Self {
+            nodes: vec![Node::Text(text.to_string())],
+        }
+    }
+
+    /// Whether the template has no variable, and is always rendered the same
+    pub fn is_static(&self) -> bool {
+        self.nodes.iter().all(|node| matches!(node, Node::Text(_)))
+    }
+
+    pub fn render(&self, context: &TemplateContext) -> String {
+        let mut output = String::new();
+        render_nodes(
+            &self.nodes,
+            &Scope {
+                context,
+                item: None,
+            },
+            &mut output,
+        );
+        output
+    }
+}
+
+/// Syntax error in a string of a JSON template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonTemplateError {
+    /// Path of the string in the JSON, like `embed.fields[0]`
+    pub path: String,
+    pub error: TemplateError,
+}
+
+impl fmt::Display for JsonTemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "in `{}`: {}", self.path, self.error)
+    }
+}
+
+impl std::error::Error for JsonTemplateError {}
+
+/// A JSON value whose strings are templates
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonTemplate {
+    String(Template),
+    Array(Vec<JsonTemplate>),
+    Object(Vec<(String, JsonTemplate)>),
+    /// Numbers, booleans and null
+    Other(Value),
+}
+
+impl JsonTemplate {
+    /// Parses the strings of a JSON value, failing on the first syntax error
+    pub fn parse(value: &Value) -> Result<Self, JsonTemplateError> {
+        let (template, mut errors) = Self::parse_lenient(value);
+        if errors.is_empty() {
+            Ok(template)
+        } else {
+            Err(errors.swap_remove(0))
+        }
+    }
+
+    /// Parses the strings of a JSON value, keeping the ones with a syntax error as
+    /// plain text. The syntax errors are returned with the template.
+    pub fn parse_lenient(value: &Value) -> (Self, Vec<JsonTemplateError>) {
+        let mut errors = Vec::new();
+        let template = Self::parse_at(value, "", &mut errors);
+        (template, errors)
+    }
+
+    fn parse_at(value: &Value, path: &str, errors: &mut Vec<JsonTemplateError>) -> Self {
+        match value {
+            Value::String(s) => Self::String(Template::parse(s).unwrap_or_else(|error| {
+                errors.push(JsonTemplateError {
+                    path: path.to_string(),
+                    error,
+                });
+                Template::text(s)
+            })),
+            Value::Array(array) => Self::Array(
+                array
+                    .iter()
+                    .enumerate()
+                    .map(|(i, value)| Self::parse_at(value, &format!("{}[{}]", path, i), errors))
+                    .collect(),
+            ),
+            Value::Object(map) => Self::Object(
+                map.iter()
+                    .map(|(key, value)| {
+                        let path = if path.is_empty() {
+                            key.clone()
+                        } else {
+                            format!("{}.{}", path, key)
+                        };
+                        (key.clone(), Self::parse_at(value, &path, errors))
+                    })
+                    .collect(),
+            ),
+            other => Self::Other(other.clone()),
+        }
+    }
+
+    /// Gets a field of an object
+    pub fn get(&self, key: &str) -> Option<&Self> {
+        match self {
+            Self::Object(fields) => fields
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Adds the `"variables"` of an object to a context. Their values are templates
+    /// themselves, rendered with the context before they are added.
+    pub fn add_variables(&self, context: &mut TemplateContext) {
+        if let Some(Self::Object(variables)) = self.get("variables") {
+            let rendered = variables
+                .iter()
+                .filter_map(|(name, value)| match value {
+                    Self::String(template) => Some((name.clone(), template.render(context))),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            for (name, value) in rendered {
+                context.set(name, value);
+            }
+        }
+    }
+
+    pub fn render(&self, context: &TemplateContext) -> Value {
+        match self {
+            Self::String(template) => Value::String(template.render(context)),
+            Self::Array(array) => Value::Array(array.iter().map(|v| v.render(context)).collect()),
+            Self::Object(fields) => Value::Object(
+                fields
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.render(context)))
+                    .collect(),
+            ),
+            Self::Other(value) => value.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> TemplateContext {
+        let mut context = TemplateContext::new();
+        context
+            .set("user.name", "Aldan")
+            .set("user.mention", "<@42>")
+            .set("arg0", "Gondor")
+            .set("arg1", "")
+            .set("args", vec!["Gondor".to_string(), "Rohan".to_string()]);
+        context
+    }
+
+    fn render(source: &str) -> String {
+        Template::parse(source).unwrap().render(&context())
+    }
+
+    #[test]
+    fn test_variables_and_filters() {
+        assert_eq!(render("Hi {{ user.name }}!"), "Hi Aldan!");
+        assert_eq!(
+            render("{{arg0 | upper}} {{ args|lower }}"),
+            "GONDOR gondor rohan"
+        );
+        assert_eq!(render("wiki/{{args | url-encode}}"), "wiki/Gondor%20Rohan");
+        assert_eq!(render("{{unknown}}."), ".");
+        assert_eq!(render("\\{{arg0}} costs \\$5"), "{{arg0}} costs $5");
+    }
+
+    #[test]
+    fn test_legacy_variables() {
+        assert_eq!(
+            render("$ping: $0 $args $me"),
+            "<@42>: Gondor Gondor Rohan Aldan"
+        );
+        assert_eq!(render("$ and $x"), "$ and $x");
+    }
+
+    #[test]
+    fn test_blocks() {
+        assert_eq!(
+            render("{{#if arg0}}yes{{else}}no{{/if}} {{#if arg1}}yes{{else}}no{{/if}}"),
+            "yes no"
+        );
+        assert_eq!(
+            render("{{#each args}}{{index}}:{{this | upper}} {{/each}}"),
+            "0:GONDOR 1:ROHAN "
+        );
+        assert_eq!(
+            render("{{#each args}}{{#if this}}[{{this}}]{{/if}}{{/each}}"),
+            "[Gondor][Rohan]"
+        );
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        use TemplateError::*;
+
+        assert_eq!(Template::parse("{{arg0"), Err(UnclosedTag));
+        assert_eq!(Template::parse("{{ }}"), Err(EmptyTag));
+        assert_eq!(
+            Template::parse("{{arg0 | shout}}"),
+            Err(UnknownFilter("shout".into()))
+        );
+        assert_eq!(
+            Template::parse("{{#if arg0}}yes"),
+            Err(UnclosedBlock("if".into()))
+        );
+        assert_eq!(
+            Template::parse("{{#each args}}{{/if}}"),
+            Err(UnexpectedClose("if".into()))
+        );
+        assert_eq!(Template::parse("{{else}}"), Err(UnexpectedElse));
+        assert_eq!(
+            Template::parse("{{#unless arg0}}{{/unless}}"),
+            Err(UnknownBlock("unless".into()))
+        );
+        assert_eq!(
+            Template::parse("{{user name}}"),
+            Err(InvalidVariable("user name".into()))
+        );
+    }
+
+    #[test]
+    fn test_json_template() {
+        let value = serde_json::json!({
+            "variables": {"region": "{{arg0 | lower}}"},
+            "content": "{{user.mention}} visits {{region}}",
+            "embed": {"fields": [["Count", "{{#each args}}+{{/each}}", true]]}
+        });
+        let template = JsonTemplate::parse(&value).unwrap();
+        let mut context = context();
+        template.add_variables(&mut context);
+
+        let rendered = template.render(&context);
+        assert_eq!(rendered["content"], "<@42> visits gondor");
+        assert_eq!(rendered["embed"]["fields"][0][1], "++");
+        assert_eq!(rendered["embed"]["fields"][0][2], true);
+
+        let error = JsonTemplate::parse(&serde_json::json!({
+            "embed": {"fields": [["Title", "{{#if arg0}}"]]}
+        }))
+        .unwrap_err();
+        assert_eq!(error.path, "embed.fields[0][1]");
+    }
+
+    #[test]
+    fn test_lenient_json_template() {
+        let (template, errors) = JsonTemplate::parse_lenient(&serde_json::json!({
+            "content": "Use {{ }} to write {{arg0}}",
+            "title": "{{user.name}}"
+        }));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "content");
+
+        let rendered = template.render(&context());
+        assert_eq!(rendered["content"], "Use {{ }} to write {{arg0}}");
+        assert_eq!(rendered["title"], "Aldan");
+    }
+}
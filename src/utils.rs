@@ -50,15 +50,17 @@ pub async fn get_json_from_message<T: DeserializeOwned>(
         );
         serde_json::from_str(&content[a..=b]).map_err(JsonError)
     } else {
-        let a = &msg.attachments[0];
-        if a.size <= MAX_JSON_FILE_SIZE {
-            match a.download().await {
-                Ok(json_data) => serde_json::from_slice(&json_data).map_err(JsonError),
-                Err(e) => Err(DownloadError(e)),
-            }
-        } else {
-            Err(FileTooBig(a.size))
-        }
+        let json_data = download_attachment(&msg.attachments[0]).await?;
+        serde_json::from_slice(&json_data).map_err(JsonError)
+    }
+}
+
+/// Downloads an attachment if it is not bigger than [`MAX_JSON_FILE_SIZE`]
+pub async fn download_attachment(a: &Attachment) -> Result<Vec<u8>, JsonMessageError> {
+    if a.size <= MAX_JSON_FILE_SIZE {
+        a.download().await.map_err(DownloadError)
+    } else {
+        Err(FileTooBig(a.size))
     }
 }
 
@@ -250,11 +252,110 @@ pub fn parse_motd(motd: impl ToString) -> String {
     res
 }
 
-pub fn to_json_safe_string(s: impl ToString) -> String {
-    // serialize as string to get string escapes
-    let s = serde_json::ser::to_string(&serde_json::Value::String(s.to_string())).unwrap();
-    // remove the surrounding quotes
-    s[1..s.len() - 1].to_string()
+/// Line by line diff of two texts, with `- ` before the removed lines, `+ ` before the
+/// added ones, and `  ` before the unchanged ones. Only `context` unchanged lines are
+/// kept around each change, the others are replaced with `...`.
+pub fn line_diff(old: &str, new: &str, context: usize) -> Vec<String> {
+    let old = old.lines().collect::<Vec<_>>();
+    let new = new.lines().collect::<Vec<_>>();
+
+    // lengths of the longest common subsequences of the ends of the texts
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push((' ', old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(('-', old[i]));
+            i += 1;
+        } else {
+            lines.push(('+', new[j]));
+            j += 1;
+        }
+    }
+
+    let is_near_change = |k: usize| {
+        lines[k.saturating_sub(context)..(k + context + 1).min(lines.len())]
+            .iter()
+            .any(|(kind, _)| *kind != ' ')
+    };
+    let mut diff = Vec::new();
+    for (k, (kind, line)) in lines.iter().enumerate() {
+        if *kind != ' ' || is_near_change(k) {
+            diff.push(format!("{} {}", kind, line));
+        } else if diff.last().map_or(true, |last| last != "...") {
+            diff.push("...".into());
+        }
+    }
+    diff
+}
+
+pub fn to_csv_safe_string(s: &str) -> String {
+    if s.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Parses CSV content into rows of fields.
+///
+/// Quoted fields may contain commas, line breaks and doubled quotes.
+/// Returns `None` if a quoted field is never closed.
+pub fn parse_csv(content: &str) -> Option<Vec<Vec<String>>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+        } else {
+            match c {
+                '"' => in_quotes = true,
+                ',' => row.push(std::mem::take(&mut field)),
+                '\r' if chars.peek() == Some(&'\n') => (),
+                '\n' | '\r' => {
+                    row.push(std::mem::take(&mut field));
+                    rows.push(std::mem::take(&mut row));
+                }
+                _ => field.push(c),
+            }
+        }
+    }
+    if in_quotes {
+        return None;
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    // skip blank lines
+    rows.retain(|row| row.len() > 1 || !row[0].is_empty());
+
+    Some(rows)
 }
 
 use serenity::utils::Colour;
@@ -418,6 +519,7 @@ use serenity::async_trait;
 use serenity::builder::CreateInteractionResponse;
 use serenity::http::Http;
 use serenity::model::interactions::{
+    application_command::ApplicationCommandInteraction,
     message_component::MessageComponentInteraction, InteractionApplicationCommandCallbackDataFlags,
 };
 
@@ -476,14 +578,77 @@ Error: {}
     }
 }
 
+#[async_trait]
+impl InteractionEasyResponse for ApplicationCommandInteraction {
+    async fn respond_no_failure<F>(
+        &self,
+        ctx: impl AsRef<Http> + Send + Sync + 'async_trait,
+        f: F,
+    ) -> ()
+    where
+        F: 'async_trait
+            + Send
+            + Sync
+            + FnOnce(&mut CreateInteractionResponse) -> &mut CreateInteractionResponse,
+    {
+        if let Err(e) = self.create_interaction_response(ctx, f).await {
+            println!(
+                "=== ERROR ===
+Error sending application command response to {} {:?}
+Error: {}
+=== END ===",
+                self.user.tag(),
+                self.user.id,
+                e
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::to_json_safe_string;
+    use super::{line_diff, parse_csv, to_csv_safe_string};
+
+    #[test]
+    fn test_line_diff() {
+        let old = "{\n  \"content\": \"hi\",\n  \"a\": 1,\n  \"b\": 2,\n  \"c\": 3\n}";
+        let new =
+            "{\n  \"content\": \"hello\",\n  \"a\": 1,\n  \"b\": 2,\n  \"c\": 3,\n  \"d\": 4\n}";
+
+        assert_eq!(
+            line_diff(old, new, 0),
+            [
+                "...",
+                "-   \"content\": \"hi\",",
+                "+   \"content\": \"hello\",",
+                "...",
+                "-   \"c\": 3",
+                "+   \"c\": 3,",
+                "+   \"d\": 4",
+                "...",
+            ]
+        );
+        assert_eq!(line_diff(old, new, 1)[0], "  {");
+        assert_eq!(line_diff(old, old, 2), ["..."]);
+    }
 
     #[test]
-    fn test_json_safe_string() {
-        let s = "\"holà\"\n}";
+    fn test_csv_round_trip() {
+        let fields = ["EoA-1", "a, \"quoted\" title", "line\nbreak", ""];
+        let row = fields
+            .iter()
+            .map(|f| to_csv_safe_string(f))
+            .collect::<Vec<_>>()
+            .join(",");
+        let content = format!("id,title,links,empty\r\n{}\r\n\r\n", row);
 
-        assert_eq!(to_json_safe_string(s), "\\\"holà\\\"\\n}");
+        assert_eq!(
+            parse_csv(&content),
+            Some(vec![
+                vec!["id".into(), "title".into(), "links".into(), "empty".into()],
+                fields.iter().map(|&f| f.into()).collect()
+            ])
+        );
+        assert_eq!(parse_csv("\"unclosed,field"), None);
     }
 }